@@ -0,0 +1,16 @@
+-- 書籍の全文検索用のカラムとインデックスを追加
+-- タイトル・著者・説明・ISBN（ハイフンあり・なし）を検索対象とする
+ALTER TABLE books
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(author, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(isbn, '')), 'A') ||
+        setweight(to_tsvector('simple', regexp_replace(coalesce(isbn, ''), '[^0-9Xx]', '', 'g')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS books_search_vector_idx ON books USING GIN (search_vector);
+
+-- 著者・所有者での絞り込み用のインデックス
+CREATE INDEX IF NOT EXISTS books_user_id_idx ON books (user_id);
+CREATE INDEX IF NOT EXISTS books_created_at_idx ON books (created_at DESC);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS books_isbn_idx;
+UPDATE books SET isbn = isbn_display WHERE isbn_display <> '';
+ALTER TABLE books DROP COLUMN IF EXISTS isbn_display;
@@ -0,0 +1,71 @@
+-- ISBNをハイフンなしのISBN-13に正規化して保存し、表示用の形式を別カラムに残す
+-- チェックディジットが一致しないなど、ISBNとして正しくない値は変換せずにそのまま残し、件数を報告する
+ALTER TABLE books ADD COLUMN isbn_display VARCHAR(255) NOT NULL DEFAULT '';
+UPDATE books SET isbn_display = isbn;
+
+-- ISBN-13の先頭12桁からチェックディジットを算出する（このマイグレーションの中でのみ使う）
+CREATE FUNCTION pg_temp.isbn13_check_digit(body TEXT) RETURNS TEXT AS $$
+    SELECT ((10 - SUM(
+        substr(body, i, 1)::int * CASE WHEN i % 2 = 1 THEN 1 ELSE 3 END
+    ) % 10) % 10)::text
+    FROM generate_series(1, 12) AS i
+$$ LANGUAGE sql IMMUTABLE;
+
+-- ISBN-10のチェックディジットが一致するかを返す。各桁に10から1までの重みを掛けた合計が11の倍数になる（Xは10を表す）
+CREATE FUNCTION pg_temp.isbn10_is_valid(digits TEXT) RETURNS BOOLEAN AS $$
+    SELECT SUM(
+        CASE WHEN substr(digits, i, 1) = 'X' THEN 10 ELSE substr(digits, i, 1)::int END * (11 - i)
+    ) % 11 = 0
+    FROM generate_series(1, 10) AS i
+$$ LANGUAGE sql IMMUTABLE;
+
+-- ISBN-13は、書籍用の978・979で始まりチェックディジットが一致するもののみハイフンを取り除く
+UPDATE books
+SET isbn = t.digits
+FROM (
+    SELECT book_id, regexp_replace(isbn, '[^0-9]', '', 'g') AS digits
+    FROM books
+) AS t
+WHERE books.book_id = t.book_id
+AND t.digits ~ '^97[89][0-9]{10}$'
+AND right(t.digits, 1) = pg_temp.isbn13_check_digit(t.digits);
+
+-- ISBN-10は、チェックディジットが一致するもののみ先頭に978を付け、チェックディジットを計算し直してISBN-13に変換する
+UPDATE books
+SET isbn = t.body || pg_temp.isbn13_check_digit(t.body)
+FROM (
+    SELECT book_id, '978' || left(digits, 9) AS body
+    FROM (
+        SELECT book_id, upper(regexp_replace(isbn, '[^0-9Xx]', '', 'g')) AS digits
+        FROM books
+    ) AS d
+    WHERE digits ~ '^[0-9]{9}[0-9X]$'
+    AND pg_temp.isbn10_is_valid(digits)
+) AS t
+WHERE books.book_id = t.book_id;
+
+-- 変換したISBNの表示用の形式は、アプリケーションと同じく接頭部の後にのみハイフンを入れる
+UPDATE books
+SET isbn_display = left(isbn, 3) || '-' || substr(isbn, 4)
+WHERE isbn ~ '^97[89][0-9]{10}$'
+AND right(isbn, 1) = pg_temp.isbn13_check_digit(isbn);
+
+-- 変換できなかったISBNの件数を報告する。該当する書籍は、正しいISBNに更新する必要がある
+DO $$
+DECLARE
+    invalid_count BIGINT;
+BEGIN
+    SELECT COUNT(*) INTO invalid_count
+    FROM books
+    WHERE NOT (
+        isbn ~ '^97[89][0-9]{10}$'
+        AND right(isbn, 1) = pg_temp.isbn13_check_digit(isbn)
+    );
+    IF invalid_count > 0 THEN
+        RAISE WARNING '% book(s) have an invalid ISBN and were left unchanged', invalid_count;
+    END IF;
+END
+$$;
+
+-- ISBNでの検索・重複チェック用のインデックス
+CREATE INDEX IF NOT EXISTS books_isbn_idx ON books (isbn);
@@ -0,0 +1,18 @@
+-- Add down migration script here
+
+ALTER TABLE returned_checkouts DROP COLUMN IF EXISTS copy_id;
+
+-- 1冊の書籍に複数の貸出がある場合は戻せないため、最も古い貸出のみ残す
+DELETE FROM checkouts AS c
+USING checkouts AS other
+WHERE c.book_id = other.book_id
+    AND (c.checked_out_at, c.checkout_id) > (other.checked_out_at, other.checkout_id);
+DROP INDEX IF EXISTS checkouts_book_id_idx;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_book_id_key UNIQUE (book_id);
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_copy_id_fkey;
+ALTER TABLE checkouts DROP COLUMN IF EXISTS copy_id;
+
+DROP TRIGGER IF EXISTS book_copies_updated_at_trigger ON book_copies;
+DROP TABLE IF EXISTS book_copies;
+DROP FUNCTION IF EXISTS next_book_copy_barcode();
+DROP SEQUENCE IF EXISTS book_copies_barcode_seq;
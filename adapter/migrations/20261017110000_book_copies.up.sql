@@ -0,0 +1,46 @@
+-- 書籍（タイトル）ごとに複数の物理的な蔵書を持てるように、book_copiesテーブルを作成する
+CREATE SEQUENCE IF NOT EXISTS book_copies_barcode_seq;
+
+-- バーコードが指定されない場合に使う連番のバーコードを採番する
+CREATE OR REPLACE FUNCTION next_book_copy_barcode() RETURNS VARCHAR AS '
+    SELECT ''C'' || lpad(nextval(''book_copies_barcode_seq'')::text, 8, ''0'');
+' LANGUAGE 'sql';
+
+CREATE TABLE IF NOT EXISTS book_copies (
+    copy_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    barcode VARCHAR(255) NOT NULL UNIQUE DEFAULT next_book_copy_barcode(),
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_copies_book_id_idx ON book_copies (book_id);
+
+-- book_copiesテーブルへのset_updated_atトリガー追加
+CREATE TRIGGER book_copies_updated_at_trigger
+    BEFORE UPDATE ON book_copies FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 既存の書籍は蔵書を1冊ずつ持つものとし、蔵書IDには書籍IDをそのまま使う
+INSERT INTO book_copies (copy_id, book_id, created_at)
+SELECT book_id, book_id, created_at FROM books ORDER BY created_at;
+
+-- 貸出は書籍ではなく蔵書単位で行う
+ALTER TABLE checkouts ADD COLUMN copy_id UUID;
+UPDATE checkouts SET copy_id = book_id;
+ALTER TABLE checkouts ALTER COLUMN copy_id SET NOT NULL;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_key UNIQUE (copy_id);
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_fkey
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE;
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_book_id_key;
+CREATE INDEX IF NOT EXISTS checkouts_book_id_idx ON checkouts (book_id);
+
+ALTER TABLE returned_checkouts ADD COLUMN copy_id UUID;
+UPDATE returned_checkouts SET copy_id = book_id;
+ALTER TABLE returned_checkouts ALTER COLUMN copy_id SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS book_tags;
+DROP TABLE IF EXISTS tags;
@@ -0,0 +1,25 @@
+-- 書籍を分類するためのタグを管理するtagsテーブルの作成
+-- タグ名は小文字に正規化して保存する
+CREATE TABLE IF NOT EXISTS tags (
+    tag_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(50) NOT NULL UNIQUE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+-- 書籍とタグの多対多の関連を管理するbook_tagsテーブルの作成
+CREATE TABLE IF NOT EXISTS book_tags (
+    book_id UUID NOT NULL,
+    tag_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    PRIMARY KEY (book_id, tag_id),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(tag_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- タグによる絞り込み用のインデックス
+CREATE INDEX IF NOT EXISTS book_tags_tag_id_idx ON book_tags (tag_id);
@@ -0,0 +1,2 @@
+-- 書影の画像形式。画像本体はBlobストレージに保存し、未登録の場合はNULLとする
+ALTER TABLE books ADD COLUMN cover_content_type VARCHAR(50);
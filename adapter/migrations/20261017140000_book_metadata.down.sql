@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+ALTER TABLE books DROP COLUMN IF EXISTS page_count;
+ALTER TABLE books DROP COLUMN IF EXISTS published_on;
+ALTER TABLE books DROP COLUMN IF EXISTS publisher;
@@ -0,0 +1,4 @@
+-- ISBNから補完できる書誌情報。手入力の場合は未設定でもよい
+ALTER TABLE books ADD COLUMN publisher VARCHAR(255);
+ALTER TABLE books ADD COLUMN published_on DATE;
+ALTER TABLE books ADD COLUMN page_count INTEGER CHECK (page_count > 0);
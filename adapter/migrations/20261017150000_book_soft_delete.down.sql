@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS books_deleted_at_idx;
+DELETE FROM books WHERE deleted_at IS NOT NULL;
+ALTER TABLE books DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,4 @@
+-- 削除された書籍は行を残したまま削除日時を記録し、一覧や取得の対象から外す
+ALTER TABLE books ADD COLUMN deleted_at TIMESTAMP(3) WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS books_deleted_at_idx ON books (deleted_at) WHERE deleted_at IS NOT NULL;
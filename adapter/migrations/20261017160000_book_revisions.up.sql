@@ -0,0 +1,20 @@
+-- 書籍の変更履歴を管理するbook_revisionsテーブルの作成
+-- 変更前後の項目の値をJSONで保存する
+CREATE TABLE IF NOT EXISTS book_revisions (
+    revision_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    -- 変更したユーザー。ユーザーが削除されても履歴は残す
+    user_id UUID,
+    before JSONB NOT NULL,
+    after JSONB NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_revisions_book_id_created_at_idx ON book_revisions (book_id, created_at);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+ALTER TABLE books DROP COLUMN IF EXISTS version;
@@ -0,0 +1,2 @@
+-- 書籍の書誌情報の版。楽観的排他制御のため、内容を変更するたびに1ずつ増やす
+ALTER TABLE books ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
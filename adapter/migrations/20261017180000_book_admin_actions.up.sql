@@ -0,0 +1,24 @@
+-- 管理者が所有者以外の書籍を更新・削除した記録を管理するbook_admin_actionsテーブルの作成
+-- 所有者が自分の書籍に対する管理者の操作を確認できるようにする
+CREATE TABLE IF NOT EXISTS book_admin_actions (
+    admin_action_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    -- 操作された時点の書籍の所有者
+    owner_id UUID NOT NULL,
+    -- 操作した管理者。管理者のユーザーが削除されても記録は残す
+    admin_id UUID,
+    action VARCHAR(20) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (owner_id) REFERENCES users(user_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (admin_id) REFERENCES users(user_id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_admin_actions_owner_id_created_at_idx ON book_admin_actions (owner_id, created_at);
@@ -0,0 +1,22 @@
+-- 譲渡先の承認を待っている書籍の譲渡を管理するbook_transfersテーブルの作成
+-- 承認・拒否された譲渡は削除する。承認待ちの譲渡は書籍ごとに1件までとする
+CREATE TABLE IF NOT EXISTS book_transfers (
+    book_transfer_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL UNIQUE,
+    -- 譲渡を依頼した時点の所有者
+    from_user_id UUID NOT NULL,
+    to_user_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (from_user_id) REFERENCES users(user_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (to_user_id) REFERENCES users(user_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_transfers_to_user_id_idx ON book_transfers (to_user_id);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS books_location_id_idx;
+ALTER TABLE books DROP COLUMN IF EXISTS location_id;
+DROP TRIGGER IF EXISTS locations_updated_at_trigger ON locations;
+DROP TABLE IF EXISTS locations;
@@ -0,0 +1,23 @@
+-- 書籍を置いている場所（拠点・部屋・棚）を管理するlocationsテーブルの作成
+CREATE TABLE IF NOT EXISTS locations (
+    location_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    site VARCHAR(255) NOT NULL,
+    room VARCHAR(255) NOT NULL,
+    shelf VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    UNIQUE (site, room, shelf)
+);
+
+CREATE TRIGGER locations_updated_at_trigger
+    BEFORE UPDATE ON locations FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 書籍の置き場所。未設定の書籍はNULLとし、書籍が置かれている場所は削除できない
+ALTER TABLE books ADD COLUMN location_id UUID
+    REFERENCES locations(location_id)
+    ON DELETE RESTRICT
+    ON UPDATE CASCADE;
+
+CREATE INDEX IF NOT EXISTS books_location_id_idx ON books (location_id);
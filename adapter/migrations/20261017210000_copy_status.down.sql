@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+ALTER TABLE returned_checkouts DROP COLUMN IF EXISTS outcome;
+DROP TABLE IF EXISTS book_copy_status_changes;
+ALTER TABLE book_copies DROP COLUMN IF EXISTS status;
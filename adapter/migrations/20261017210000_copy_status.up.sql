@@ -0,0 +1,28 @@
+-- 蔵書の状態（available: 貸出可能、in-repair: 修理中、lost: 紛失、damaged: 破損、withdrawn: 除籍）
+-- 貸出できるのはavailableの蔵書のみ
+ALTER TABLE book_copies ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'available';
+
+-- 蔵書の状態の変更履歴を管理するbook_copy_status_changesテーブルの作成
+CREATE TABLE IF NOT EXISTS book_copy_status_changes (
+    status_change_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    copy_id UUID NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    note TEXT NOT NULL,
+    -- 変更したユーザー。ユーザーが削除されても履歴は残す
+    changed_by UUID,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(user_id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_copy_status_changes_copy_id_created_at_idx ON book_copy_status_changes (copy_id, created_at);
+
+-- 貸出の終わり方（returned: 返却、lost: 紛失）
+ALTER TABLE returned_checkouts ADD COLUMN outcome VARCHAR(20) NOT NULL DEFAULT 'returned';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS book_reviews_updated_at_trigger ON book_reviews;
+DROP TABLE IF EXISTS book_reviews;
@@ -0,0 +1,59 @@
+-- ユーザーがお気に入りに登録した書籍を管理するfavoritesテーブルの作成
+CREATE TABLE IF NOT EXISTS favorites (
+    user_id UUID NOT NULL,
+    book_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    PRIMARY KEY (user_id, book_id),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+-- まだ登録されていない書籍のほしいものリストを管理するwishlist_itemsテーブルの作成
+-- ISBNと書名の少なくともどちらかを指定する
+-- ISBNが一致する書籍が登録されると、その書籍をfulfilled_book_idに記録する
+CREATE TABLE IF NOT EXISTS wishlist_items (
+    wishlist_item_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    isbn VARCHAR(255),
+    isbn_display VARCHAR(255),
+    title VARCHAR(255),
+    fulfilled_book_id UUID,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    CHECK (isbn IS NOT NULL OR title IS NOT NULL),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (fulfilled_book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS wishlist_items_user_id_idx ON wishlist_items (user_id);
+CREATE INDEX IF NOT EXISTS wishlist_items_isbn_idx ON wishlist_items (isbn)
+    WHERE fulfilled_book_id IS NULL;
+
+-- ユーザーへの通知を管理するnotificationsテーブルの作成
+CREATE TABLE IF NOT EXISTS notifications (
+    notification_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    book_id UUID,
+    message TEXT NOT NULL,
+    read_at TIMESTAMP(3) WITH TIME ZONE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS notifications_user_id_idx ON notifications (user_id, created_at);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS purchase_request_votes;
+DROP TABLE IF EXISTS purchase_requests;
@@ -0,0 +1,48 @@
+-- 書籍の購入リクエストを管理するpurchase_requestsテーブルの作成
+-- 状態（open: 受付中、approved: 承認済み、ordered: 発注済み、received: 受領済み、rejected: 却下）と、
+-- 各状態になった日時を記録する。受領すると書籍を登録し、その書籍をbook_idに記録する
+CREATE TABLE IF NOT EXISTS purchase_requests (
+    purchase_request_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- リクエストしたユーザー。ユーザーが削除されてもリクエストは残す
+    user_id UUID,
+    isbn VARCHAR(255) NOT NULL,
+    isbn_display VARCHAR(255) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    author VARCHAR(255) NOT NULL,
+    reason TEXT NOT NULL,
+    link TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'open',
+    book_id UUID,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    approved_at TIMESTAMP(3) WITH TIME ZONE,
+    ordered_at TIMESTAMP(3) WITH TIME ZONE,
+    received_at TIMESTAMP(3) WITH TIME ZONE,
+    rejected_at TIMESTAMP(3) WITH TIME ZONE,
+
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL,
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL
+);
+
+-- 同じISBNの購入リクエストは、受領・却下されるまで1件までとする
+CREATE UNIQUE INDEX IF NOT EXISTS purchase_requests_isbn_active_idx ON purchase_requests (isbn)
+    WHERE status NOT IN ('received', 'rejected');
+CREATE INDEX IF NOT EXISTS purchase_requests_status_idx ON purchase_requests (status, created_at);
+
+-- 購入リクエストへの賛成票を管理するpurchase_request_votesテーブルの作成
+CREATE TABLE IF NOT EXISTS purchase_request_votes (
+    purchase_request_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    PRIMARY KEY (purchase_request_id, user_id),
+    FOREIGN KEY (purchase_request_id) REFERENCES purchase_requests(purchase_request_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
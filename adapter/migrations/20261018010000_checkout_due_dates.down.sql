@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS checkouts_due_at_idx;
+ALTER TABLE returned_checkouts DROP COLUMN IF EXISTS due_at;
+ALTER TABLE checkouts DROP COLUMN IF EXISTS due_at;
+ALTER TABLE books DROP COLUMN IF EXISTS loan_period_days;
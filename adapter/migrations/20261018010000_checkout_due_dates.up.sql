@@ -0,0 +1,18 @@
+-- 書籍ごとの貸出期間（日数）。NULLの場合は設定の既定値を使う
+ALTER TABLE books ADD COLUMN loan_period_days INTEGER CHECK (loan_period_days > 0);
+
+-- 貸出の返却期限。既存の貸出は既定の貸出期間（14日）で埋める
+-- NOTE: マイグレーションからは環境変数を参照できないため、CHECKOUT_LOAN_PERIOD_DAYSで
+-- 14日以外を設定している環境では、既存の貸出の返却期限が設定と異なる値になる。
+-- その場合はマイグレーションの後に、設定した日数で次のように埋め直すこと（30日の例）
+--   UPDATE checkouts SET due_at = checked_out_at + INTERVAL '30 days';
+--   UPDATE returned_checkouts SET due_at = checked_out_at + INTERVAL '30 days';
+ALTER TABLE checkouts ADD COLUMN due_at TIMESTAMP(3) WITH TIME ZONE;
+UPDATE checkouts SET due_at = checked_out_at + INTERVAL '14 days';
+ALTER TABLE checkouts ALTER COLUMN due_at SET NOT NULL;
+
+ALTER TABLE returned_checkouts ADD COLUMN due_at TIMESTAMP(3) WITH TIME ZONE;
+UPDATE returned_checkouts SET due_at = checked_out_at + INTERVAL '14 days';
+ALTER TABLE returned_checkouts ALTER COLUMN due_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS checkouts_due_at_idx ON checkouts (due_at);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS checkout_renewals_checkout_id_idx;
+DROP TABLE IF EXISTS checkout_renewals;
@@ -0,0 +1,12 @@
+-- 貸出の延長を記録するcheckout_renewalsテーブルの作成
+-- 返却後も延長の回数を履歴に残すため、checkout_idはcheckoutsテーブルとreturned_checkoutsテーブルのどちらかを指す
+CREATE TABLE IF NOT EXISTS checkout_renewals (
+    checkout_renewal_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    checkout_id UUID NOT NULL,
+    -- 延長前と延長後の返却期限
+    previous_due_at TIMESTAMP(3) WITH TIME ZONE NOT NULL,
+    due_at TIMESTAMP(3) WITH TIME ZONE NOT NULL,
+    renewed_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+CREATE INDEX IF NOT EXISTS checkout_renewals_checkout_id_idx ON checkout_renewals (checkout_id);
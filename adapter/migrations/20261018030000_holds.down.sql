@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS holds_expires_at_idx;
+DROP INDEX IF EXISTS holds_book_id_created_at_idx;
+DROP TABLE IF EXISTS holds;
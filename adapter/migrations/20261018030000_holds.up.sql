@@ -0,0 +1,24 @@
+-- 貸出中の書籍の予約を管理するholdsテーブルの作成
+-- 予約は作成日時の順に並び、蔵書が返却されると先頭の予約から取り置きになる
+-- 借りた・取り消した・受け取りの期限を過ぎた予約は削除する
+CREATE TABLE IF NOT EXISTS holds (
+    hold_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    -- 取り置きを始めた日時と受け取りの期限。順番待ちの間はNULL
+    ready_at TIMESTAMP(3) WITH TIME ZONE,
+    expires_at TIMESTAMP(3) WITH TIME ZONE,
+
+    UNIQUE (book_id, user_id),
+    CHECK ((ready_at IS NULL) = (expires_at IS NULL)),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS holds_book_id_created_at_idx ON holds (book_id, created_at);
+CREATE INDEX IF NOT EXISTS holds_expires_at_idx ON holds (expires_at);
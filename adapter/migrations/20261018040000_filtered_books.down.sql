@@ -0,0 +1,3 @@
+-- Add down migration script here
+
+DROP FUNCTION IF EXISTS filtered_books(TEXT, TEXT, UUID, BOOLEAN, TEXT[], UUID, UUID, TEXT);
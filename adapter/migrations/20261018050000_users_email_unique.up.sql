@@ -0,0 +1,3 @@
+-- ログインに使うメールアドレスが、同時の登録・変更で重複しないよう一意にする
+-- NOTE: すでに重複しているメールアドレスがある場合は、適用前に変更しておく必要がある
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_key ON users (email);
@@ -0,0 +1,4 @@
+-- 書影を登録するたびに発行するID。画像本体はこのIDを含むキーでBlobストレージに保存する
+-- 登録し直しに失敗しても、前の書影の画像を上書きしないようにする
+-- このカラムを追加する前に登録された書影はNULLとし、IDを含まないキーで保存されている
+ALTER TABLE books ADD COLUMN cover_id UUID;
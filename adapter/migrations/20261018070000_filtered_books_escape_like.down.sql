@@ -0,0 +1,82 @@
+-- Add down migration script here
+
+CREATE OR REPLACE FUNCTION filtered_books(
+    q TEXT,
+    author_query TEXT,
+    owner_id UUID,
+    unavailable BOOLEAN,
+    tag_names TEXT[],
+    location UUID,
+    favorited_by UUID,
+    sort_key TEXT
+) RETURNS TABLE (
+    book_id UUID,
+    text_key TEXT,
+    time_key TIMESTAMP(3) WITH TIME ZONE,
+    num_key FLOAT8
+) AS $$
+    SELECT
+        b.book_id,
+        CASE sort_key
+            WHEN 'title' THEN b.title
+            WHEN 'author' THEN b.author
+            ELSE ''
+        END::text,
+        CASE sort_key
+            WHEN 'created_at' THEN b.created_at
+            WHEN 'updated_at' THEN b.updated_at
+            ELSE 'epoch'::timestamptz
+        END,
+        CASE sort_key
+            WHEN 'most_borrowed' THEN (
+                (SELECT COUNT(*) FROM checkouts AS c WHERE c.book_id = b.book_id)
+                + (SELECT COUNT(*) FROM returned_checkouts AS rc WHERE rc.book_id = b.book_id)
+            )::float8
+            WHEN 'rating' THEN COALESCE(
+                (SELECT AVG(br.rating) FROM book_reviews AS br WHERE br.book_id = b.book_id),
+                0
+            )::float8
+            WHEN 'relevance' THEN
+                ts_rank(b.search_vector, websearch_to_tsquery('simple', q))::float8
+            ELSE 0
+        END
+    FROM books AS b
+    WHERE b.deleted_at IS NULL
+    AND (
+        q IS NULL
+        OR b.search_vector @@ websearch_to_tsquery('simple', q)
+        OR b.title ILIKE '%' || q || '%'
+        OR b.author ILIKE '%' || q || '%'
+    )
+    AND (author_query IS NULL OR b.author ILIKE '%' || author_query || '%')
+    AND (owner_id IS NULL OR b.user_id = owner_id)
+    -- 貸出可能な蔵書がないかどうかで絞り込む
+    AND (
+        unavailable IS NULL
+        OR unavailable = NOT EXISTS (
+            SELECT 1 FROM book_copies AS bc
+            WHERE bc.book_id = b.book_id
+            AND bc.status = 'available'
+            AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+        )
+    )
+    -- 指定したタグをすべて持つ書籍に絞り込む
+    AND (
+        cardinality(tag_names) = 0
+        OR (
+            SELECT COUNT(*) FROM book_tags AS bt
+            INNER JOIN tags AS t USING(tag_id)
+            WHERE bt.book_id = b.book_id AND t.name = ANY(tag_names)
+        ) = cardinality(tag_names)
+    )
+    AND (location IS NULL OR b.location_id = location)
+    AND (
+        favorited_by IS NULL
+        OR EXISTS (
+            SELECT 1 FROM favorites AS f
+            WHERE f.book_id = b.book_id AND f.user_id = favorited_by
+        )
+    )
+$$ LANGUAGE sql STABLE;
+
+DROP FUNCTION IF EXISTS escape_like(TEXT);
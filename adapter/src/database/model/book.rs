@@ -1,43 +1,337 @@
 use kernel::model::{
-    book::{Book, Checkout},
-    id::{BookId, CheckoutId, UserId},
-    user::{BookOwner, CheckoutUser},
+    book::{
+        Book, BookAdminAction, BookAdminActionKind, BookCatalogEntry, BookCopy, BookRating,
+        BookRevision, BookSnapshot, BookTransfer, Checkout, CopyStatus, CopyStatusAction,
+        CopyStatusChange, CoverImageType, TagCount,
+    },
+    id::{
+        BookAdminActionId, BookId, BookRevisionId, BookTransferId, CheckoutId, CopyId,
+        CopyStatusChangeId, LocationId, UserId,
+    },
+    isbn::Isbn,
+    location::Location,
+    user::{BookEditor, BookOwner, CheckoutUser},
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use shared::error::AppError;
+use sqlx::types::Json;
 
 pub struct BookRow {
     pub book_id: BookId,
     pub title: String,
     pub author: String,
     pub isbn: String,
+    pub isbn_display: String,
     pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
     pub owned_by: UserId,
     pub owner_name: String,
+    pub cover_content_type: Option<String>,
+    pub version: i32,
+    pub loan_period_days: Option<i32>,
+    pub location_id: Option<LocationId>,
+    pub location_site: Option<String>,
+    pub location_room: Option<String>,
+    pub location_shelf: Option<String>,
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
 }
 
 impl BookRow {
-    pub fn into_book(self, checkout: Option<Checkout>) -> Book {
+    pub fn into_book(self, copies: Vec<BookCopy>, tags: Vec<String>) -> Book {
         let BookRow {
             book_id,
             title,
             author,
             isbn,
+            isbn_display,
             description,
+            publisher,
+            published_on,
+            page_count,
             owned_by,
             owner_name,
+            cover_content_type,
+            version,
+            loan_period_days,
+            location_id,
+            location_site,
+            location_room,
+            location_shelf,
+            average_rating,
+            review_count,
         } = self;
+        // 置き場所はLEFT OUTER JOINで取得するため、すべての列がそろった場合のみ設定する
+        let location = match (location_id, location_site, location_room, location_shelf) {
+            (Some(id), Some(site), Some(room), Some(shelf)) => Some(Location {
+                id,
+                site,
+                room,
+                shelf,
+            }),
+            _ => None,
+        };
         Book {
             id: book_id,
             title,
             author,
-            isbn,
+            isbn: Isbn::from_stored(isbn, isbn_display),
             description,
+            publisher,
+            published_on,
+            page_count,
             owner: BookOwner {
                 id: owned_by,
                 name: owner_name,
             },
-            checkout,
+            copies,
+            tags,
+            cover: cover_content_type.and_then(|c| c.parse::<CoverImageType>().ok()),
+            version,
+            location,
+            rating: BookRating {
+                average: average_rating,
+                count: review_count,
+            },
+            loan_period_days,
+        }
+    }
+}
+
+/// 書籍の編集可能な項目の値を格納する型
+/// 変更履歴ではJSONとして保存する
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BookSnapshotRow {
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub isbn_display: String,
+    pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
+}
+
+impl From<BookSnapshotRow> for BookSnapshot {
+    fn from(value: BookSnapshotRow) -> Self {
+        let BookSnapshotRow {
+            title,
+            author,
+            isbn,
+            isbn_display,
+            description,
+            publisher,
+            published_on,
+            page_count,
+        } = value;
+        BookSnapshot {
+            title,
+            author,
+            isbn: Isbn::from_stored(isbn, isbn_display),
+            description,
+            publisher,
+            published_on,
+            page_count,
+        }
+    }
+}
+
+impl From<BookSnapshot> for BookSnapshotRow {
+    fn from(value: BookSnapshot) -> Self {
+        let BookSnapshot {
+            title,
+            author,
+            isbn,
+            description,
+            publisher,
+            published_on,
+            page_count,
+        } = value;
+        BookSnapshotRow {
+            title,
+            author,
+            isbn: isbn.as_isbn13().into(),
+            isbn_display: isbn.display().into(),
+            description,
+            publisher,
+            published_on,
+            page_count,
+        }
+    }
+}
+
+/// 書籍の変更履歴を格納する型
+pub struct BookRevisionRow {
+    pub revision_id: BookRevisionId,
+    pub book_id: BookId,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub before: Json<BookSnapshotRow>,
+    pub after: Json<BookSnapshotRow>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<BookRevisionRow> for BookRevision {
+    fn from(value: BookRevisionRow) -> Self {
+        let BookRevisionRow {
+            revision_id,
+            book_id,
+            user_id,
+            user_name,
+            before,
+            after,
+            created_at,
+        } = value;
+        BookRevision {
+            id: revision_id,
+            book_id,
+            changed_by: user_id
+                .zip(user_name)
+                .map(|(id, name)| BookEditor { id, name }),
+            changed_at: created_at,
+            before: before.0.into(),
+            after: after.0.into(),
+        }
+    }
+}
+
+/// 管理者による書籍の操作の記録を格納する型
+pub struct BookAdminActionRow {
+    pub admin_action_id: BookAdminActionId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub action: String,
+    pub admin_id: Option<UserId>,
+    pub admin_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<BookAdminActionRow> for BookAdminAction {
+    type Error = AppError;
+
+    fn try_from(value: BookAdminActionRow) -> Result<Self, Self::Error> {
+        let BookAdminActionRow {
+            admin_action_id,
+            book_id,
+            book_title,
+            action,
+            admin_id,
+            admin_name,
+            created_at,
+        } = value;
+        Ok(BookAdminAction {
+            id: admin_action_id,
+            book_id,
+            book_title,
+            action: action
+                .parse::<BookAdminActionKind>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            admin: admin_id
+                .zip(admin_name)
+                .map(|(id, name)| BookEditor { id, name }),
+            acted_at: created_at,
+        })
+    }
+}
+
+/// 蔵書の状態を変える前に、権限と現在の状態を確認するための型
+pub struct CopyStateRow {
+    pub owned_by: UserId,
+    pub status: String,
+    pub checkout_id: Option<CheckoutId>,
+    pub borrowed_by: Option<UserId>,
+}
+
+/// 蔵書の状態の変更履歴を格納する型
+pub struct CopyStatusChangeRow {
+    pub status_change_id: CopyStatusChangeId,
+    pub copy_id: CopyId,
+    pub action: String,
+    pub from_status: String,
+    pub to_status: String,
+    pub note: String,
+    pub changed_by: Option<UserId>,
+    pub changed_by_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<CopyStatusChangeRow> for CopyStatusChange {
+    type Error = AppError;
+
+    fn try_from(value: CopyStatusChangeRow) -> Result<Self, Self::Error> {
+        let CopyStatusChangeRow {
+            status_change_id,
+            copy_id,
+            action,
+            from_status,
+            to_status,
+            note,
+            changed_by,
+            changed_by_name,
+            created_at,
+        } = value;
+        let status = |s: String| {
+            s.parse::<CopyStatus>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+        };
+        Ok(CopyStatusChange {
+            id: status_change_id,
+            copy_id,
+            action: action
+                .parse::<CopyStatusAction>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            from: status(from_status)?,
+            to: status(to_status)?,
+            note,
+            changed_by: changed_by
+                .zip(changed_by_name)
+                .map(|(id, name)| BookEditor { id, name }),
+            changed_at: created_at,
+        })
+    }
+}
+
+/// 承認待ちの書籍の譲渡を格納する型
+pub struct BookTransferRow {
+    pub book_transfer_id: BookTransferId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub from_user_id: UserId,
+    pub from_user_name: String,
+    pub to_user_id: UserId,
+    pub to_user_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<BookTransferRow> for BookTransfer {
+    fn from(value: BookTransferRow) -> Self {
+        let BookTransferRow {
+            book_transfer_id,
+            book_id,
+            book_title,
+            from_user_id,
+            from_user_name,
+            to_user_id,
+            to_user_name,
+            created_at,
+        } = value;
+        BookTransfer {
+            id: book_transfer_id,
+            book_id,
+            book_title,
+            from: BookOwner {
+                id: from_user_id,
+                name: from_user_name,
+            },
+            to: BookOwner {
+                id: to_user_id,
+                name: to_user_name,
+            },
+            requested_at: created_at,
         }
     }
 }
@@ -48,33 +342,172 @@ pub struct PaginatedBookRow {
     pub id: BookId,
 }
 
-/// 貸出情報を格納する型
-pub struct BookCheckoutRow {
-    pub checkout_id: CheckoutId,
+/// 削除済みの書籍のページネーション用のadapter内部の型
+pub struct DeletedBookRow {
+    pub total: i64,
+    pub id: BookId,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// カーソルによるページネーション用のadapter内部の型
+/// 並び替えに使ったキーを次のページのカーソルにするため保持する
+pub struct BookKeyRow {
+    pub book_id: BookId,
+    pub text_key: String,
+    pub time_key: DateTime<Utc>,
+    pub num_key: f64,
+}
+
+/// 蔵書とその貸出情報を格納する型
+pub struct BookCopyRow {
+    pub copy_id: CopyId,
     pub book_id: BookId,
-    pub user_id: UserId,
-    pub user_name: String,
-    pub checked_out_at: DateTime<Utc>,
+    pub barcode: String,
+    pub status: String,
+    pub checkout_id: Option<CheckoutId>,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub checked_out_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    /// 貸出中でない場合は0
+    pub renewal_count: i64,
 }
 
-/// Checkout型に変換するFromトレイトの実装
-impl From<BookCheckoutRow> for Checkout {
-    fn from(value: BookCheckoutRow) -> Self {
-        let BookCheckoutRow {
+/// BookCopy型に変換するTryFromトレイトの実装
+impl TryFrom<BookCopyRow> for BookCopy {
+    type Error = AppError;
+
+    fn try_from(value: BookCopyRow) -> Result<Self, Self::Error> {
+        let BookCopyRow {
+            copy_id,
+            barcode,
+            status,
             checkout_id,
             user_id,
             user_name,
             checked_out_at,
+            due_at,
+            renewal_count,
             ..
         } = value;
 
-        Checkout {
+        // 貸出中の場合のみ貸出情報の列がすべて埋まる
+        let checkout = match (checkout_id, user_id, user_name, checked_out_at, due_at) {
+            (Some(checkout_id), Some(id), Some(name), Some(checked_out_at), Some(due_at)) => {
+                Some(Checkout {
+                    checkout_id,
+                    checked_out_by: CheckoutUser { id, name },
+                    checked_out_at,
+                    due_at,
+                    renewal_count,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(BookCopy {
+            id: copy_id,
+            barcode,
+            status: status
+                .parse::<CopyStatus>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            checkout,
+        })
+    }
+}
+
+/// 書籍の目録を1行ずつ取得する際に使う型
+pub struct BookCatalogRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub isbn_display: String,
+    pub description: String,
+    pub owned_by: UserId,
+    pub owner_name: String,
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
+    pub copy_status: Option<String>,
+    pub checkout_id: Option<CheckoutId>,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub checked_out_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub renewal_count: i64,
+}
+
+impl TryFrom<BookCatalogRow> for BookCatalogEntry {
+    type Error = AppError;
+
+    fn try_from(value: BookCatalogRow) -> Result<Self, Self::Error> {
+        let BookCatalogRow {
+            book_id,
+            title,
+            author,
+            isbn,
+            isbn_display,
+            description,
+            owned_by,
+            owner_name,
+            copy_id,
+            barcode,
+            copy_status,
             checkout_id,
-            checked_out_by: CheckoutUser {
-                id: user_id,
-                name: user_name,
-            },
+            user_id,
+            user_name,
             checked_out_at,
-        }
+            due_at,
+            renewal_count,
+        } = value;
+        // 蔵書の列はLEFT OUTER JOINのため、蔵書がない書籍では空になる
+        let copy = match (copy_id, barcode, copy_status) {
+            (Some(copy_id), Some(barcode), Some(status)) => {
+                Some(BookCopy::try_from(BookCopyRow {
+                    copy_id,
+                    book_id,
+                    barcode,
+                    status,
+                    checkout_id,
+                    user_id,
+                    user_name,
+                    checked_out_at,
+                    due_at,
+                    renewal_count,
+                })?)
+            }
+            _ => None,
+        };
+        Ok(BookCatalogEntry {
+            book_id,
+            title,
+            author,
+            isbn: Isbn::from_stored(isbn, isbn_display),
+            description,
+            owner: BookOwner {
+                id: owned_by,
+                name: owner_name,
+            },
+            copy,
+        })
+    }
+}
+
+/// 書籍に付いたタグを格納する型
+pub struct BookTagRow {
+    pub book_id: BookId,
+    pub name: String,
+}
+
+/// タグごとの書籍数を格納する型
+pub struct TagCountRow {
+    pub name: String,
+    pub book_count: i64,
+}
+
+impl From<TagCountRow> for TagCount {
+    fn from(value: TagCountRow) -> Self {
+        let TagCountRow { name, book_count } = value;
+        TagCount { name, book_count }
     }
 }
@@ -1,25 +1,59 @@
 use kernel::model::{
-    checkout::{Checkout, CheckoutBook},
-    id::{BookId, CheckoutId, UserId},
+    book::CopyStatus,
+    checkout::{Checkout, CheckoutBook, CheckoutOutcome},
+    id::{BookId, CheckoutId, CopyId, UserId},
 };
+use shared::error::AppError;
 use sqlx::types::chrono::{DateTime, Utc};
 
 /// 貸出状態を確認するための型
 pub struct CheckoutStateRow {
     pub book_id: BookId,
+    pub copy_id: Option<CopyId>,
     pub checkout_id: Option<CheckoutId>,
     pub user_id: Option<UserId>,
 }
 
+/// 貸出を延長できるかを確認するための型
+pub struct CheckoutRenewalStateRow {
+    pub book_id: BookId,
+    pub checkout_id: Option<CheckoutId>,
+    pub user_id: Option<UserId>,
+    pub renewal_count: i64,
+    pub held_by_others: bool,
+}
+
+/// 貸し出す蔵書を選ぶ際に使う型
+pub struct CheckoutCandidateRow {
+    pub book_id: BookId,
+    pub copy_id: Option<CopyId>,
+    pub copy_status: Option<String>,
+    pub checkout_id: Option<CheckoutId>,
+}
+
+impl CheckoutCandidateRow {
+    /// 蔵書が貸し出せる状態かどうかを返す
+    pub fn is_lendable(&self) -> bool {
+        self.copy_status
+            .as_deref()
+            .and_then(|s| s.parse::<CopyStatus>().ok())
+            .is_some_and(CopyStatus::is_lendable)
+    }
+}
+
 /// 貸出中の一覧を取得する際に使う型
 pub struct CheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    pub renewal_count: i64,
     pub title: String,
     pub author: String,
     pub isbn: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
 }
 
 impl From<CheckoutRow> for Checkout {
@@ -29,20 +63,29 @@ impl From<CheckoutRow> for Checkout {
             book_id,
             user_id,
             checked_out_at,
+            due_at,
+            renewal_count,
             title,
             author,
             isbn,
+            copy_id,
+            barcode,
         } = value;
         Self {
             id: checkout_id,
             checked_out_by: user_id,
             checked_out_at,
+            due_at,
+            renewal_count,
             returned_at: None, // 返却日時は未設定
+            outcome: None,
             book: CheckoutBook {
                 id: book_id,
                 title,
                 author,
                 isbn,
+                copy_id,
+                barcode,
             },
         }
     }
@@ -54,35 +97,56 @@ pub struct ReturnedCheckoutRow {
     pub book_id: BookId,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    pub renewal_count: i64,
     pub returned_at: DateTime<Utc>,
+    pub outcome: String,
     pub title: String,
     pub author: String,
     pub isbn: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
 }
 
-impl From<ReturnedCheckoutRow> for Checkout {
-    fn from(value: ReturnedCheckoutRow) -> Self {
+impl TryFrom<ReturnedCheckoutRow> for Checkout {
+    type Error = AppError;
+
+    fn try_from(value: ReturnedCheckoutRow) -> Result<Self, Self::Error> {
         let ReturnedCheckoutRow {
             checkout_id,
             book_id,
             user_id,
             checked_out_at,
+            due_at,
+            renewal_count,
             returned_at,
+            outcome,
             title,
             author,
             isbn,
+            copy_id,
+            barcode,
         } = value;
-        Self {
+        Ok(Self {
             id: checkout_id,
             checked_out_by: user_id,
             checked_out_at,
+            due_at,
+            renewal_count,
             returned_at: Some(returned_at),
+            outcome: Some(
+                outcome
+                    .parse::<CheckoutOutcome>()
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            ),
             book: CheckoutBook {
                 id: book_id,
                 title,
                 author,
                 isbn,
+                copy_id,
+                barcode,
             },
-        }
+        })
     }
 }
@@ -0,0 +1,52 @@
+//! カーソルによるページネーションで使うカーソルの定義
+//! カーソルは最後に返した行の並び替えのキーを保持し、
+//! JSONをBase64エンコードした文字列としてクライアントに渡す
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use shared::error::{AppError, AppResult};
+
+use kernel::model::id::{BookId, CheckoutId, UserId};
+
+/// カーソルを不透明な文字列に変換する
+pub fn encode_cursor<T: Serialize>(cursor: &T) -> String {
+    // 自前で定義した構造体のシリアライズは失敗しない
+    let json = serde_json::to_vec(cursor).unwrap_or_default();
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+/// クライアントから受け取った文字列をカーソルに戻す
+pub fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> AppResult<T> {
+    let json = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| AppError::InvalidCursorError)?;
+    serde_json::from_slice(&json).map_err(|_| AppError::InvalidCursorError)
+}
+
+/// 書籍一覧のカーソル
+/// 並び順ごとに文字列・日時・数値のキーを持ち、使わないキーには固定値が入る
+#[derive(Serialize, Deserialize)]
+pub struct BookCursor {
+    /// カーソルを作成したときの並び替えのキーと昇順かどうか
+    pub sort: String,
+    pub asc: bool,
+    pub text_key: String,
+    pub time_key: DateTime<Utc>,
+    pub num_key: f64,
+    pub book_id: BookId,
+}
+
+/// ユーザー一覧のカーソル
+#[derive(Serialize, Deserialize)]
+pub struct UserCursor {
+    pub created_at: DateTime<Utc>,
+    pub user_id: UserId,
+}
+
+/// 貸出一覧のカーソル
+#[derive(Serialize, Deserialize)]
+pub struct CheckoutCursor {
+    pub checked_out_at: DateTime<Utc>,
+    pub checkout_id: CheckoutId,
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    hold::{Hold, HoldBook},
+    id::{BookId, HoldId},
+};
+
+/// 予約を順番とともに取得する際に使う型
+pub struct HoldRow {
+    pub hold_id: HoldId,
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub ready_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<HoldRow> for Hold {
+    fn from(value: HoldRow) -> Self {
+        let HoldRow {
+            hold_id,
+            book_id,
+            title,
+            author,
+            position,
+            created_at,
+            ready_at,
+            expires_at,
+        } = value;
+        Hold {
+            id: hold_id,
+            book: HoldBook {
+                id: book_id,
+                title,
+                author,
+            },
+            position,
+            created_at,
+            ready_at,
+            expires_at,
+        }
+    }
+}
+
+/// 予約できるかを確認するための型
+pub struct HoldStateRow {
+    /// 予約するユーザーが書籍を借りているかどうか
+    pub checked_out: bool,
+    /// 予約するユーザーが書籍を予約済みかどうか
+    pub held: bool,
+}
@@ -0,0 +1,25 @@
+use kernel::model::{id::LocationId, location::Location};
+
+pub struct LocationRow {
+    pub location_id: LocationId,
+    pub site: String,
+    pub room: String,
+    pub shelf: String,
+}
+
+impl From<LocationRow> for Location {
+    fn from(value: LocationRow) -> Self {
+        let LocationRow {
+            location_id,
+            site,
+            room,
+            shelf,
+        } = value;
+        Location {
+            id: location_id,
+            site,
+            room,
+            shelf,
+        }
+    }
+}
@@ -1,4 +1,11 @@
 pub mod auth;
 pub mod book;
 pub mod checkout;
+pub mod cursor;
+pub mod hold;
+pub mod location;
+pub mod notification;
+pub mod purchase_request;
+pub mod review;
 pub mod user;
+pub mod wishlist;
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, NotificationId},
+    notification::{Notification, NotificationKind},
+};
+use shared::error::AppError;
+
+pub struct NotificationRow {
+    pub notification_id: NotificationId,
+    pub kind: String,
+    pub book_id: Option<BookId>,
+    pub message: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<NotificationRow> for Notification {
+    type Error = AppError;
+
+    fn try_from(value: NotificationRow) -> Result<Self, Self::Error> {
+        let NotificationRow {
+            notification_id,
+            kind,
+            book_id,
+            message,
+            read_at,
+            created_at,
+        } = value;
+        Ok(Notification {
+            id: notification_id,
+            kind: kind
+                .parse::<NotificationKind>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            book_id,
+            message,
+            read_at,
+            created_at,
+        })
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, PurchaseRequestId, UserId},
+    isbn::Isbn,
+    purchase_request::{PurchaseRequest, PurchaseRequestStatus},
+    user::PurchaseRequester,
+};
+use shared::error::AppError;
+
+pub struct PurchaseRequestRow {
+    pub purchase_request_id: PurchaseRequestId,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub isbn: String,
+    pub isbn_display: String,
+    pub title: String,
+    pub author: String,
+    pub reason: String,
+    pub link: Option<String>,
+    pub status: String,
+    pub votes: i64,
+    pub voted: bool,
+    pub book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub ordered_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PurchaseRequestRow> for PurchaseRequest {
+    type Error = AppError;
+
+    fn try_from(value: PurchaseRequestRow) -> Result<Self, Self::Error> {
+        let PurchaseRequestRow {
+            purchase_request_id,
+            user_id,
+            user_name,
+            isbn,
+            isbn_display,
+            title,
+            author,
+            reason,
+            link,
+            status,
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        } = value;
+        Ok(PurchaseRequest {
+            id: purchase_request_id,
+            requested_by: user_id
+                .zip(user_name)
+                .map(|(id, name)| PurchaseRequester { id, name }),
+            isbn: Isbn::from_stored(isbn, isbn_display),
+            title,
+            author,
+            reason,
+            link,
+            status: status
+                .parse::<PurchaseRequestStatus>()
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        })
+    }
+}
+
+pub struct PaginatedPurchaseRequestRow {
+    pub total: i64,
+    pub purchase_request_id: PurchaseRequestId,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub isbn: String,
+    pub isbn_display: String,
+    pub title: String,
+    pub author: String,
+    pub reason: String,
+    pub link: Option<String>,
+    pub status: String,
+    pub votes: i64,
+    pub voted: bool,
+    pub book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub ordered_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<PaginatedPurchaseRequestRow> for PurchaseRequest {
+    type Error = AppError;
+
+    fn try_from(value: PaginatedPurchaseRequestRow) -> Result<Self, Self::Error> {
+        let PaginatedPurchaseRequestRow {
+            total: _,
+            purchase_request_id,
+            user_id,
+            user_name,
+            isbn,
+            isbn_display,
+            title,
+            author,
+            reason,
+            link,
+            status,
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        } = value;
+        PurchaseRequestRow {
+            purchase_request_id,
+            user_id,
+            user_name,
+            isbn,
+            isbn_display,
+            title,
+            author,
+            reason,
+            link,
+            status,
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        }
+        .try_into()
+    }
+}
+
+/// 状態を変える前に、行ロックをかけて取得する購入リクエストの行
+pub struct PurchaseRequestLockRow {
+    pub user_id: Option<UserId>,
+    pub isbn: String,
+    pub isbn_display: String,
+    pub title: String,
+    pub author: String,
+    pub status: String,
+}
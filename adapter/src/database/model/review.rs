@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, ReviewId, UserId},
+    review::Review,
+    user::Reviewer,
+};
+
+pub struct ReviewRow {
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub rating: i16,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ReviewRow> for Review {
+    fn from(value: ReviewRow) -> Self {
+        let ReviewRow {
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        Review {
+            id: review_id,
+            book_id,
+            reviewer: Reviewer {
+                id: user_id,
+                name: user_name,
+            },
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+pub struct PaginatedReviewRow {
+    pub total: i64,
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub rating: i16,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PaginatedReviewRow> for Review {
+    fn from(value: PaginatedReviewRow) -> Self {
+        let PaginatedReviewRow {
+            total: _,
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        ReviewRow {
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+        .into()
+    }
+}
+
+/// 変更・削除の前に、投稿者を確認するために取得するレビューの行
+pub struct ReviewOwnerRow {
+    pub user_id: UserId,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, WishlistItemId},
+    isbn::Isbn,
+    wishlist::WishlistItem,
+};
+
+pub struct WishlistItemRow {
+    pub wishlist_item_id: WishlistItemId,
+    pub isbn: Option<String>,
+    pub isbn_display: Option<String>,
+    pub title: Option<String>,
+    pub fulfilled_book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WishlistItemRow> for WishlistItem {
+    fn from(value: WishlistItemRow) -> Self {
+        let WishlistItemRow {
+            wishlist_item_id,
+            isbn,
+            isbn_display,
+            title,
+            fulfilled_book_id,
+            created_at,
+        } = value;
+        WishlistItem {
+            id: wishlist_item_id,
+            isbn: isbn.map(|isbn| {
+                let display = isbn_display.unwrap_or_else(|| isbn.clone());
+                Isbn::from_stored(isbn, display)
+            }),
+            title,
+            fulfilled_book_id,
+            created_at,
+        }
+    }
+}
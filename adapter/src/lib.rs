@@ -1,4 +1,6 @@
 //! 外部との接続を行うレイヤー
 pub mod database;
+pub mod metadata;
 pub mod redis;
 pub mod repository;
+pub mod storage;
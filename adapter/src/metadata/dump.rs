@@ -0,0 +1,153 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    sync::OnceCell,
+};
+
+use kernel::{
+    model::{book::BookMetadata, isbn::Isbn},
+    repository::book_metadata::BookMetadataProvider,
+};
+use shared::error::{AppError, AppResult};
+
+use super::{AuthorRecord, EditionRecord};
+
+/// OpenLibraryのデータダンプから書誌情報を取得する提供元
+/// ダンプは「type, key, revision, last_modified, JSON」のタブ区切りで、版と著者のレコードを読み込む
+/// 起動を遅らせないよう、最初の取得時にダンプ全体を読み込んでISBNごとの索引をメモリ上に作る
+pub struct OpenLibraryDumpProvider {
+    path: PathBuf,
+    index: OnceCell<HashMap<String, BookMetadata>>,
+}
+
+impl OpenLibraryDumpProvider {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            index: OnceCell::new(),
+        }
+    }
+
+    async fn load(&self) -> AppResult<HashMap<String, BookMetadata>> {
+        let read_error = |e: std::io::Error| {
+            AppError::ExternalServiceError(format!(
+                "failed to read book metadata dump {}: {e}",
+                self.path.display()
+            ))
+        };
+        let file = tokio::fs::File::open(&self.path)
+            .await
+            .map_err(read_error)?;
+        let mut lines = BufReader::new(file).lines();
+
+        let mut editions = Vec::new();
+        let mut authors = HashMap::new();
+        while let Some(line) = lines.next_line().await.map_err(read_error)? {
+            let mut columns = line.splitn(5, '\t');
+            let (Some(record_type), Some(key), Some(json)) =
+                (columns.next(), columns.next(), columns.nth(2))
+            else {
+                continue;
+            };
+            // 解析できないレコードは読み飛ばす
+            match record_type {
+                "/type/edition" => {
+                    if let Ok(edition) = serde_json::from_str::<EditionRecord>(json) {
+                        editions.push(edition);
+                    }
+                }
+                "/type/author" => {
+                    if let Ok(author) = serde_json::from_str::<AuthorRecord>(json) {
+                        authors.insert(key.to_string(), author.name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut index = HashMap::new();
+        for edition in editions {
+            let isbns = edition.isbn13s();
+            if isbns.is_empty() {
+                continue;
+            }
+            let author_names = edition
+                .author_keys()
+                .filter_map(|key| authors.get(key).cloned())
+                .collect();
+            let metadata = edition.into_metadata(author_names);
+            for isbn in isbns {
+                index.entry(isbn).or_insert_with(|| metadata.clone());
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for OpenLibraryDumpProvider {
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        let index = self.index.get_or_try_init(|| self.load()).await?;
+        Ok(index.get(isbn.as_isbn13()).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_find_by_isbn() -> anyhow::Result<()> {
+        let dump = [
+            "/type/author\t/authors/OL1A\t1\t2024-01-01T00:00:00\t{\"name\": \"初田直也\"}",
+            "/type/author\t/authors/OL2A\t1\t2024-01-01T00:00:00\t{\"name\": \"山口聖弘\"}",
+            "/type/edition\t/books/OL1M\t3\t2024-01-01T00:00:00\t{\"title\": \"実践Rustプログラミング入門\", \"authors\": [{\"key\": \"/authors/OL1A\"}, {\"key\": \"/authors/OL2A\"}], \"publishers\": [\"秀和システム\"], \"publish_date\": \"2020-08-27\", \"number_of_pages\": 548, \"isbn_10\": [\"4798061700\"]}",
+            "/type/edition\t/books/OL2M\t1\t2024-01-01T00:00:00\t{\"title\": \"By statement only\", \"by_statement\": \"Jane Doe.\", \"publish_date\": \"2021\", \"isbn_13\": [\"9784065301951\"]}",
+            "/type/edition\t/books/OL3M\t1\t2024-01-01T00:00:00\tnot json",
+            "/type/work\t/works/OL1W\t1\t2024-01-01T00:00:00\t{\"title\": \"Work\"}",
+        ]
+        .join("\n");
+        let path = std::env::temp_dir().join(format!("ol-dump-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, dump)?;
+        let provider = OpenLibraryDumpProvider::new(path.clone());
+
+        // ISBN-10で登録されたレコードも、ISBN-13で取得できる
+        let metadata = provider
+            .find_by_isbn(&Isbn::from_str("978-4-7980-6170-2")?)
+            .await?
+            .unwrap();
+        assert_eq!(
+            metadata,
+            BookMetadata {
+                title: "実践Rustプログラミング入門".into(),
+                author: Some("初田直也, 山口聖弘".into()),
+                publisher: Some("秀和システム".into()),
+                published_on: chrono::NaiveDate::from_ymd_opt(2020, 8, 27),
+                page_count: Some(548),
+            }
+        );
+
+        // 著者のレコードがない場合はby_statementを著者名とする
+        let metadata = provider
+            .find_by_isbn(&Isbn::from_str("9784065301951")?)
+            .await?
+            .unwrap();
+        assert_eq!(metadata.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.published_on, None);
+
+        assert!(
+            provider
+                .find_by_isbn(&Isbn::from_str("9784065369579")?)
+                .await?
+                .is_none()
+        );
+
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+
+use kernel::{
+    model::{book::BookMetadata, isbn::Isbn},
+    repository::book_metadata::BookMetadataProvider,
+};
+use shared::error::{AppError, AppResult};
+
+use super::{AuthorRecord, EditionRecord};
+
+/// 書誌情報の取得を待つ時間の上限
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// OpenLibrary互換のAPIから書誌情報を取得する提供元
+/// {base_url}/isbn/{ISBN}.jsonで版のレコードを、{base_url}/authors/{ID}.jsonで著者のレコードを取得する
+pub struct OpenLibraryHttpProvider {
+    client: Client,
+    base_url: String,
+}
+
+impl OpenLibraryHttpProvider {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// パスのレコードを取得する。存在しない場合はNoneを返す
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> AppResult<Option<T>> {
+        let url = format!("{}{path}", self.base_url);
+        let request_error = |e: reqwest::Error| {
+            AppError::ExternalServiceError(format!("failed to fetch {url}: {e}"))
+        };
+
+        let res = self
+            .client
+            .get(&url)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(request_error)?;
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = res
+            .error_for_status()
+            .map_err(request_error)?
+            .bytes()
+            .await
+            .map_err(request_error)?;
+
+        serde_json::from_slice(&body).map(Some).map_err(|e| {
+            AppError::ExternalServiceError(format!("unexpected response from {url}: {e}"))
+        })
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for OpenLibraryHttpProvider {
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        let path = format!("/isbn/{}.json", isbn.as_isbn13());
+        let Some(edition) = self.get_json::<EditionRecord>(&path).await? else {
+            return Ok(None);
+        };
+
+        let mut author_names = Vec::new();
+        for key in edition.author_keys() {
+            if let Some(author) = self
+                .get_json::<AuthorRecord>(&format!("{key}.json"))
+                .await?
+            {
+                author_names.push(author.name);
+            }
+        }
+
+        Ok(Some(edition.into_metadata(author_names)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use axum::{Json, Router, http::StatusCode, routing::get};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// OpenLibraryのAPIの代わりに応答するスタブサーバーを起動し、ベースURLを返す
+    async fn start_stub_server() -> anyhow::Result<String> {
+        let app = Router::new()
+            .route(
+                "/isbn/9784798061702.json",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "title": "実践Rustプログラミング入門",
+                        "authors": [{ "key": "/authors/OL1A" }],
+                        "publishers": ["秀和システム"],
+                        "publish_date": "Aug 27, 2020",
+                        "number_of_pages": 548,
+                    }))
+                }),
+            )
+            .route(
+                "/authors/OL1A.json",
+                get(|| async { Json(serde_json::json!({ "name": "初田直也" })) }),
+            )
+            .route(
+                "/isbn/9784065301951.json",
+                get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Ok(format!("http://{addr}/"))
+    }
+
+    #[tokio::test]
+    async fn test_find_by_isbn() -> anyhow::Result<()> {
+        let provider = OpenLibraryHttpProvider::new(start_stub_server().await?);
+
+        let metadata = provider
+            .find_by_isbn(&Isbn::from_str("4-7980-6170-0")?)
+            .await?
+            .unwrap();
+        assert_eq!(
+            metadata,
+            BookMetadata {
+                title: "実践Rustプログラミング入門".into(),
+                author: Some("初田直也".into()),
+                publisher: Some("秀和システム".into()),
+                published_on: chrono::NaiveDate::from_ymd_opt(2020, 8, 27),
+                page_count: Some(548),
+            }
+        );
+
+        // 見つからない場合はNone、APIがエラーを返した場合はエラーとする
+        assert!(
+            provider
+                .find_by_isbn(&Isbn::from_str("9784065369579")?)
+                .await?
+                .is_none()
+        );
+        let res = provider
+            .find_by_isbn(&Isbn::from_str("9784065301951")?)
+            .await;
+        assert!(matches!(res, Err(AppError::ExternalServiceError(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! ISBNから書誌情報を取得する提供元の具象実装をするモジュール
+//! データダンプとAPIはどちらもOpenLibraryのレコード形式を扱う
+pub mod dump;
+pub mod http;
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use kernel::{
+    model::{book::BookMetadata, isbn::Isbn},
+    repository::book_metadata::BookMetadataProvider,
+};
+use shared::error::AppResult;
+
+/// 書誌情報を取得しない場合の提供元。常に見つからなかったものとして扱う
+pub struct DisabledBookMetadataProvider;
+
+#[async_trait]
+impl BookMetadataProvider for DisabledBookMetadataProvider {
+    async fn find_by_isbn(&self, _isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        Ok(None)
+    }
+}
+
+/// OpenLibraryの版（edition）のレコード
+#[derive(Debug, Deserialize)]
+struct EditionRecord {
+    title: String,
+    #[serde(default)]
+    authors: Vec<AuthorRef>,
+    /// 「著者名 著」のような表記。著者のレコードを参照できない場合に使う
+    by_statement: Option<String>,
+    #[serde(default)]
+    publishers: Vec<String>,
+    publish_date: Option<String>,
+    number_of_pages: Option<i32>,
+    #[serde(default)]
+    isbn_13: Vec<String>,
+    #[serde(default)]
+    isbn_10: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AuthorRef {
+    /// /authors/OL1234567Aの形式
+    key: String,
+}
+
+/// OpenLibraryの著者のレコード
+#[derive(Debug, Deserialize)]
+struct AuthorRecord {
+    name: String,
+}
+
+impl EditionRecord {
+    /// レコードに含まれるISBNを、正規化したISBN-13として返す。形式が不正なものは除く
+    fn isbn13s(&self) -> Vec<String> {
+        let mut isbns = self
+            .isbn_13
+            .iter()
+            .chain(&self.isbn_10)
+            .filter_map(|isbn| Isbn::from_str(isbn).ok())
+            .map(|isbn| isbn.as_isbn13().to_string())
+            .collect::<Vec<_>>();
+        isbns.dedup();
+        isbns
+    }
+
+    fn author_keys(&self) -> impl Iterator<Item = &str> {
+        self.authors.iter().map(|author| author.key.as_str())
+    }
+
+    /// 著者のレコードから解決した著者名とともに書誌情報に変換する
+    fn into_metadata(self, author_names: Vec<String>) -> BookMetadata {
+        let author = if author_names.is_empty() {
+            self.by_statement
+                .map(|s| s.trim().trim_end_matches('.').to_string())
+                .filter(|s| !s.is_empty())
+        } else {
+            Some(author_names.join(", "))
+        };
+        BookMetadata {
+            title: self.title,
+            author,
+            publisher: self.publishers.into_iter().next(),
+            published_on: self.publish_date.as_deref().and_then(parse_publish_date),
+            page_count: self.number_of_pages.filter(|&n| n > 0),
+        }
+    }
+}
+
+/// OpenLibraryの発行日を日付に変換する
+/// 年や年月のみのように日付まで特定できない表記はNoneとする
+fn parse_publish_date(value: &str) -> Option<NaiveDate> {
+    const FORMATS: [&str; 5] = ["%Y-%m-%d", "%B %d, %Y", "%b %d, %Y", "%d %B %Y", "%Y/%m/%d"];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value.trim(), format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_publish_date() {
+        let expected = NaiveDate::from_ymd_opt(2019, 3, 15);
+        assert_eq!(parse_publish_date("2019-03-15"), expected);
+        assert_eq!(parse_publish_date("March 15, 2019"), expected);
+        assert_eq!(parse_publish_date("Mar 15, 2019"), expected);
+        assert_eq!(parse_publish_date("15 March 2019"), expected);
+        assert_eq!(parse_publish_date("2019"), None);
+        assert_eq!(parse_publish_date("March 2019"), None);
+    }
+}
@@ -2,25 +2,58 @@
 
 use async_trait::async_trait;
 use derive_new::new;
-use std::collections::HashMap;
+use futures::{TryStreamExt, stream::BoxStream};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Cursor,
+    sync::Arc,
+};
 
 use kernel::model::{
     book::{
-        Book, BookListOptions, Checkout,
-        event::{CreateBook, DeleteBook, UpdateBook},
+        Book, BookAdminAction, BookAdminActionKind, BookAvailability, BookCatalogEntry, BookCopy,
+        BookCover, BookImportResult, BookImportStatus, BookListOptions, BookRevision, BookSnapshot,
+        BookSort, BookSortKey, BookTransfer, CopyStatus, CopyStatusChange, CoverImageType,
+        CoverSize, DeletedBook, DeletedBookListOptions, TagCount,
+        event::{
+            AcceptBookTransfer, AddBookTags, ChangeCopyStatus, CreateBook, CreateBookCopy,
+            DeclineBookTransfer, DeleteBook, DeleteBookCopy, ImportBooks, MoveBook, PatchBook,
+            PurgeBook, RemoveBookTag, RestoreBook, RevertBook, SetBookLoanPeriod, TransferAllBooks,
+            TransferBook, UpdateBook, UploadBookCover,
+        },
     },
-    id::{BookId, UserId},
-    list::PaginatedList,
+    checkout::CheckoutOutcome,
+    id::{BookId, CheckoutId, CopyId, LocationId, UserId},
+    isbn::Isbn,
+    list::{CursorPaginatedList, PaginatedList, SortOrder},
+    notification::NotificationKind,
 };
 use kernel::repository::book::BookRepository;
 use shared::error::{AppError, AppResult};
 
 use crate::database::ConnectionPool;
-use crate::database::model::book::{BookCheckoutRow, BookRow, PaginatedBookRow};
+use crate::database::model::{
+    book::{
+        BookAdminActionRow, BookCatalogRow, BookCopyRow, BookKeyRow, BookRevisionRow, BookRow,
+        BookSnapshotRow, BookTagRow, BookTransferRow, CopyStateRow, CopyStatusChangeRow,
+        DeletedBookRow, PaginatedBookRow, TagCountRow,
+    },
+    cursor::{BookCursor, decode_cursor, encode_cursor},
+};
+use crate::storage::BlobStorage;
+use sqlx::types::{Json, Uuid};
+
+use super::hold::advance_holds;
+
+/// サムネイルの幅と高さの上限（ピクセル）。縦横比は元画像に合わせる
+const THUMBNAIL_SIZE: u32 = 200;
 
 #[derive(new)]
 pub struct BookRepositoryImpl {
     db: ConnectionPool,
+    blob_storage: Arc<dyn BlobStorage>,
+    /// 予約した書籍を取り置いておく日数
+    hold_pickup_days: i32,
 }
 
 // NOTE: 「set DATABASE_URL to use query macros online～」の警告を消すためには
@@ -28,41 +61,146 @@ pub struct BookRepositoryImpl {
 #[async_trait]
 impl BookRepository for BookRepositoryImpl {
     /// 書籍を登録する
+    /// 登録した書籍には、連番のバーコードを持つ蔵書を1冊追加する
     async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()> {
-        sqlx::query!(
+        let mut tx = self.db.begin().await?;
+        insert_book(&mut tx, event, user_id).await?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍を一括で登録する
+    /// 所有者が見つからない行は不備あり、同じISBNの書籍が登録済みまたは前の行にある行は重複として登録しない
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>> {
+        let ImportBooks {
+            rows,
+            default_owner,
+            dry_run,
+        } = event;
+
+        let mut tx = self.db.begin().await?;
+
+        // 所有者のメールアドレスをまとめてユーザーIDに変換する
+        let emails = rows
+            .iter()
+            .filter_map(|row| row.owner_email.clone())
+            .collect::<Vec<_>>();
+        let owners: HashMap<String, UserId> = sqlx::query!(
             r#"
-            INSERT INTO books (title, author, isbn, description, user_id)
-            VALUES ($1, $2, $3, $4, $5)
+            SELECT email, user_id AS "user_id: UserId"
+            FROM users
+            WHERE email = ANY($1)
             "#,
-            event.title,
-            event.author,
-            event.isbn,
-            event.description,
-            user_id as _
+            &emails
         )
-        .execute(self.db.inner_ref())
+        .fetch_all(&mut *tx)
         .await
-        // sqlx::Error型をAppError型に変換する
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(|row| (row.email, row.user_id))
+        .collect();
 
-        Ok(())
+        // 登録済みのISBNをまとめて取得する
+        let isbns = rows
+            .iter()
+            .map(|row| row.book.isbn.as_isbn13().to_string())
+            .collect::<Vec<_>>();
+        let mut registered: HashSet<String> = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT isbn FROM books WHERE isbn = ANY($1) AND deleted_at IS NULL
+            "#,
+            &isbns
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .collect();
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            let owner = match &row.owner_email {
+                Some(email) => owners
+                    .get(email)
+                    .copied()
+                    .ok_or_else(|| format!("owner with email {email} not found")),
+                None => default_owner.ok_or_else(|| "owner email is required".to_string()),
+            };
+            let status = match owner {
+                Err(message) => BookImportStatus::Invalid(message),
+                // 登録済みのISBNに加え、同じ取り込み内で先に登録したISBNも重複とみなす
+                Ok(_) if !registered.insert(row.book.isbn.as_isbn13().to_string()) => {
+                    BookImportStatus::Duplicate
+                }
+                Ok(owner) => {
+                    BookImportStatus::Created(insert_book(&mut tx, row.book, owner).await?)
+                }
+            };
+            results.push(BookImportResult {
+                line: row.line,
+                status,
+            });
+        }
+
+        if dry_run {
+            tx.rollback().await.map_err(AppError::TransactionError)?;
+        } else {
+            tx.commit().await.map_err(AppError::TransactionError)?;
+        }
+
+        Ok(results)
     }
-    /// 指定したlimit, offsetに応じて、書籍を取得する
+    /// 指定した検索・絞り込み条件と並び順、limit, offsetに応じて、書籍を取得する
     async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>> {
-        let BookListOptions { limit, offset } = options;
+        let BookListOptions {
+            limit,
+            offset,
+            q,
+            author,
+            owner,
+            location,
+            favorited_by,
+            availability,
+            tags,
+            sort,
+        } = options;
+        // 貸出状態の絞り込みは「貸出可能な蔵書がないかどうか」の真偽値としてクエリに渡す
+        let checked_out = availability.map(|a| a == BookAvailability::CheckedOut);
+        let (sort_key, ascending) = sort_key_and_direction(sort, q.is_some());
 
+        // 検索・絞り込みの条件と並び替えのキーはfiltered_books関数で算出し、
+        // 最後にbook_idを加えた組で並べることで、同じ値の書籍同士の順序を確定させる
+        // 検索キーワードが指定され、並び順が未指定の場合は全文検索の一致度が高い順に並べる
         let rows = sqlx::query_as!(
             PaginatedBookRow,
             r#"
             SELECT COUNT(*) OVER() AS "total!",
-                b.book_id AS id
-            FROM books as b
-            ORDER BY b.created_at DESC
+                s.book_id AS "id!"
+            FROM filtered_books($3, $4, $5, $6, $7, $8, $9, $10) AS s
+            ORDER BY
+                CASE WHEN $11 THEN s.text_key END ASC,
+                CASE WHEN $11 THEN s.time_key END ASC,
+                CASE WHEN $11 THEN s.num_key END ASC,
+                CASE WHEN $11 THEN s.book_id END ASC,
+                s.text_key DESC,
+                s.time_key DESC,
+                s.num_key DESC,
+                s.book_id DESC
             LIMIT $1
             OFFSET $2
             "#,
             limit,
-            offset
+            offset,
+            q,
+            author,
+            owner as _,
+            checked_out,
+            &tags,
+            location as _,
+            favorited_by as _,
+            sort_key,
+            ascending,
         )
         .fetch_all(self.db.inner_ref())
         .await
@@ -71,47 +209,176 @@ impl BookRepository for BookRepositoryImpl {
         // レコードがない場合はtotalを0にする
         let total = rows.first().map(|r| r.total).unwrap_or_default();
         let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+        let items = self.find_by_ids(&book_ids).await?;
 
-        let rows: Vec<BookRow> = sqlx::query_as!(
-            BookRow,
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        })
+    }
+    /// カーソルの位置から、指定した検索・絞り込み条件と並び順に応じて書籍を取得する
+    async fn find_all_by_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<String>,
+    ) -> AppResult<CursorPaginatedList<Book>> {
+        let BookListOptions {
+            limit,
+            q,
+            author,
+            owner,
+            location,
+            favorited_by,
+            availability,
+            tags,
+            sort,
+            ..
+        } = options;
+        let checked_out = availability.map(|a| a == BookAvailability::CheckedOut);
+        let (sort_key, ascending) = sort_key_and_direction(sort, q.is_some());
+
+        // 別の並び順で作られたカーソルは受け付けない
+        let cursor = cursor
+            .as_deref()
+            .map(decode_cursor::<BookCursor>)
+            .transpose()?;
+        if cursor
+            .as_ref()
+            .is_some_and(|c| (c.sort.as_str(), c.asc) != (sort_key, ascending))
+        {
+            return Err(AppError::InvalidCursorError);
+        }
+        let (text_key, time_key, num_key, cursor_book_id) = match cursor {
+            Some(c) => (
+                Some(c.text_key),
+                Some(c.time_key),
+                Some(c.num_key),
+                Some(c.book_id),
+            ),
+            None => (None, None, None, None),
+        };
+
+        // find_allと同じfiltered_books関数で並び替えのキーを算出し、カーソルより後ろの行だけを取得する
+        // 次のページの有無を判定するため、limitより1件多く取得する
+        let mut rows = sqlx::query_as!(
+            BookKeyRow,
             r#"
-            SELECT 
-                b.book_id AS book_id, 
-                b.title AS title, 
-                b.author AS author, 
-                b.isbn AS isbn, 
-                b.description AS description,
-                u.user_id AS owned_by,
-                u.name AS owner_name
-            FROM books AS b
-            INNER JOIN users AS u USING(user_id)
-            WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
-            ORDER BY b.created_at DESC
+            SELECT
+                s.book_id AS "book_id!",
+                s.text_key AS "text_key!",
+                s.time_key AS "time_key!",
+                s.num_key AS "num_key!"
+            FROM filtered_books($2, $3, $4, $5, $6, $7, $8, $9) AS s
+            WHERE $14::uuid IS NULL
+                OR ($10 AND (s.text_key, s.time_key, s.num_key, s.book_id)
+                    > ($11::text, $12::timestamptz, $13::float8, $14::uuid))
+                OR (NOT $10 AND (s.text_key, s.time_key, s.num_key, s.book_id)
+                    < ($11::text, $12::timestamptz, $13::float8, $14::uuid))
+            ORDER BY
+                CASE WHEN $10 THEN s.text_key END ASC,
+                CASE WHEN $10 THEN s.time_key END ASC,
+                CASE WHEN $10 THEN s.num_key END ASC,
+                CASE WHEN $10 THEN s.book_id END ASC,
+                s.text_key DESC,
+                s.time_key DESC,
+                s.num_key DESC,
+                s.book_id DESC
+            LIMIT $1
             "#,
-            &book_ids as _
+            limit + 1,
+            q,
+            author,
+            owner as _,
+            checked_out,
+            &tags,
+            location as _,
+            favorited_by as _,
+            sort_key,
+            ascending,
+            text_key,
+            time_key,
+            num_key,
+            cursor_book_id as _,
         )
         .fetch_all(self.db.inner_ref())
         .await
-        // sqlx::Error型をAppError型に変換する
         .map_err(AppError::SpecificOperationError)?;
 
-        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
-        let mut checkouts = self.find_checktouts(&book_ids).await?;
-
-        let items = rows
-            .into_iter()
-            .map(|row| {
-                let checkout = checkouts.remove(&row.book_id);
-                row.into_book(checkout)
+        // limitより多く取得できた場合は、limit件目の行を次のページのカーソルにする
+        let next_cursor = if rows.len() as i64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().map(|row| {
+                encode_cursor(&BookCursor {
+                    sort: sort_key.into(),
+                    asc: ascending,
+                    text_key: row.text_key.clone(),
+                    time_key: row.time_key,
+                    num_key: row.num_key,
+                    book_id: row.book_id,
+                })
             })
-            .collect();
-        Ok(PaginatedList {
-            total,
-            limit,
-            offset,
+        } else {
+            None
+        };
+        let book_ids = rows.into_iter().map(|r| r.book_id).collect::<Vec<BookId>>();
+        let items = self.find_by_ids(&book_ids).await?;
+
+        Ok(CursorPaginatedList {
             items,
+            limit,
+            next_cursor,
+        })
+    }
+    /// 全書籍の目録を、書籍の登録日時順・蔵書の追加順に1行ずつ取得する
+    fn stream_catalog(&self) -> BoxStream<'static, AppResult<BookCatalogEntry>> {
+        // ストリームが呼出し元でレスポンスボディとして読まれる間もプールを使えるよう、
+        // コネクションプールを複製してストリームに持たせる
+        let db = self.db.clone();
+        Box::pin(async_stream::try_stream! {
+            let mut rows = sqlx::query_as!(
+                BookCatalogRow,
+                r#"
+                SELECT
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.isbn,
+                    b.isbn_display,
+                    b.description,
+                    u.user_id AS owned_by,
+                    u.name AS owner_name,
+                    bc.copy_id AS "copy_id?: CopyId",
+                    bc.barcode AS "barcode?",
+                    bc.status AS "copy_status?",
+                    c.checkout_id AS "checkout_id?: CheckoutId",
+                    cu.user_id AS "user_id?: UserId",
+                    cu.name AS "user_name?",
+                    c.checked_out_at AS "checked_out_at?",
+                    c.due_at AS "due_at?",
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!"
+                FROM books AS b
+                INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN book_copies AS bc ON bc.book_id = b.book_id
+                LEFT OUTER JOIN checkouts AS c ON c.copy_id = bc.copy_id
+                LEFT OUTER JOIN users AS cu ON cu.user_id = c.user_id
+                WHERE b.deleted_at IS NULL
+                ORDER BY b.created_at ASC, b.book_id ASC, bc.created_at ASC, bc.copy_id ASC
+                "#
+            )
+            .fetch(db.inner_ref())
+            .map_err(AppError::SpecificOperationError);
+
+            while let Some(row) = rows.try_next().await? {
+                yield BookCatalogEntry::try_from(row)?;
+            }
         })
     }
+
     /// 書籍を取得する
     async fn find_by_id(&self, book_id: BookId) -> AppResult<Option<Book>> {
         let row = sqlx::query_as!(
@@ -122,12 +389,32 @@ impl BookRepository for BookRepositoryImpl {
                 b.title as title, 
                 b.author as author, 
                 b.isbn as isbn, 
+                b.isbn_display as isbn_display,
                 b.description as description,
+                b.publisher,
+                b.published_on,
+                b.page_count,
                 u.user_id as owned_by,
-                u.name AS owner_name
+                u.name AS owner_name,
+                b.cover_content_type,
+                b.version,
+                b.loan_period_days,
+                l.location_id AS "location_id?: LocationId",
+                l.site AS "location_site?",
+                l.room AS "location_room?",
+                l.shelf AS "location_shelf?",
+                r.average_rating,
+                COALESCE(r.review_count, 0) AS "review_count!"
             FROM books AS b
             INNER JOIN users AS u USING(user_id)
+            LEFT OUTER JOIN locations AS l ON l.location_id = b.location_id
+            LEFT OUTER JOIN (
+                SELECT book_id, AVG(rating)::float8 AS average_rating, COUNT(*) AS review_count
+                FROM book_reviews
+                GROUP BY book_id
+            ) AS r ON r.book_id = b.book_id
             WHERE b.book_id = $1
+            AND b.deleted_at IS NULL
             "#,
             book_id as _, // query_as!マクロによるコンパイル時の型チェックを無効化（sqlx::query!マクロのドキュメントに記載されている）
         )
@@ -138,148 +425,1765 @@ impl BookRepository for BookRepositoryImpl {
 
         // BookRowをBookに変換して返す
         match row {
-            Some(row) => {
-                let book_id = row.book_id;
-                let mut checkouts = self.find_checktouts(&[book_id]).await?;
-                let checkout = checkouts.remove(&book_id);
-                Ok(Some(row.into_book(checkout)))
-            }
+            Some(row) => Ok(self.load_books(vec![row]).await?.pop()),
             None => Ok(None),
         }
     }
 
+    /// ISBNに一致する書籍を登録日時の古い順にすべて取得する
+    async fn find_by_isbn(&self, isbn: Isbn) -> AppResult<Vec<Book>> {
+        let rows: Vec<BookRow> = sqlx::query_as!(
+            BookRow,
+            r#"
+            SELECT 
+                b.book_id AS book_id, 
+                b.title AS title, 
+                b.author AS author, 
+                b.isbn AS isbn, 
+                b.isbn_display AS isbn_display,
+                b.description AS description,
+                b.publisher,
+                b.published_on,
+                b.page_count,
+                u.user_id AS owned_by,
+                u.name AS owner_name,
+                b.cover_content_type,
+                b.version,
+                b.loan_period_days,
+                l.location_id AS "location_id?: LocationId",
+                l.site AS "location_site?",
+                l.room AS "location_room?",
+                l.shelf AS "location_shelf?",
+                r.average_rating,
+                COALESCE(r.review_count, 0) AS "review_count!"
+            FROM books AS b
+            INNER JOIN users AS u USING(user_id)
+            LEFT OUTER JOIN locations AS l ON l.location_id = b.location_id
+            LEFT OUTER JOIN (
+                SELECT book_id, AVG(rating)::float8 AS average_rating, COUNT(*) AS review_count
+                FROM book_reviews
+                GROUP BY book_id
+            ) AS r ON r.book_id = b.book_id
+            WHERE b.isbn = $1
+            AND b.deleted_at IS NULL
+            ORDER BY b.created_at ASC, b.book_id ASC
+            "#,
+            isbn.as_isbn13()
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        // sqlx::Error型をAppError型に変換する
+        .map_err(AppError::SpecificOperationError)?;
+
+        self.load_books(rows).await
+    }
+
     /// 書籍を更新する
     async fn update(&self, event: UpdateBook) -> AppResult<()> {
-        let res = sqlx::query!(
+        let UpdateBook {
+            book_id,
+            title,
+            author,
+            isbn,
+            description,
+            publisher,
+            published_on,
+            page_count,
+            requested_user,
+            requested_by_admin,
+            expected_version,
+        } = event;
+
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, book_id).await?;
+        // 内容を変更できるのは所有者か管理者のみ
+        let by_admin = authorize_book_edit(&locked, requested_user, requested_by_admin)?;
+        check_version(book_id, expected_version, locked.version)?;
+        let after = BookSnapshot {
+            title,
+            author,
+            isbn,
+            description,
+            publisher,
+            published_on,
+            page_count,
+        };
+        let changed =
+            write_book_snapshot(&mut tx, book_id, locked.snapshot, after, requested_user).await?;
+        if changed && by_admin {
+            record_admin_action(
+                &mut tx,
+                book_id,
+                locked.owner,
+                requested_user,
+                BookAdminActionKind::Update,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍の指定された項目のみを更新する
+    /// 指定されていない項目は、行ロックをかけて取得した現在の値のままとする
+    async fn patch(&self, event: PatchBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 内容を変更できるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+        check_version(event.book_id, event.expected_version, locked.version)?;
+
+        let current = locked.snapshot.clone();
+        let after = BookSnapshot {
+            title: event.title.unwrap_or(current.title),
+            author: event.author.unwrap_or(current.author),
+            isbn: event.isbn.unwrap_or(current.isbn),
+            description: event.description.unwrap_or(current.description),
+            publisher: event.publisher.unwrap_or(current.publisher),
+            published_on: event.published_on.unwrap_or(current.published_on),
+            page_count: event.page_count.unwrap_or(current.page_count),
+        };
+        let changed = write_book_snapshot(
+            &mut tx,
+            event.book_id,
+            locked.snapshot,
+            after,
+            event.requested_user,
+        )
+        .await?;
+        if changed && by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::Update,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍の変更履歴を取得する
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>> {
+        let exists = sqlx::query_scalar!(
             r#"
-            UPDATE books
-            SET title = $1, author = $2, isbn = $3, description = $4
-            WHERE book_id = $5
-            AND user_id = $6
-            "#,
-            event.title,
-            event.author,
-            event.isbn,
-            event.description,
-            event.book_id as _,
-            // 内容を変更できるのは所有者のみ
-            event.requested_user as _,
+            SELECT EXISTS (
+                SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+            ) AS "exists!"
+            "#,
+            book_id as _,
         )
-        .execute(self.db.inner_ref())
+        .fetch_one(self.db.inner_ref())
         .await
-        // sqlx::Error型をAppError型に変換する
         .map_err(AppError::SpecificOperationError)?;
+        if !exists {
+            return Err(AppError::EntityNotFound("specified book not found".into()));
+        }
 
-        if res.rows_affected() == 0 {
-            return Err(AppError::EntityNotFound(
-                "specified book not found or you do not have permission to update it".into(),
-            ));
+        let rows = sqlx::query_as!(
+            BookRevisionRow,
+            r#"
+            SELECT
+                r.revision_id,
+                r.book_id,
+                u.user_id AS "user_id?: UserId",
+                u.name AS "user_name?",
+                r.before AS "before: Json<BookSnapshotRow>",
+                r.after AS "after: Json<BookSnapshotRow>",
+                r.created_at
+            FROM book_revisions AS r
+            LEFT OUTER JOIN users AS u ON u.user_id = r.user_id
+            WHERE r.book_id = $1
+            ORDER BY r.created_at DESC, r.revision_id DESC
+            "#,
+            book_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().map(BookRevision::from).collect())
+    }
+
+    /// 書籍の内容を、指定した変更履歴の時点（変更後の値）に戻す
+    async fn revert(&self, event: RevertBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 戻せるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        let target = sqlx::query_scalar!(
+            r#"
+            SELECT after AS "after: Json<BookSnapshotRow>"
+            FROM book_revisions
+            WHERE revision_id = $1
+            AND book_id = $2
+            "#,
+            event.revision_id as _,
+            event.book_id as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified revision not found".into()))?;
+
+        let changed = write_book_snapshot(
+            &mut tx,
+            event.book_id,
+            locked.snapshot,
+            target.0.into(),
+            event.requested_user,
+        )
+        .await?;
+        if changed && by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::Update,
+            )
+            .await?;
         }
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
 
-    /// 書籍を削除する
+    /// 書籍を削除済みにする
+    /// 貸出中の蔵書がある書籍は、貸出記録を失わないよう削除しない
     async fn delete(&self, event: DeleteBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 書籍を削除できるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+        check_version(event.book_id, event.expected_version, locked.version)?;
+
         let res = sqlx::query!(
             r#"
-            DELETE FROM books
-            WHERE book_id = $1
-            AND user_id = $2
+            UPDATE books AS b
+            SET deleted_at = CURRENT_TIMESTAMP(3)
+            WHERE b.book_id = $1
+            AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.book_id = b.book_id)
             "#,
             event.book_id as _,
-            // 書籍を削除できるのは所有者のみ
-            event.requested_user as _,
         )
-        .execute(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
         .map_err(AppError::SpecificOperationError)?;
 
         if res.rows_affected() == 0 {
-            return Err(AppError::EntityNotFound(
-                "specified book not found or you do not have permission to delete it".into(),
-            ));
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）は貸出中の蔵書があるため削除できません",
+                event.book_id
+            )));
+        }
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::Delete,
+            )
+            .await?;
         }
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
-}
 
-impl BookRepositoryImpl {
-    /// 指定された書籍IDの貸出情報を取得する
-    async fn find_checktouts(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Checkout>> {
-        let res = sqlx::query_as!(
-            BookCheckoutRow,
+    /// 書籍を他のユーザーに譲渡する
+    /// 承認待ちの譲渡がある場合は、新しい譲渡で置き換える
+    async fn transfer(&self, event: TransferBook) -> AppResult<Option<BookTransfer>> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 譲渡できるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+        if locked.owner == event.to_user {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）はすでに譲渡先のユーザーが所有しています",
+                event.book_id
+            )));
+        }
+        ensure_user_exists(&mut tx, event.to_user).await?;
+
+        sqlx::query!(
             r#"
-            SELECT 
-                c.checkout_id,
-                c.book_id,
-                u.user_id,
-                u.name AS user_name,
-                c.checked_out_at
-            FROM checkouts AS c
-            INNER JOIN users AS u USING(user_id)
-            WHERE c.book_id = ANY($1)
+            DELETE FROM book_transfers WHERE book_id = $1
             "#,
-            &book_ids as _
+            event.book_id as _,
         )
-        .fetch_all(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
-        .map_err(AppError::SpecificOperationError)?
-        .into_iter()
-        .map(|checkout| (checkout.book_id, Checkout::from(checkout)))
-        .collect();
+        .map_err(AppError::SpecificOperationError)?;
+
+        let transfer = if event.require_acceptance {
+            let row = sqlx::query_as!(
+                BookTransferRow,
+                r#"
+                WITH t AS (
+                    INSERT INTO book_transfers (book_id, from_user_id, to_user_id)
+                    VALUES ($1, $2, $3)
+                    RETURNING book_transfer_id, book_id, from_user_id, to_user_id, created_at
+                )
+                SELECT
+                    t.book_transfer_id,
+                    t.book_id,
+                    b.title AS book_title,
+                    t.from_user_id,
+                    f.name AS from_user_name,
+                    t.to_user_id,
+                    r.name AS to_user_name,
+                    t.created_at
+                FROM t
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN users AS f ON f.user_id = t.from_user_id
+                INNER JOIN users AS r ON r.user_id = t.to_user_id
+                "#,
+                event.book_id as _,
+                locked.owner as _,
+                event.to_user as _,
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            Some(BookTransfer::from(row))
+        } else {
+            sqlx::query!(
+                r#"
+                UPDATE books SET user_id = $2 WHERE book_id = $1
+                "#,
+                event.book_id as _,
+                event.to_user as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            None
+        };
 
-        Ok(res)
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::Transfer,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(transfer)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
+    /// 指定したユーザーが譲渡先となっている承認待ちの譲渡を取得する
+    /// 削除済みの書籍の譲渡は承認できないため含めない
+    async fn find_incoming_transfers(&self, user_id: UserId) -> AppResult<Vec<BookTransfer>> {
+        let rows = sqlx::query_as!(
+            BookTransferRow,
+            r#"
+            SELECT
+                t.book_transfer_id,
+                t.book_id,
+                b.title AS book_title,
+                t.from_user_id,
+                f.name AS from_user_name,
+                t.to_user_id,
+                r.name AS to_user_name,
+                t.created_at
+            FROM book_transfers AS t
+            INNER JOIN books AS b USING(book_id)
+            INNER JOIN users AS f ON f.user_id = t.from_user_id
+            INNER JOIN users AS r ON r.user_id = t.to_user_id
+            WHERE t.to_user_id = $1
+            AND b.deleted_at IS NULL
+            ORDER BY t.created_at ASC, t.book_transfer_id ASC
+            "#,
+            user_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
 
-    use super::*;
-    use crate::repository::user::UserRepositoryImpl;
-    use kernel::{model::user::event::CreateUser, repository::user::UserRepository};
+        Ok(rows.into_iter().map(BookTransfer::from).collect())
+    }
 
-    #[sqlx::test(fixtures("common"))]
-    async fn test_create_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        // RepositoryImplを初期化
-        let user_repository = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let repository = BookRepositoryImpl::new(ConnectionPool::new(pool));
+    /// 承認待ちの譲渡を承認する
+    /// 譲渡を依頼した後に書籍が削除されたり所有者が変わったりした場合は、譲渡を取り消す
+    async fn accept_transfer(&self, event: AcceptBookTransfer) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
 
-        // ユーザーを登録
-        let user = user_repository
-            .create(CreateUser {
-                name: "Test User".into(),
-                email: "test@example.com".into(),
-                password: "test_password".into(),
-            })
-            .await?;
+        let from_user = sqlx::query_scalar!(
+            r#"
+            DELETE FROM book_transfers
+            WHERE book_id = $1
+            AND to_user_id = $2
+            RETURNING from_user_id AS "from_user_id: UserId"
+            "#,
+            event.book_id as _,
+            event.requested_user as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified transfer not found".into()))?;
 
-        // CreateBookイベントを作成
-        let book = CreateBook {
-            title: "Test Title".into(),
-            author: "Test Author".into(),
-            isbn: "Test ISBN".into(),
-            description: "Test Description".into(),
-        };
-        // 書籍を登録し、正常終了することを確認
-        repository.create(book, user.id).await?;
+        let res = sqlx::query!(
+            r#"
+            UPDATE books SET user_id = $2
+            WHERE book_id = $1
+            AND user_id = $3
+            AND deleted_at IS NULL
+            "#,
+            event.book_id as _,
+            event.requested_user as _,
+            from_user as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
-        // 書籍を取得し、登録した書籍が含まれていることを確認
-        let options = BookListOptions {
-            limit: 10,
-            offset: 0,
-        };
-        let books = repository.find_all(options).await?;
-        assert_eq!(books.items.len(), 1);
+        if res.rows_affected() == 0 {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）は譲渡の依頼後に削除されたか所有者が変わったため、譲渡を取り消しました",
+                event.book_id
+            )));
+        }
 
-        // 取得した一覧から蔵書IDを取得し、
-        // find_by_idで取得できることを確認
-        let book_id = books.items[0].id;
-        let book = repository.find_by_id(book_id).await?;
-        assert!(book.is_some());
+        Ok(())
+    }
+
+    /// 承認待ちの譲渡を拒否する
+    async fn decline_transfer(&self, event: DeclineBookTransfer) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM book_transfers
+            WHERE book_id = $1
+            AND to_user_id = $2
+            "#,
+            event.book_id as _,
+            event.requested_user as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified transfer not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// ユーザーが所有するすべての書籍を譲渡する
+    /// 削除済みの書籍も、復元できるよう所有者を残すため、あえて譲渡の対象に含める
+    /// 譲渡元のユーザーが関わる承認待ちの譲渡（依頼したものと受け取る予定のもの）は取り消す
+    /// 管理者が他のユーザーの書籍を譲渡した場合は、書籍ごとに管理者の操作として記録する
+    async fn transfer_all(&self, event: TransferAllBooks) -> AppResult<u64> {
+        if event.from_user == event.to_user {
+            return Err(AppError::UnprocessableEntity(
+                "cannot transfer books to the same user".into(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+        ensure_user_exists(&mut tx, event.from_user).await?;
+        ensure_user_exists(&mut tx, event.to_user).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM book_transfers
+            WHERE from_user_id = $1
+            OR to_user_id = $1
+            OR book_id IN (SELECT book_id FROM books WHERE user_id = $1)
+            "#,
+            event.from_user as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let book_ids = sqlx::query_scalar!(
+            r#"
+            UPDATE books SET user_id = $2 WHERE user_id = $1
+            RETURNING book_id AS "book_id: BookId"
+            "#,
+            event.from_user as _,
+            event.to_user as _,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if event.requested_user != event.from_user {
+            for &book_id in &book_ids {
+                record_admin_action(
+                    &mut tx,
+                    book_id,
+                    event.from_user,
+                    event.requested_user,
+                    BookAdminActionKind::Transfer,
+                )
+                .await?;
+            }
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(book_ids.len() as u64)
+    }
+
+    /// 蔵書の状態を変える
+    /// 借りているユーザーが紛失を報告した場合は、貸出を紛失として返却済みにする
+    async fn change_copy_status(&self, event: ChangeCopyStatus) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let to = event.action.target();
+
+        // 貸し出せる状態に戻る場合は予約を進めるため、貸出と同じくSERIALIZABLEに設定
+        if to == CopyStatus::Available {
+            self.set_transaction_serializable(&mut tx).await?;
+        }
+
+        let state = sqlx::query_as!(
+            CopyStateRow,
+            r#"
+            SELECT
+                b.user_id AS owned_by,
+                bc.status,
+                c.checkout_id AS "checkout_id?: CheckoutId",
+                c.user_id AS "borrowed_by?: UserId"
+            FROM book_copies AS bc
+            INNER JOIN books AS b USING(book_id)
+            LEFT OUTER JOIN checkouts AS c ON c.copy_id = bc.copy_id
+            WHERE bc.book_id = $1
+            AND bc.copy_id = $2
+            AND b.deleted_at IS NULL
+            FOR UPDATE OF bc
+            "#,
+            event.book_id as _,
+            event.copy_id as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified copy not found".into()))?;
+
+        // 所有者と管理者はすべての操作を、借りているユーザーは紛失・破損の報告のみを行える
+        let is_borrower = state.borrowed_by == Some(event.requested_user);
+        if state.owned_by != event.requested_user
+            && !event.requested_by_admin
+            && !(is_borrower && event.action.allowed_for_borrower())
+        {
+            return Err(AppError::ForbiddenOperationError);
+        }
+
+        let from = state
+            .status
+            .parse::<CopyStatus>()
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+        if !event.action.can_apply_to(from) {
+            return Err(AppError::UnprocessableEntity(format!(
+                "蔵書（{}）は{}のため、{}を行えません",
+                event.copy_id,
+                from.as_ref(),
+                event.action.as_ref()
+            )));
+        }
+        if state.checkout_id.is_some() && !event.action.allowed_for_borrower() {
+            return Err(AppError::UnprocessableEntity(format!(
+                "蔵書（{}）は貸出中のため、{}を行えません",
+                event.copy_id,
+                event.action.as_ref()
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE book_copies SET status = $2 WHERE copy_id = $1
+            "#,
+            event.copy_id as _,
+            to.as_ref(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 紛失した蔵書は戻ってこないため、貸出を開いたままにせず紛失として終える
+        if let Some(checkout_id) = state.checkout_id
+            && to == CopyStatus::Lost
+        {
+            sqlx::query!(
+                r#"
+                INSERT INTO returned_checkouts
+                    (checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, outcome)
+                SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, $2
+                FROM checkouts
+                WHERE checkout_id = $1
+                "#,
+                checkout_id as _,
+                CheckoutOutcome::Lost.as_ref(),
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            sqlx::query!(
+                r#"
+                DELETE FROM checkouts WHERE checkout_id = $1
+                "#,
+                checkout_id as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO book_copy_status_changes
+                (copy_id, action, from_status, to_status, note, changed_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            event.copy_id as _,
+            event.action.as_ref(),
+            from.as_ref(),
+            to.as_ref(),
+            event.note,
+            event.requested_user as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 修理などから戻った蔵書は、順番待ちの予約者に回す
+        if to == CopyStatus::Available {
+            advance_holds(
+                &mut tx,
+                event.book_id,
+                chrono::Utc::now(),
+                self.hold_pickup_days,
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 蔵書の状態の変更履歴を、古い順に取得する
+    async fn find_copy_status_history(
+        &self,
+        book_id: BookId,
+        copy_id: CopyId,
+    ) -> AppResult<Vec<CopyStatusChange>> {
+        let rows = sqlx::query_as!(
+            CopyStatusChangeRow,
+            r#"
+            SELECT
+                s.status_change_id,
+                s.copy_id,
+                s.action,
+                s.from_status,
+                s.to_status,
+                s.note,
+                u.user_id AS "changed_by?: UserId",
+                u.name AS "changed_by_name?",
+                s.created_at
+            FROM book_copy_status_changes AS s
+            INNER JOIN book_copies AS bc USING(copy_id)
+            LEFT OUTER JOIN users AS u ON u.user_id = s.changed_by
+            WHERE bc.book_id = $1
+            AND s.copy_id = $2
+            ORDER BY s.created_at ASC, s.status_change_id ASC
+            "#,
+            book_id as _,
+            copy_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        rows.into_iter().map(CopyStatusChange::try_from).collect()
+    }
+
+    /// 書籍の置き場所を移す
+    async fn move_to(&self, event: MoveBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 置き場所を移せるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE books SET location_id = $2 WHERE book_id = $1
+            "#,
+            event.book_id as _,
+            event.location_id as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                AppError::EntityNotFound("specified location not found".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::Move,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍の貸出期間を変える
+    async fn set_loan_period(&self, event: SetBookLoanPeriod) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 貸出期間を変えられるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE books SET loan_period_days = $2 WHERE book_id = $1
+            "#,
+            event.book_id as _,
+            event.loan_period_days,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::SetLoanPeriod,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 管理者が指定したユーザーの書籍に対して行った操作を取得する
+    async fn find_admin_actions(&self, owner: UserId) -> AppResult<Vec<BookAdminAction>> {
+        let rows = sqlx::query_as!(
+            BookAdminActionRow,
+            r#"
+            SELECT
+                a.admin_action_id,
+                a.book_id,
+                b.title AS book_title,
+                a.action,
+                u.user_id AS "admin_id?: UserId",
+                u.name AS "admin_name?",
+                a.created_at
+            FROM book_admin_actions AS a
+            INNER JOIN books AS b USING(book_id)
+            LEFT OUTER JOIN users AS u ON u.user_id = a.admin_id
+            WHERE a.owner_id = $1
+            ORDER BY a.created_at DESC, a.admin_action_id DESC
+            "#,
+            owner as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        rows.into_iter().map(BookAdminAction::try_from).collect()
+    }
+
+    /// 削除済みの書籍を、削除日時の新しい順に取得する
+    async fn find_deleted(
+        &self,
+        options: DeletedBookListOptions,
+    ) -> AppResult<PaginatedList<DeletedBook>> {
+        let DeletedBookListOptions { limit, offset } = options;
+
+        let rows = sqlx::query_as!(
+            DeletedBookRow,
+            r#"
+            SELECT
+                COUNT(*) OVER() AS "total!",
+                b.book_id AS id,
+                b.deleted_at AS "deleted_at!"
+            FROM books AS b
+            WHERE b.deleted_at IS NOT NULL
+            ORDER BY b.deleted_at DESC, b.book_id ASC
+            LIMIT $1
+            OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let book_ids = rows.iter().map(|r| r.id).collect::<Vec<_>>();
+        // 2つのクエリの間に完全に削除された書籍は結果に含まれないため、書籍IDで対応づける
+        let deleted_at = rows
+            .into_iter()
+            .map(|r| (r.id, r.deleted_at))
+            .collect::<HashMap<_, _>>();
+        let items = self
+            .find_by_ids(&book_ids)
+            .await?
+            .into_iter()
+            .filter_map(|book| {
+                let deleted_at = *deleted_at.get(&book.id)?;
+                Some(DeletedBook { book, deleted_at })
+            })
+            .collect();
+
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        })
+    }
+
+    /// 削除済みの書籍を復元する
+    async fn restore(&self, event: RestoreBook) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE books
+            SET deleted_at = NULL
+            WHERE book_id = $1
+            AND deleted_at IS NOT NULL
+            "#,
+            event.book_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified deleted book not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 削除済みの書籍を完全に削除する
+    /// 削除済みにする時点で貸出中でないことを確認しているが、返却済みの貸出記録は履歴に表示されなくなる
+    /// 書影の削除は書籍の削除の後に行い、失敗してもログに残すのみとする
+    async fn purge(&self, event: PurgeBook) -> AppResult<()> {
+        let cover_id = sqlx::query_scalar!(
+            r#"
+            DELETE FROM books
+            WHERE book_id = $1
+            AND deleted_at IS NOT NULL
+            RETURNING cover_id
+            "#,
+            event.book_id as _,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified deleted book not found".into()))?;
+
+        self.delete_cover_blobs(event.book_id, cover_id).await;
+
+        Ok(())
+    }
+
+    /// 書籍に蔵書を追加する
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        // 追加した蔵書で予約を進めるため、貸出と同じくSERIALIZABLEに設定
+        self.set_transaction_serializable(&mut tx).await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 蔵書を追加できるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO book_copies (book_id, barcode)
+            VALUES ($1, COALESCE($2, next_book_copy_barcode()))
+            "#,
+            event.book_id as _,
+            event.barcode,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::UnprocessableEntity("specified barcode is already in use".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::AddCopy,
+            )
+            .await?;
+        }
+        // 追加した蔵書は、順番待ちの予約者に回す
+        advance_holds(
+            &mut tx,
+            event.book_id,
+            chrono::Utc::now(),
+            self.hold_pickup_days,
+        )
+        .await?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍から蔵書を取り除く。貸出中の蔵書は取り除けない
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // 蔵書を取り除けるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        let checked_out = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id
+            ) AS "checked_out!"
+            FROM book_copies AS bc
+            WHERE bc.copy_id = $1
+            AND bc.book_id = $2
+            "#,
+            event.copy_id as _,
+            event.book_id as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified copy not found".into()))?;
+
+        if checked_out {
+            return Err(AppError::UnprocessableEntity(format!(
+                "蔵書（{}）は貸出中のため取り除けません",
+                event.copy_id
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM book_copies WHERE copy_id = $1
+            "#,
+            event.copy_id as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::DeleteCopy,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍にタグを付ける
+    /// まだないタグは作成し、既に付いているタグはそのままにする
+    async fn add_tags(&self, event: AddBookTags) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // タグを付けられるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO tags (name)
+            SELECT * FROM UNNEST($1::text[])
+            ON CONFLICT (name) DO NOTHING
+            "#,
+            &event.tags
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO book_tags (book_id, tag_id)
+            SELECT $1, tag_id FROM tags WHERE name = ANY($2)
+            ON CONFLICT (book_id, tag_id) DO NOTHING
+            "#,
+            event.book_id as _,
+            &event.tags
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::AddTags,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍からタグを外す
+    async fn remove_tag(&self, event: RemoveBookTag) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, event.book_id).await?;
+        // タグを外せるのは所有者か管理者のみ
+        let by_admin =
+            authorize_book_edit(&locked, event.requested_user, event.requested_by_admin)?;
+
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM book_tags AS bt
+            USING tags AS t
+            WHERE bt.tag_id = t.tag_id
+            AND bt.book_id = $1
+            AND t.name = $2
+            "#,
+            event.book_id as _,
+            event.tag,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified tag not found on the book".into(),
+            ));
+        }
+
+        if by_admin {
+            record_admin_action(
+                &mut tx,
+                event.book_id,
+                locked.owner,
+                event.requested_user,
+                BookAdminActionKind::RemoveTag,
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 書籍に付いているすべてのタグを、名前順に書籍の数とともに取得する
+    async fn find_all_tags(&self) -> AppResult<Vec<TagCount>> {
+        sqlx::query_as!(
+            TagCountRow,
+            r#"
+            SELECT t.name, COUNT(*) AS "book_count!"
+            FROM tags AS t
+            INNER JOIN book_tags AS bt USING(tag_id)
+            INNER JOIN books AS b USING(book_id)
+            WHERE b.deleted_at IS NULL
+            GROUP BY t.name
+            ORDER BY t.name ASC
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map(|rows| rows.into_iter().map(TagCount::from).collect())
+        .map_err(AppError::SpecificOperationError)
+    }
+
+    /// 書籍の書影を登録する
+    /// 元画像とサムネイルをBlobストレージに保存し、画像形式を書籍に記録する
+    async fn upload_cover(&self, event: UploadBookCover) -> AppResult<()> {
+        let UploadBookCover {
+            book_id,
+            image_type,
+            data,
+            requested_user,
+            requested_by_admin,
+        } = event;
+
+        let mut tx = self.db.begin().await?;
+        let locked = lock_book(&mut tx, book_id).await?;
+        // 書影を登録できるのは所有者か管理者のみ
+        let by_admin = authorize_book_edit(&locked, requested_user, requested_by_admin)?;
+
+        // 画像のデコードとエンコードはCPUを使うため、非同期ランタイムをブロックしないよう別スレッドで行う
+        let (data, thumbnail) =
+            tokio::task::spawn_blocking(move || -> AppResult<(Vec<u8>, Vec<u8>)> {
+                let thumbnail = make_thumbnail(&data, image_type)?;
+                Ok((data, thumbnail))
+            })
+            .await
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))??;
+
+        let old_cover_id = sqlx::query_scalar!(
+            r#"
+            SELECT cover_id FROM books WHERE book_id = $1
+            "#,
+            book_id as _,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 新しい書影は別のキーに保存し、書籍の更新を確定してから前の書影を削除する
+        // 途中で失敗した場合は新しい書影を削除し、前の書影をそのまま残す
+        let cover_id = Uuid::new_v4();
+        let res = async move {
+            self.blob_storage
+                .put(
+                    &cover_key(book_id, Some(cover_id), CoverSize::Original),
+                    data,
+                )
+                .await?;
+            self.blob_storage
+                .put(
+                    &cover_key(book_id, Some(cover_id), CoverSize::Thumbnail),
+                    thumbnail,
+                )
+                .await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE books
+                SET cover_content_type = $1, cover_id = $2
+                WHERE book_id = $3
+                "#,
+                image_type.as_ref(),
+                cover_id,
+                book_id as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            if by_admin {
+                record_admin_action(
+                    &mut tx,
+                    book_id,
+                    locked.owner,
+                    requested_user,
+                    BookAdminActionKind::UploadCover,
+                )
+                .await?;
+            }
+            tx.commit().await.map_err(AppError::TransactionError)
+        }
+        .await;
+
+        if let Err(e) = res {
+            self.delete_cover_blobs(book_id, Some(cover_id)).await;
+            return Err(e);
+        }
+        self.delete_cover_blobs(book_id, old_cover_id).await;
+
+        Ok(())
+    }
+
+    /// 書籍の書影を取得する
+    async fn find_cover(&self, book_id: BookId, size: CoverSize) -> AppResult<Option<BookCover>> {
+        let Some(row) = sqlx::query!(
+            r#"
+            SELECT cover_content_type, cover_id FROM books
+            WHERE book_id = $1 AND deleted_at IS NULL
+            "#,
+            book_id as _,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(image_type) = row
+            .cover_content_type
+            .and_then(|c| c.parse::<CoverImageType>().ok())
+        else {
+            return Ok(None);
+        };
+
+        let data = self
+            .blob_storage
+            .get(&cover_key(book_id, row.cover_id, size))
+            .await?;
+        Ok(data.map(|data| BookCover { image_type, data }))
+    }
+}
+
+/// 行ロックをかけて取得した、削除済みでない書籍の編集に必要な情報
+struct LockedBook {
+    snapshot: BookSnapshot,
+    version: i32,
+    owner: UserId,
+}
+
+/// 書籍の編集可能な項目の値と書誌情報の版、所有者を、行ロックをかけて取得する
+/// 削除済みの書籍は存在しないものとする
+async fn lock_book(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+) -> AppResult<LockedBook> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            title, author, isbn, isbn_display, description, publisher, published_on, page_count,
+            version, user_id AS "user_id: UserId"
+        FROM books
+        WHERE book_id = $1
+        AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        book_id as _,
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+    let snapshot = BookSnapshotRow {
+        title: row.title,
+        author: row.author,
+        isbn: row.isbn,
+        isbn_display: row.isbn_display,
+        description: row.description,
+        publisher: row.publisher,
+        published_on: row.published_on,
+        page_count: row.page_count,
+    };
+    Ok(LockedBook {
+        snapshot: snapshot.into(),
+        version: row.version,
+        owner: row.user_id,
+    })
+}
+
+/// 書籍を編集できるのは所有者か管理者のみとする
+/// 管理者が所有者以外の書籍を編集する場合はtrueを返す
+fn authorize_book_edit(
+    locked: &LockedBook,
+    requested_user: UserId,
+    requested_by_admin: bool,
+) -> AppResult<bool> {
+    if locked.owner == requested_user {
+        return Ok(false);
+    }
+    if requested_by_admin {
+        return Ok(true);
+    }
+    Err(AppError::ForbiddenOperationError)
+}
+
+/// 指定したユーザーが存在することを確認する
+async fn ensure_user_exists(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: UserId,
+) -> AppResult<()> {
+    let exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM users WHERE user_id = $1) AS "exists!"
+        "#,
+        user_id as _,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    if !exists {
+        return Err(AppError::EntityNotFound(
+            "Specified user not found".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 管理者が所有者以外の書籍に対して行った操作を記録する
+async fn record_admin_action(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    owner: UserId,
+    admin: UserId,
+    action: BookAdminActionKind,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO book_admin_actions (book_id, owner_id, admin_id, action)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        book_id as _,
+        owner as _,
+        admin as _,
+        action.as_ref(),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 書誌情報の版が、指定された版と一致することを確認する
+fn check_version(book_id: BookId, expected: Option<i32>, actual: i32) -> AppResult<()> {
+    if expected.is_some_and(|expected| expected != actual) {
+        return Err(AppError::PreconditionFailed(format!(
+            "書籍（{book_id}）は他のユーザーによって変更されています"
+        )));
+    }
+    Ok(())
+}
+
+/// 書籍の内容をafterの値で更新し、変更履歴に記録する
+/// beforeから値が変わっていない場合は何もせずにfalseを返す
+async fn write_book_snapshot(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    before: BookSnapshot,
+    after: BookSnapshot,
+    user_id: UserId,
+) -> AppResult<bool> {
+    if before.changed_fields(&after).is_empty() {
+        return Ok(false);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE books
+        SET title = $1, author = $2, isbn = $3, isbn_display = $4, description = $5,
+            publisher = $6, published_on = $7, page_count = $8, version = version + 1
+        WHERE book_id = $9
+        "#,
+        after.title,
+        after.author,
+        after.isbn.as_isbn13(),
+        after.isbn.display(),
+        after.description,
+        after.publisher,
+        after.published_on,
+        after.page_count,
+        book_id as _,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO book_revisions (book_id, user_id, before, after)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        book_id as _,
+        user_id as _,
+        Json(BookSnapshotRow::from(before)) as _,
+        Json(BookSnapshotRow::from(after)) as _,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(true)
+}
+
+/// 書籍と、連番のバーコードを持つ蔵書1冊を登録する
+pub(super) async fn insert_book(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    event: CreateBook,
+    user_id: UserId,
+) -> AppResult<BookId> {
+    let book_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO books (
+            title, author, isbn, isbn_display, description,
+            publisher, published_on, page_count, user_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING book_id AS "book_id: BookId"
+        "#,
+        event.title,
+        event.author,
+        event.isbn.as_isbn13(),
+        event.isbn.display(),
+        event.description,
+        event.publisher,
+        event.published_on,
+        event.page_count,
+        user_id as _
+    )
+    .fetch_one(&mut **tx)
+    .await
+    // sqlx::Error型をAppError型に変換する
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO book_copies (book_id)
+        VALUES ($1)
+        "#,
+        book_id as _
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    notify_wishlist_users(tx, book_id, &event.isbn, &event.title).await?;
+
+    Ok(book_id)
+}
+
+/// ほしいものリストに同じISBNを追加していたユーザーに、書籍が登録されたことを通知する
+/// 通知した項目には登録された書籍を記録し、同じ項目で再び通知しないようにする
+async fn notify_wishlist_users(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    isbn: &Isbn,
+    title: &str,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+        WITH fulfilled AS (
+            UPDATE wishlist_items
+            SET fulfilled_book_id = $1
+            WHERE isbn = $2
+            AND fulfilled_book_id IS NULL
+            RETURNING user_id
+        )
+        INSERT INTO notifications (user_id, kind, book_id, message)
+        SELECT DISTINCT user_id, $3, $1, $4
+        FROM fulfilled
+        "#,
+        book_id as _,
+        isbn.as_isbn13(),
+        NotificationKind::WishlistBookRegistered.as_ref(),
+        format!("ほしいものリストの「{title}」が登録されました"),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+/// 並び順をクエリに渡すキー名と昇順かどうかに変換する
+fn sort_key_and_direction(sort: Option<BookSort>, has_query: bool) -> (&'static str, bool) {
+    let Some(BookSort { key, order }) = sort else {
+        // 並び順が未指定の場合は、一致度または登録日時の降順とする
+        return if has_query {
+            ("relevance", false)
+        } else {
+            ("created_at", false)
+        };
+    };
+    let key = match key {
+        BookSortKey::Title => "title",
+        BookSortKey::Author => "author",
+        BookSortKey::CreatedAt => "created_at",
+        BookSortKey::UpdatedAt => "updated_at",
+        BookSortKey::MostBorrowed => "most_borrowed",
+        BookSortKey::Rating => "rating",
+    };
+    (key, order == SortOrder::Asc)
+}
+
+/// 書影をBlobストレージに保存する際のキー
+/// 書影のIDがない場合は、IDを導入する前に登録された書影のキーとする
+fn cover_key(book_id: BookId, cover_id: Option<Uuid>, size: CoverSize) -> String {
+    let name = match size {
+        CoverSize::Original => "original",
+        CoverSize::Thumbnail => "thumbnail",
+    };
+    match cover_id {
+        Some(cover_id) => format!("covers/{book_id}/{cover_id}/{name}"),
+        None => format!("covers/{book_id}/{name}"),
+    }
+}
+
+/// 元画像と同じ形式で、幅と高さがTHUMBNAIL_SIZEに収まるサムネイルを生成する
+fn make_thumbnail(data: &[u8], image_type: CoverImageType) -> AppResult<Vec<u8>> {
+    let format = match image_type {
+        CoverImageType::Jpeg => image::ImageFormat::Jpeg,
+        CoverImageType::Png => image::ImageFormat::Png,
+    };
+    let image = image::load_from_memory_with_format(data, format).map_err(|_| {
+        AppError::UnprocessableEntity(format!(
+            "cover image could not be decoded as {}",
+            image_type.as_ref()
+        ))
+    })?;
+
+    let mut thumbnail = Vec::new();
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut Cursor::new(&mut thumbnail), format)
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+
+    Ok(thumbnail)
+}
+
+impl BookRepositoryImpl {
+    /// トランザクションの分離レベルをSERIALIZABLEに設定する
+    async fn set_transaction_serializable(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> AppResult<()> {
+        sqlx::query!("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        Ok(())
+    }
+
+    /// 書影の画像を削除する。削除に失敗してもログに残すのみとする
+    async fn delete_cover_blobs(&self, book_id: BookId, cover_id: Option<Uuid>) {
+        for size in [CoverSize::Original, CoverSize::Thumbnail] {
+            let key = cover_key(book_id, cover_id, size);
+            if let Err(e) = self.blob_storage.delete(&key).await {
+                tracing::error!(error = %e, key, "failed to delete cover");
+            }
+        }
+    }
+
+    /// 指定された書籍IDの書籍を、指定された順に取得する
+    async fn find_by_ids(&self, book_ids: &[BookId]) -> AppResult<Vec<Book>> {
+        let rows: Vec<BookRow> = sqlx::query_as!(
+            BookRow,
+            r#"
+            SELECT 
+                b.book_id AS book_id, 
+                b.title AS title, 
+                b.author AS author, 
+                b.isbn AS isbn, 
+                b.isbn_display AS isbn_display,
+                b.description AS description,
+                b.publisher,
+                b.published_on,
+                b.page_count,
+                u.user_id AS owned_by,
+                u.name AS owner_name,
+                b.cover_content_type,
+                b.version,
+                b.loan_period_days,
+                l.location_id AS "location_id?: LocationId",
+                l.site AS "location_site?",
+                l.room AS "location_room?",
+                l.shelf AS "location_shelf?",
+                r.average_rating,
+                COALESCE(r.review_count, 0) AS "review_count!"
+            FROM books AS b
+            INNER JOIN users AS u USING(user_id)
+            LEFT OUTER JOIN locations AS l ON l.location_id = b.location_id
+            LEFT OUTER JOIN (
+                SELECT book_id, AVG(rating)::float8 AS average_rating, COUNT(*) AS review_count
+                FROM book_reviews
+                GROUP BY book_id
+            ) AS r ON r.book_id = b.book_id
+            WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
+            -- 書籍IDを決めたクエリの並び順を維持する
+            ORDER BY array_position($1::uuid[], b.book_id)
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        // sqlx::Error型をAppError型に変換する
+        .map_err(AppError::SpecificOperationError)?;
+
+        self.load_books(rows).await
+    }
+
+    /// BookRowに蔵書とタグを加えてBookに変換する
+    async fn load_books(&self, rows: Vec<BookRow>) -> AppResult<Vec<Book>> {
+        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
+        let mut copies = self.find_copies(&book_ids).await?;
+        let mut tags = self.find_tags(&book_ids).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let copies = copies.remove(&row.book_id).unwrap_or_default();
+                let tags = tags.remove(&row.book_id).unwrap_or_default();
+                row.into_book(copies, tags)
+            })
+            .collect())
+    }
+
+    /// 指定された書籍IDに付いたタグ名を、名前順に取得する
+    async fn find_tags(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Vec<String>>> {
+        let rows = sqlx::query_as!(
+            BookTagRow,
+            r#"
+            SELECT bt.book_id, t.name
+            FROM book_tags AS bt
+            INNER JOIN tags AS t USING(tag_id)
+            WHERE bt.book_id = ANY($1)
+            ORDER BY t.name ASC
+            "#,
+            &book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut tags: HashMap<BookId, Vec<String>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.book_id).or_default().push(row.name);
+        }
+
+        Ok(tags)
+    }
+
+    /// 指定された書籍IDの蔵書を、貸出情報とともに取得する
+    async fn find_copies(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Vec<BookCopy>>> {
+        let rows = sqlx::query_as!(
+            BookCopyRow,
+            r#"
+            SELECT 
+                bc.copy_id,
+                bc.book_id,
+                bc.barcode,
+                bc.status,
+                c.checkout_id AS "checkout_id?: CheckoutId",
+                u.user_id AS "user_id?: UserId",
+                u.name AS "user_name?",
+                c.checked_out_at AS "checked_out_at?",
+                c.due_at AS "due_at?",
+                (
+                    SELECT COUNT(*) FROM checkout_renewals AS cr
+                    WHERE cr.checkout_id = c.checkout_id
+                ) AS "renewal_count!"
+            FROM book_copies AS bc
+            LEFT OUTER JOIN checkouts AS c USING(copy_id)
+            LEFT OUTER JOIN users AS u ON u.user_id = c.user_id
+            WHERE bc.book_id = ANY($1)
+            ORDER BY bc.created_at ASC, bc.copy_id ASC
+            "#,
+            &book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut copies: HashMap<BookId, Vec<BookCopy>> = HashMap::new();
+        for row in rows {
+            copies
+                .entry(row.book_id)
+                .or_default()
+                .push(BookCopy::try_from(row)?);
+        }
+
+        Ok(copies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::{
+        location::LocationRepositoryImpl,
+        review::ReviewRepositoryImpl,
+        test_helper::{book_repository, checkout_repository, create_test_user},
+        user::UserRepositoryImpl,
+    };
+    use crate::storage::{BlobStorage, local::LocalBlobStorage};
+    use kernel::model::book::{BookField, event::ImportBookRow};
+    use kernel::model::id::BookRevisionId;
+    use kernel::{
+        model::{
+            checkout::event::CreateCheckout,
+            location::event::{CreateLocation, DeleteLocation},
+            review::event::CreateReview,
+            user::event::DeleteUser,
+        },
+        repository::{
+            checkout::CheckoutRepository, location::LocationRepository, review::ReviewRepository,
+            user::UserRepository,
+        },
+    };
+    use shared::config::CheckoutConfig;
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_create_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        // RepositoryImplを初期化
+        let repository = book_repository(pool.clone());
+
+        // ユーザーを登録
+        let user = create_test_user(&pool, "Test User").await?;
+
+        // CreateBookイベントを作成
+        let book = CreateBook {
+            title: "Test Title".into(),
+            author: "Test Author".into(),
+            isbn: Isbn::from_str("978-4-7980-6170-2")?,
+            description: "Test Description".into(),
+            publisher: Some("Test Publisher".into()),
+            published_on: chrono::NaiveDate::from_ymd_opt(2019, 3, 15),
+            page_count: Some(320),
+        };
+        // 書籍を登録し、正常終了することを確認
+        repository.create(book, user.id).await?;
+
+        // 書籍を取得し、登録した書籍が含まれていることを確認
+        let options = BookListOptions {
+            limit: 10,
+            offset: 0,
+            ..Default::default()
+        };
+        let books = repository.find_all(options).await?;
+        assert_eq!(books.items.len(), 1);
+
+        // 取得した一覧から蔵書IDを取得し、
+        // find_by_idで取得できることを確認
+        let book_id = books.items[0].id;
+        let book = repository.find_by_id(book_id).await?;
+        assert!(book.is_some());
 
         // 取得した書籍の情報が登録した書籍と一致することを確認
         let Book {
@@ -288,43 +2192,1370 @@ mod tests {
             author,
             isbn,
             description,
+            publisher,
+            published_on,
+            page_count,
             owner,
             ..
         } = book.unwrap();
         assert_eq!(id, book_id);
         assert_eq!(title, "Test Title");
         assert_eq!(author, "Test Author");
-        assert_eq!(isbn, "Test ISBN");
+        assert_eq!(isbn.as_isbn13(), "9784798061702");
+        assert_eq!(isbn.display(), "978-4798061702");
         assert_eq!(description, "Test Description");
+        assert_eq!(publisher.as_deref(), Some("Test Publisher"));
+        assert_eq!(published_on, chrono::NaiveDate::from_ymd_opt(2019, 3, 15));
+        assert_eq!(page_count, Some(320));
         assert_eq!(owner.name, user.name);
         Ok(())
     }
 
     #[sqlx::test(fixtures("common", "book"))]
-    async fn test_update_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        // RepositoryImplを初期化
-        let repository = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+    async fn test_update_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        // RepositoryImplを初期化
+        let repository = book_repository(pool.clone());
+
+        // 更新する書籍のIDを指定
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b").unwrap();
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        const NEW_AUTHOR: &str = "New Author";
+        assert_ne!(book.author, NEW_AUTHOR);
+
+        // 書籍を更新
+        let update_book = UpdateBook {
+            book_id: book.id,
+            title: book.title,
+            author: NEW_AUTHOR.into(), // 更新箇所
+            isbn: book.isbn,
+            description: book.description,
+            publisher: book.publisher,
+            published_on: book.published_on,
+            page_count: book.page_count,
+            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c").unwrap(),
+            requested_by_admin: false,
+            expected_version: Some(book.version),
+        };
+        repository.update(update_book).await?;
+
+        // 書籍を再度取得し、更新が反映されて版が増えていることを確認
+        let updated_book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(updated_book.author, NEW_AUTHOR);
+        assert_eq!(updated_book.version, book.version + 1);
+
+        // 古い版を指定した更新と削除は失敗し、内容は変わらない
+        let res = repository
+            .update(UpdateBook {
+                book_id,
+                title: updated_book.title.clone(),
+                author: "Stale Author".into(),
+                isbn: updated_book.isbn.clone(),
+                description: updated_book.description.clone(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+                requested_user: updated_book.owner.id,
+                requested_by_admin: false,
+                expected_version: Some(book.version),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+        let res = repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: updated_book.owner.id,
+                requested_by_admin: false,
+                expected_version: Some(book.version),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.author, NEW_AUTHOR);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_patch_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+
+        let patch = |publisher: Option<Option<String>>| PatchBook {
+            book_id,
+            title: Some("Patched Title".into()),
+            author: None,
+            isbn: None,
+            description: None,
+            publisher,
+            published_on: None,
+            page_count: None,
+            requested_user: owner_id,
+            requested_by_admin: false,
+            expected_version: None,
+        };
+
+        // 指定した項目のみが更新される
+        repository
+            .patch(patch(Some(Some("Patched Publisher".into()))))
+            .await?;
+        let patched = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(patched.title, "Patched Title");
+        assert_eq!(patched.publisher.as_deref(), Some("Patched Publisher"));
+        assert_eq!(patched.author, book.author);
+        assert_eq!(patched.description, book.description);
+        assert_eq!(patched.isbn, book.isbn);
+
+        // Some(None)を指定した任意項目は値が削除される
+        repository.patch(patch(Some(None))).await?;
+        let patched = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(patched.publisher, None);
+
+        let revisions = repository.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].changed_fields(), vec![BookField::Publisher]);
+
+        // 所有者以外は更新できない
+        let res = repository
+            .patch(PatchBook {
+                requested_user: UserId::new(),
+                ..patch(None)
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_admin_override(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let admin = create_test_user(&pool, "Admin").await?;
+
+        // 存在しない書籍は、権限より先に404とする
+        let res = repository
+            .delete(DeleteBook {
+                book_id: BookId::new(),
+                requested_user: admin.id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 管理者は所有者以外の書籍も更新・削除でき、その操作が所有者向けに記録される
+        repository
+            .patch(PatchBook {
+                book_id,
+                title: None,
+                author: None,
+                isbn: None,
+                description: Some("Fixed by admin".into()),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+                requested_user: admin.id,
+                requested_by_admin: true,
+                expected_version: None,
+            })
+            .await?;
+
+        // 蔵書やタグも、所有者でも管理者でもない場合は403とし、管理者は操作できる
+        let res = repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: admin.id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: admin.id,
+                requested_by_admin: true,
+            })
+            .await?;
+        let copy_id = repository.find_by_id(book_id).await?.unwrap().copies[1].id;
+        repository
+            .delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id,
+                requested_user: admin.id,
+                requested_by_admin: true,
+            })
+            .await?;
+        repository
+            .add_tags(AddBookTags {
+                book_id,
+                tags: vec!["rust".into()],
+                requested_user: admin.id,
+                requested_by_admin: true,
+            })
+            .await?;
+        repository
+            .remove_tag(RemoveBookTag {
+                book_id,
+                tag: "rust".into(),
+                requested_user: admin.id,
+                requested_by_admin: true,
+            })
+            .await?;
+
+        repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: admin.id,
+                requested_by_admin: true,
+                expected_version: None,
+            })
+            .await?;
+        assert!(repository.find_by_id(book_id).await?.is_none());
+
+        let actions = repository.find_admin_actions(owner_id).await?;
+        assert_eq!(actions.len(), 6);
+        for kind in [
+            BookAdminActionKind::Update,
+            BookAdminActionKind::AddCopy,
+            BookAdminActionKind::DeleteCopy,
+            BookAdminActionKind::AddTags,
+            BookAdminActionKind::RemoveTag,
+            BookAdminActionKind::Delete,
+        ] {
+            assert!(actions.iter().any(|a| a.action == kind));
+        }
+        assert!(
+            actions
+                .iter()
+                .all(|a| a.book_id == book_id && a.admin.as_ref().map(|u| u.id) == Some(admin.id))
+        );
+        assert!(repository.find_admin_actions(admin.id).await?.is_empty());
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_transfer(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let user_repository = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let recipient = create_test_user(&pool, "Recipient").await?;
+        let transfer =
+            |to_user: UserId, requested_user: UserId, require_acceptance: bool| TransferBook {
+                book_id,
+                to_user,
+                require_acceptance,
+                requested_user,
+                requested_by_admin: false,
+            };
+
+        // 所有者以外は譲渡できず、所有者自身や存在しないユーザーには譲渡できない
+        let res = repository
+            .transfer(transfer(owner_id, recipient.id, false))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        let res = repository
+            .transfer(transfer(owner_id, owner_id, false))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let res = repository
+            .transfer(transfer(UserId::new(), owner_id, false))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 承認が必要な場合は、承認されるまで所有者は変わらない
+        let pending = repository
+            .transfer(transfer(recipient.id, owner_id, true))
+            .await?
+            .unwrap();
+        assert_eq!(pending.book_id, book_id);
+        assert_eq!(pending.from.id, owner_id);
+        assert_eq!(pending.to.id, recipient.id);
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, owner_id);
+        let incoming = repository.find_incoming_transfers(recipient.id).await?;
+        assert_eq!(incoming.len(), 1);
+        assert!(
+            repository
+                .find_incoming_transfers(owner_id)
+                .await?
+                .is_empty()
+        );
+
+        // 譲渡先以外は承認できず、拒否すると譲渡は取り消される
+        let res = repository
+            .accept_transfer(AcceptBookTransfer {
+                book_id,
+                requested_user: owner_id,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        repository
+            .decline_transfer(DeclineBookTransfer {
+                book_id,
+                requested_user: recipient.id,
+            })
+            .await?;
+        assert!(
+            repository
+                .find_incoming_transfers(recipient.id)
+                .await?
+                .is_empty()
+        );
+
+        // 承認すると所有者が変わる
+        repository
+            .transfer(transfer(recipient.id, owner_id, true))
+            .await?;
+        repository
+            .accept_transfer(AcceptBookTransfer {
+                book_id,
+                requested_user: recipient.id,
+            })
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, recipient.id);
+
+        // 承認が不要な場合はすぐに所有者が変わり、管理者による譲渡は記録される
+        assert!(
+            repository
+                .transfer(TransferBook {
+                    requested_by_admin: true,
+                    ..transfer(owner_id, owner_id, false)
+                })
+                .await?
+                .is_none()
+        );
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, owner_id);
+        let actions = repository.find_admin_actions(recipient.id).await?;
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].action, BookAdminActionKind::Transfer);
+
+        // 書籍を所有しているユーザーは削除できず、すべて譲渡すると削除できる
+        let res = user_repository.delete(DeleteUser { id: owner_id }).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let transferred = repository
+            .transfer_all(TransferAllBooks {
+                from_user: owner_id,
+                to_user: recipient.id,
+                requested_user: owner_id,
+            })
+            .await?;
+        // フィクスチャの書籍はすべて同じユーザーが所有している
+        assert_eq!(transferred, 3);
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, recipient.id);
+        user_repository.delete(DeleteUser { id: owner_id }).await?;
+        assert!(repository.find_by_id(book_id).await?.is_some());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_transfer_all_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
 
-        // 更新する書籍のIDを指定
-        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b").unwrap();
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let deleted_book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let recipient = create_test_user(&pool, "Recipient").await?;
+        let other = create_test_user(&pool, "Other").await?;
+        let admin = create_test_user(&pool, "Admin").await?;
+
+        // 削除済みの書籍と、承認待ちの譲渡がある状態にする
+        repository
+            .delete(DeleteBook {
+                book_id: deleted_book_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await?;
+        repository
+            .transfer(TransferBook {
+                book_id,
+                to_user: other.id,
+                require_acceptance: true,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        assert_eq!(repository.find_incoming_transfers(other.id).await?.len(), 1);
+
+        // 削除済みの書籍も譲渡され、承認待ちの譲渡は取り消される
+        let transferred = repository
+            .transfer_all(TransferAllBooks {
+                from_user: owner_id,
+                to_user: recipient.id,
+                requested_user: admin.id,
+            })
+            .await?;
+        assert_eq!(transferred, 3);
+        let deleted = repository
+            .find_deleted(DeletedBookListOptions {
+                limit: 10,
+                offset: 0,
+            })
+            .await?;
+        assert_eq!(deleted.items[0].book.id, deleted_book_id);
+        assert_eq!(deleted.items[0].book.owner.id, recipient.id);
+        assert!(
+            repository
+                .find_incoming_transfers(other.id)
+                .await?
+                .is_empty()
+        );
+
+        // 管理者による譲渡は、譲渡元のユーザーの書籍ごとに記録される
+        let actions = repository.find_admin_actions(owner_id).await?;
+        assert_eq!(actions.len(), 3);
+        assert!(
+            actions
+                .iter()
+                .all(|a| a.action == BookAdminActionKind::Transfer
+                    && a.admin.as_ref().map(|u| u.id) == Some(admin.id))
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_move_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let location_repository = LocationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = create_test_user(&pool, "Other").await?;
+        let location = location_repository
+            .create(CreateLocation::new("本社".into(), "3F".into(), "A".into()))
+            .await?;
+        let move_to = |location_id: Option<LocationId>, requested_user: UserId| MoveBook {
+            book_id,
+            location_id,
+            requested_user,
+            requested_by_admin: false,
+        };
+
+        // 置き場所は未設定の状態から始まる
+        assert!(
+            repository
+                .find_by_id(book_id)
+                .await?
+                .unwrap()
+                .location
+                .is_none()
+        );
+
+        // 所有者以外は移せず、存在しない置き場所には移せない
+        let res = repository
+            .move_to(move_to(Some(location.id), other.id))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        let res = repository
+            .move_to(move_to(Some(LocationId::new()), owner_id))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        repository
+            .move_to(move_to(Some(location.id), owner_id))
+            .await?;
         let book = repository.find_by_id(book_id).await?.unwrap();
-        const NEW_AUTHOR: &str = "New Author";
-        assert_ne!(book.author, NEW_AUTHOR);
+        assert_eq!(book.location, Some(location.clone()));
 
-        // 書籍を更新
-        let update_book = UpdateBook {
-            book_id: book.id,
-            title: book.title,
-            author: NEW_AUTHOR.into(), // 更新箇所
-            isbn: book.isbn,
-            description: book.description,
-            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c").unwrap(),
+        // 置き場所で絞り込める
+        let books = repository
+            .find_all(BookListOptions {
+                limit: 10,
+                location: Some(location.id),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(
+            books.items.iter().map(|b| b.id).collect::<Vec<_>>(),
+            vec![book_id]
+        );
+
+        // 書籍が置かれている場所は削除できない
+        let res = location_repository
+            .delete(DeleteLocation::new(location.id))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 管理者による移動は所有者向けに記録される
+        repository
+            .move_to(MoveBook {
+                requested_by_admin: true,
+                ..move_to(None, other.id)
+            })
+            .await?;
+        assert!(
+            repository
+                .find_by_id(book_id)
+                .await?
+                .unwrap()
+                .location
+                .is_none()
+        );
+        let actions = repository.find_admin_actions(owner_id).await?;
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].action, BookAdminActionKind::Move);
+        location_repository
+            .delete(DeleteLocation::new(location.id))
+            .await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_revisions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        let original_description = book.description.clone();
+        assert!(repository.find_revisions(book_id).await?.is_empty());
+
+        let update = |description: &str| UpdateBook {
+            book_id,
+            title: book.title.clone(),
+            author: book.author.clone(),
+            isbn: book.isbn.clone(),
+            description: description.into(),
+            publisher: book.publisher.clone(),
+            published_on: book.published_on,
+            page_count: book.page_count,
+            requested_user: owner_id,
+            requested_by_admin: false,
+            expected_version: None,
         };
-        repository.update(update_book).await?;
 
-        // 書籍を再度取得し、更新が反映されていることを確認
-        let updated_book = repository.find_by_id(book_id).await?.unwrap();
-        assert_eq!(updated_book.author, NEW_AUTHOR);
+        // 変更のない更新は記録しない
+        repository.update(update(&original_description)).await?;
+        assert!(repository.find_revisions(book_id).await?.is_empty());
+
+        repository.update(update("")).await?;
+        let revisions = repository.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 1);
+        let revision = &revisions[0];
+        assert_eq!(revision.changed_fields(), vec![BookField::Description]);
+        assert_eq!(revision.before.description, original_description);
+        assert_eq!(revision.after.description, "");
+        assert_eq!(revision.changed_by.as_ref().unwrap().id, owner_id);
+
+        // 続けて2回編集し、3件の変更履歴がある状態にする
+        repository
+            .update(UpdateBook {
+                title: "Second Title".into(),
+                ..update("")
+            })
+            .await?;
+        repository
+            .update(UpdateBook {
+                title: "Third Title".into(),
+                ..update("Third Description")
+            })
+            .await?;
+        let revisions = repository.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 3);
+        // 変更履歴は新しい順に並ぶ
+        let second = revisions[1].id;
+        let first = revisions[2].id;
+
+        // 所有者でも管理者でもないユーザーは戻せない
+        let other = create_test_user(&pool, "Other User").await?;
+        let res = repository
+            .revert(RevertBook {
+                book_id,
+                revision_id: second,
+                requested_user: other.id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        // 指定した変更履歴の時点の内容に戻る
+        repository
+            .revert(RevertBook {
+                book_id,
+                revision_id: second,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        let reverted = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(reverted.title, "Second Title");
+        assert_eq!(reverted.description, "");
+        assert_eq!(reverted.author, book.author);
+
+        // 管理者は所有者以外の書籍も戻せる。戻した内容も履歴に残る
+        repository
+            .revert(RevertBook {
+                book_id,
+                revision_id: first,
+                requested_user: other.id,
+                requested_by_admin: true,
+            })
+            .await?;
+        let reverted = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(reverted.title, book.title);
+        assert_eq!(reverted.description, "");
+        let revisions = repository.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 5);
+        assert_eq!(revisions[0].after.title, book.title);
+        assert_eq!(revisions[0].changed_by.as_ref().unwrap().id, other.id);
+
+        // 別の書籍の変更履歴は指定できない
+        let res = repository
+            .revert(RevertBook {
+                book_id,
+                revision_id: BookRevisionId::new(),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_all_with_filters(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool.clone());
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let checked_out_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+
+        // 1冊だけ貸し出しておく
+        checkout_repository
+            .create(CreateCheckout::new(
+                checked_out_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+
+        let find = |options: BookListOptions| {
+            let repository = &repository;
+            async move {
+                repository
+                    .find_all(BookListOptions {
+                        limit: 10,
+                        ..options
+                    })
+                    .await
+                    .map(|books| books.items.into_iter().map(|b| b.id).collect::<Vec<_>>())
+            }
+        };
+
+        // キーワード検索（タイトルの部分一致、ハイフンなしのISBN）
+        let books = find(BookListOptions {
+            q: Some("Rust".into()),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books.len(), 3);
+        let books = find(BookListOptions {
+            q: Some("9784065301951".into()),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books, vec![checked_out_id]);
+
+        // 著者での絞り込み
+        let books = find(BookListOptions {
+            author: Some("高野".into()),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books, vec![checked_out_id]);
+
+        // 所有者での絞り込み
+        let books = find(BookListOptions {
+            owner: Some(owner_id),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books.len(), 3);
+        let books = find(BookListOptions {
+            owner: Some(UserId::new()),
+            ..Default::default()
+        })
+        .await?;
+        assert!(books.is_empty());
+
+        // 貸出状態での絞り込み
+        let books = find(BookListOptions {
+            availability: Some(BookAvailability::CheckedOut),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books, vec![checked_out_id]);
+        let books = find(BookListOptions {
+            availability: Some(BookAvailability::Available),
+            ..Default::default()
+        })
+        .await?;
+        assert_eq!(books.len(), 2);
+        assert!(!books.contains(&checked_out_id));
+
+        // 部分一致の検索では、%や_をワイルドカードではなく文字そのものとして扱う
+        sqlx::query!(
+            "UPDATE books SET title = '100% Rust' WHERE book_id = $1",
+            checked_out_id as _
+        )
+        .execute(&pool)
+        .await?;
+        for q in ["100%", "%"] {
+            let books = find(BookListOptions {
+                q: Some(q.into()),
+                ..Default::default()
+            })
+            .await?;
+            assert_eq!(books, vec![checked_out_id]);
+        }
+        let books = find(BookListOptions {
+            author: Some("_".into()),
+            ..Default::default()
+        })
+        .await?;
+        assert!(books.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_all_with_sort(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool.clone());
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let borrowed_id = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+
+        checkout_repository
+            .create(CreateCheckout::new(
+                borrowed_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+
+        let find = |sort: BookSort| {
+            let repository = &repository;
+            async move {
+                repository
+                    .find_all(BookListOptions {
+                        limit: 10,
+                        sort: Some(sort),
+                        ..Default::default()
+                    })
+                    .await
+                    .map(|books| books.items.into_iter().map(|b| b.id).collect::<Vec<_>>())
+            }
+        };
+
+        // 昇順と降順で逆の並びになる
+        let asc = find(BookSort {
+            key: BookSortKey::Title,
+            order: SortOrder::Asc,
+        })
+        .await?;
+        let mut desc = find(BookSort {
+            key: BookSortKey::Title,
+            order: SortOrder::Desc,
+        })
+        .await?;
+        desc.reverse();
+        assert_eq!(asc.len(), 3);
+        assert_eq!(asc, desc);
+
+        // フィクスチャの書籍は登録日時が同じため、book_idの順に並ぶ
+        let mut expected = asc.clone();
+        expected.sort_by_key(|id| std::cmp::Reverse(id.raw()));
+        let created = find(BookSort::by(BookSortKey::CreatedAt)).await?;
+        assert_eq!(created, expected);
+
+        // 貸出回数の多い順
+        let borrowed = find(BookSort::by(BookSortKey::MostBorrowed)).await?;
+        assert_eq!(borrowed[0], borrowed_id);
+
+        // 評価の高い順。レビューのない書籍は後ろに並ぶ
+        let rated_id = asc[2];
+        ReviewRepositoryImpl::new(ConnectionPool::new(pool.clone()))
+            .create(CreateReview::new(rated_id, 1, None, owner_id))
+            .await?;
+        let rated = find(BookSort::by(BookSortKey::Rating)).await?;
+        assert_eq!(rated[0], rated_id);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_all_by_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let options = || BookListOptions {
+            limit: 2,
+            sort: Some(BookSort::by(BookSortKey::Title)),
+            ..Default::default()
+        };
+
+        // offsetによるページネーションと同じ順序で取得できることを確認
+        let expected = repository
+            .find_all(BookListOptions {
+                limit: 10,
+                ..options()
+            })
+            .await?
+            .into_inner()
+            .into_iter()
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+
+        let first = repository.find_all_by_cursor(options(), None).await?;
+        assert_eq!(first.items.len(), 2);
+        assert!(first.next_cursor.is_some());
+
+        // ページの途中で書籍が追加されても、取得済みの書籍が重複しないことを確認
+        repository
+            .create(
+                CreateBook {
+                    title: "A New Book".into(),
+                    author: "Test Author".into(),
+                    isbn: Isbn::from_str("978-4-7980-6170-2")?,
+                    description: "Test Description".into(),
+                    publisher: None,
+                    published_on: None,
+                    page_count: None,
+                },
+                owner_id,
+            )
+            .await?;
+
+        let second = repository
+            .find_all_by_cursor(options(), first.next_cursor)
+            .await?;
+        assert_eq!(second.items.len(), 1);
+        assert!(second.next_cursor.is_none());
+
+        let walked = first
+            .items
+            .into_iter()
+            .chain(second.items)
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+        assert_eq!(walked, expected);
+
+        // 不正なカーソル、並び順の異なるカーソルはエラーになる
+        let res = repository
+            .find_all_by_cursor(options(), Some("invalid".into()))
+            .await;
+        assert!(matches!(res, Err(AppError::InvalidCursorError)));
+        let cursor = repository
+            .find_all_by_cursor(options(), None)
+            .await?
+            .next_cursor;
+        let res = repository
+            .find_all_by_cursor(
+                BookListOptions {
+                    sort: Some(BookSort::by(BookSortKey::Author)),
+                    ..options()
+                },
+                cursor,
+            )
+            .await;
+        assert!(matches!(res, Err(AppError::InvalidCursorError)));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_by_isbn(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+
+        // 同じISBNの書籍をISBN-10の形式で追加で登録する
+        repository
+            .create(
+                CreateBook {
+                    title: "実践Rustプログラミング入門（2冊目）".into(),
+                    author: "初田直也他".into(),
+                    isbn: Isbn::from_str("4-7980-6170-0")?,
+                    description: "".into(),
+                    publisher: None,
+                    published_on: None,
+                    page_count: None,
+                },
+                owner_id,
+            )
+            .await?;
+        checkout_repository
+            .create(CreateCheckout::new(
+                book_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+
+        // バーコードから読み取ったISBN-13で、両方の書籍が貸出状況とともに取得できる
+        let books = repository
+            .find_by_isbn(Isbn::from_str("9784798061702")?)
+            .await?;
+        assert_eq!(books.len(), 2);
+        assert_eq!(books[0].id, book_id);
+        assert_eq!(books[0].available_copies(), 0);
+        assert_eq!(books[1].available_copies(), 1);
+
+        // 一致する書籍がない場合は空で返る
+        let books = repository
+            .find_by_isbn(Isbn::from_str("9780804429573")?)
+            .await?;
+        assert!(books.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_soft_delete(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool.clone());
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let checked_out_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let options = || BookListOptions {
+            limit: 10,
+            offset: 0,
+            ..Default::default()
+        };
+        let deleted_options = || DeletedBookListOptions {
+            limit: 10,
+            offset: 0,
+        };
+
+        // 貸出中の書籍は削除できず、貸出も残る
+        checkout_repository
+            .create(CreateCheckout::new(
+                checked_out_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+        let res = repository
+            .delete(DeleteBook {
+                book_id: checked_out_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        assert_eq!(
+            checkout_repository.find_unreturned_all(false).await?.len(),
+            1
+        );
+
+        // 所有者以外は削除できない
+        let res = repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: UserId::new(),
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        // 削除済みの書籍は一覧や取得の対象から外れ、削除済みの一覧に含まれる
+        let total = repository.find_all(options()).await?.total;
+        repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await?;
+        assert!(repository.find_by_id(book_id).await?.is_none());
+        assert_eq!(repository.find_all(options()).await?.total, total - 1);
+        let deleted = repository.find_deleted(deleted_options()).await?;
+        assert_eq!(deleted.total, 1);
+        assert_eq!(deleted.items[0].book.id, book_id);
+
+        // 削除済みの書籍は再度削除できない
+        let res = repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 復元すると再び取得できる
+        repository.restore(RestoreBook { book_id }).await?;
+        assert!(repository.find_by_id(book_id).await?.is_some());
+        assert_eq!(repository.find_deleted(deleted_options()).await?.total, 0);
+
+        // 削除済みでない書籍は、復元も完全な削除もできない
+        let res = repository.restore(RestoreBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        let res = repository.purge(PurgeBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 完全に削除すると、復元できなくなる
+        repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await?;
+        repository.purge(PurgeBook { book_id }).await?;
+        assert_eq!(repository.find_deleted(deleted_options()).await?.total, 0);
+        let res = repository.restore(RestoreBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_cover(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join(format!("book-covers-{}", uuid::Uuid::new_v4()));
+        let blob_storage = Arc::new(LocalBlobStorage::new(&shared::config::StorageConfig {
+            root: root.clone(),
+        }));
+        let repository = BookRepositoryImpl::new(
+            ConnectionPool::new(pool.clone()),
+            blob_storage.clone(),
+            CheckoutConfig::DEFAULT_HOLD_PICKUP_DAYS,
+        );
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+
+        // 書影が登録されていない場合
+        assert!(
+            repository
+                .find_by_id(book_id)
+                .await?
+                .unwrap()
+                .cover
+                .is_none()
+        );
+        assert!(
+            repository
+                .find_cover(book_id, CoverSize::Original)
+                .await?
+                .is_none()
+        );
+
+        let mut png = Vec::new();
+        image::RgbImage::new(400, 300)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)?;
+
+        // 所有者以外は書影を登録できない
+        let res = repository
+            .upload_cover(UploadBookCover {
+                book_id,
+                image_type: CoverImageType::Png,
+                data: png.clone(),
+                requested_user: UserId::new(),
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        // 画像として読み込めないデータは登録できない
+        let res = repository
+            .upload_cover(UploadBookCover {
+                book_id,
+                image_type: CoverImageType::Jpeg,
+                data: png.clone(),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        repository
+            .upload_cover(UploadBookCover {
+                book_id,
+                image_type: CoverImageType::Png,
+                data: png.clone(),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.cover, Some(CoverImageType::Png));
+
+        let cover = repository
+            .find_cover(book_id, CoverSize::Original)
+            .await?
+            .unwrap();
+        assert_eq!(cover.image_type, CoverImageType::Png);
+        assert_eq!(cover.data, png);
+
+        // サムネイルは縦横比を保って縮小される
+        let thumbnail = repository
+            .find_cover(book_id, CoverSize::Thumbnail)
+            .await?
+            .unwrap();
+        let thumbnail = image::load_from_memory(&thumbnail.data)?;
+        assert_eq!((thumbnail.width(), thumbnail.height()), (200, 150));
+
+        // 登録し直すと新しいキーに保存され、前の書影の画像は削除される
+        let cover_id = || async {
+            sqlx::query_scalar!(
+                "SELECT cover_id FROM books WHERE book_id = $1",
+                book_id as _
+            )
+            .fetch_one(&pool)
+            .await
+        };
+        let old_cover_id = cover_id().await?;
+        let mut jpeg = Vec::new();
+        image::RgbImage::new(300, 300)
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)?;
+        repository
+            .upload_cover(UploadBookCover {
+                book_id,
+                image_type: CoverImageType::Jpeg,
+                data: jpeg.clone(),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        let new_cover_id = cover_id().await?;
+        assert_ne!(new_cover_id, old_cover_id);
+        for size in [CoverSize::Original, CoverSize::Thumbnail] {
+            let key = cover_key(book_id, old_cover_id, size);
+            assert!(blob_storage.get(&key).await?.is_none());
+        }
+        let cover = repository
+            .find_cover(book_id, CoverSize::Original)
+            .await?
+            .unwrap();
+        assert_eq!(cover.image_type, CoverImageType::Jpeg);
+        assert_eq!(cover.data, jpeg);
+
+        // 書籍を削除済みにしても書影の画像は残り、完全に削除すると画像も削除される
+        repository
+            .delete(DeleteBook {
+                book_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+                expected_version: None,
+            })
+            .await?;
+        assert!(
+            blob_storage
+                .get(&cover_key(book_id, new_cover_id, CoverSize::Original))
+                .await?
+                .is_some()
+        );
+        repository.purge(PurgeBook { book_id }).await?;
+        for size in [CoverSize::Original, CoverSize::Thumbnail] {
+            let key = cover_key(book_id, new_cover_id, size);
+            assert!(blob_storage.get(&key).await?.is_none());
+        }
+
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_import(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let row = |line: u64, isbn: &str, owner_email: Option<&str>| -> anyhow::Result<_> {
+            Ok(ImportBookRow {
+                line,
+                book: CreateBook {
+                    title: format!("Book {line}"),
+                    author: "Test Author".into(),
+                    isbn: Isbn::from_str(isbn)?,
+                    description: "".into(),
+                    publisher: None,
+                    published_on: None,
+                    page_count: None,
+                },
+                owner_email: owner_email.map(String::from),
+            })
+        };
+        let event = |dry_run: bool| -> anyhow::Result<_> {
+            Ok(ImportBooks {
+                rows: vec![
+                    row(2, "9780804429573", None)?,
+                    // 登録済みのISBN
+                    row(3, "978-4-7980-6170-2", None)?,
+                    // 前の行と同じISBN
+                    row(4, "080442957X", None)?,
+                    row(5, "9784297124830", Some("unknown@example.com"))?,
+                ],
+                default_owner: Some(owner_id),
+                dry_run,
+            })
+        };
+        let statuses = |results: Vec<BookImportResult>| {
+            results
+                .into_iter()
+                .map(|r| (r.line, std::mem::discriminant(&r.status)))
+                .collect::<Vec<_>>()
+        };
+        let created = std::mem::discriminant(&BookImportStatus::Created(BookId::new()));
+        let duplicate = std::mem::discriminant(&BookImportStatus::Duplicate);
+        let invalid = std::mem::discriminant(&BookImportStatus::Invalid("".into()));
+        let expected = vec![(2, created), (3, duplicate), (4, duplicate), (5, invalid)];
+
+        // ドライランでは結果だけが返り、書籍は登録されない
+        let results = repository.import(event(true)?).await?;
+        assert_eq!(statuses(results), expected);
+        let books = repository
+            .find_by_isbn(Isbn::from_str("9780804429573")?)
+            .await?;
+        assert!(books.is_empty());
+
+        // ドライランでなければ登録される
+        let results = repository.import(event(false)?).await?;
+        assert_eq!(statuses(results), expected);
+        let books = repository
+            .find_by_isbn(Isbn::from_str("9780804429573")?)
+            .await?;
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].owner.id, owner_id);
+        assert_eq!(books[0].total_copies(), 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_stream_catalog(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+
+        // 蔵書を1冊追加し、もう1冊を貸し出しておく
+        repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        checkout_repository
+            .create(CreateCheckout::new(
+                book_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+
+        // 蔵書ごとに1行ずつ取得できる
+        let entries = repository.stream_catalog().try_collect::<Vec<_>>().await?;
+        assert_eq!(entries.len(), 4);
+        let copies = entries
+            .iter()
+            .filter(|e| e.book_id == book_id)
+            .filter_map(|e| e.copy.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(copies.len(), 2);
+        assert_eq!(copies.iter().filter(|c| c.checkout.is_some()).count(), 1);
+        assert!(entries.iter().all(|e| e.owner.id == owner_id));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_tags(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_a = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let book_b = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let add_tags = |book_id: BookId, tags: &[&str]| AddBookTags {
+            book_id,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            requested_user: owner_id,
+            requested_by_admin: false,
+        };
+
+        repository
+            .add_tags(add_tags(book_a, &["rust", "async"]))
+            .await?;
+        repository.add_tags(add_tags(book_b, &["rust"])).await?;
+        // 付いているタグを再度付けてもエラーにならない
+        repository.add_tags(add_tags(book_b, &["rust"])).await?;
+
+        let book = repository.find_by_id(book_a).await?.unwrap();
+        assert_eq!(book.tags, vec!["async", "rust"]);
+
+        let tags = repository.find_all_tags().await?;
+        assert_eq!(
+            tags,
+            vec![
+                TagCount {
+                    name: "async".into(),
+                    book_count: 1
+                },
+                TagCount {
+                    name: "rust".into(),
+                    book_count: 2
+                },
+            ]
+        );
+
+        // 指定したすべてのタグが付いた書籍に絞り込まれる
+        let find = |tags: &[&str]| {
+            let options = BookListOptions {
+                limit: 10,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            };
+            repository.find_all(options)
+        };
+        assert_eq!(find(&["rust"]).await?.total, 2);
+        let books = find(&["rust", "async"]).await?;
+        assert_eq!(books.items.len(), 1);
+        assert_eq!(books.items[0].id, book_a);
+        assert_eq!(find(&[]).await?.total, 3);
+
+        // 所有者以外はタグを付けられない
+        let res = repository
+            .add_tags(AddBookTags {
+                requested_user: UserId::new(),
+                ..add_tags(book_a, &["design"])
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        // タグを外す。付いていないタグは外せない
+        let remove_tag = |tag: &str| RemoveBookTag {
+            book_id: book_a,
+            tag: tag.into(),
+            requested_user: owner_id,
+            requested_by_admin: false,
+        };
+        repository.remove_tag(remove_tag("async")).await?;
+        let res = repository.remove_tag(remove_tag("async")).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(find(&["rust", "async"]).await?.total, 0);
+
         Ok(())
     }
 }
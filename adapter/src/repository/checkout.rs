@@ -6,21 +6,35 @@ use derive_new::new;
 use kernel::model::{
     checkout::{
         Checkout,
-        event::{CreateCheckout, UpdateReturned},
+        event::{CreateCheckout, RenewCheckout, UpdateReturned},
     },
-    id::{BookId, CheckoutId, UserId},
+    id::{BookId, CheckoutId, CopyId, UserId},
+    list::{CursorOptions, CursorPaginatedList},
 };
 use kernel::repository::checkout::CheckoutRepository;
 use shared::error::{AppError, AppResult};
 
+use super::hold::{advance_holds, count_lendable_copies};
 use crate::database::{
     ConnectionPool,
-    model::checkout::{CheckoutRow, CheckoutStateRow, ReturnedCheckoutRow},
+    model::{
+        checkout::{
+            CheckoutCandidateRow, CheckoutRenewalStateRow, CheckoutRow, CheckoutStateRow,
+            ReturnedCheckoutRow,
+        },
+        cursor::{CheckoutCursor, decode_cursor, encode_cursor},
+    },
 };
 
 #[derive(new)]
 pub struct CheckoutRepositoryImpl {
     db: ConnectionPool,
+    /// 書籍ごとの指定がない場合の貸出期間（日数）
+    loan_period_days: i32,
+    /// 1回の貸出で延長できる回数の上限
+    max_renewals: i32,
+    /// 予約した書籍を取り置いておく日数
+    hold_pickup_days: i32,
 }
 
 #[async_trait]
@@ -33,60 +47,119 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         self.set_transaction_serializable(&mut tx).await?;
 
         // 事前に以下をチェック
-        // - 指定の蔵書が存在するか
-        // - 指定の蔵書が貸出中でないか
-        {
+        // - 指定の書籍が存在するか
+        // - 蔵書の指定がある場合は、その蔵書が書籍に属していて、貸し出せる状態で貸出中でないか
+        // - 蔵書の指定がない場合は、貸出可能な蔵書があるか
+        // 貸出可能な蔵書が先頭に来るように並べ、1件だけ取得する
+        let copy_id = {
             let res = sqlx::query_as!(
-                CheckoutStateRow,
+                CheckoutCandidateRow,
                 r#"
                     SELECT 
                         b.book_id, 
-                        c.checkout_id AS "checkout_id?: CheckoutId", 
-                        NULL AS "user_id?: UserId"
+                        bc.copy_id AS "copy_id?: CopyId",
+                        bc.status AS "copy_status?",
+                        c.checkout_id AS "checkout_id?: CheckoutId"
                     FROM books AS b
-                    LEFT OUTER JOIN checkouts AS c USING(book_id)
-                    WHERE book_id = $1
+                    LEFT OUTER JOIN book_copies AS bc
+                        ON bc.book_id = b.book_id
+                        AND ($2::uuid IS NULL OR bc.copy_id = $2)
+                    LEFT OUTER JOIN checkouts AS c ON c.copy_id = bc.copy_id
+                    WHERE b.book_id = $1
+                    AND b.deleted_at IS NULL
+                    ORDER BY
+                        c.checkout_id IS NOT NULL OR bc.status IS DISTINCT FROM 'available',
+                        bc.created_at ASC,
+                        bc.copy_id ASC
+                    LIMIT 1
                 "#,
-                event.book_id as _
+                event.book_id as _,
+                event.copy_id as _,
             )
             .fetch_optional(&mut *tx)
             .await
             .map_err(AppError::SpecificOperationError)?;
 
             match res {
-                // 蔵書が存在しない場合
+                // 書籍が存在しない場合
                 None => {
                     return Err(AppError::EntityNotFound(format!(
                         "書籍（{}）が見つかりませんでした",
                         event.book_id
                     )));
                 }
-                // 蔵書は存在するが貸出中の場合
-                Some(CheckoutStateRow {
-                    checkout_id: Some(_),
-                    ..
-                }) => {
+                // 指定の蔵書が書籍に属していない場合
+                Some(CheckoutCandidateRow { copy_id: None, .. }) if event.copy_id.is_some() => {
+                    return Err(AppError::EntityNotFound(format!(
+                        "書籍（{}）に蔵書が見つかりませんでした",
+                        event.book_id
+                    )));
+                }
+                // 指定の蔵書が紛失・修理中などで貸し出せない場合
+                Some(
+                    row @ CheckoutCandidateRow {
+                        copy_id: Some(copy_id),
+                        ..
+                    },
+                ) if event.copy_id.is_some() && !row.is_lendable() => {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "蔵書（{}）は{}のため貸し出せません",
+                        copy_id,
+                        row.copy_status.as_deref().unwrap_or_default()
+                    )));
+                }
+                // 貸し出せる状態で貸出中でない蔵書が見つかった場合
+                Some(
+                    row @ CheckoutCandidateRow {
+                        copy_id: Some(copy_id),
+                        checkout_id: None,
+                        ..
+                    },
+                ) if row.is_lendable() => copy_id,
+                // 書籍に蔵書が1冊もない、または貸出可能な蔵書がない場合
+                Some(_) => {
                     return Err(AppError::UnprocessableEntity(format!(
-                        "書籍（{}）に対する貸出が既に存在します",
+                        "書籍（{}）に貸出可能な蔵書がありません",
                         event.book_id
                     )));
                 }
-                // それ以外は処理を続行
-                _ => {}
             }
+        };
+
+        // 他のユーザーに取り置き中の蔵書は貸し出せない
+        if count_lendable_copies(
+            &mut tx,
+            event.book_id,
+            event.checked_out_by,
+            event.checked_out_at,
+        )
+        .await?
+            <= 0
+        {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）の蔵書は他のユーザーのために取り置かれています",
+                event.book_id
+            )));
         }
 
         // 貸出情報を登録
+        // 返却期限は、書籍の貸出期間（未設定の場合は既定の貸出期間）を貸出日時に足して決める
         let checkout_id = CheckoutId::new();
         let res = sqlx::query!(
             r#"
-            INSERT INTO checkouts (checkout_id, book_id, user_id, checked_out_at)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO checkouts (checkout_id, book_id, copy_id, user_id, checked_out_at, due_at)
+            SELECT
+                $1, b.book_id, $3, $4, $5,
+                $5::timestamptz + make_interval(days => COALESCE(b.loan_period_days, $6))
+            FROM books AS b
+            WHERE b.book_id = $2
             "#,
             checkout_id as _,
             event.book_id as _,
+            copy_id as _,
             event.checked_out_by as _,
             event.checked_out_at,
+            self.loan_period_days,
         )
         .execute(&mut *tx)
         .await
@@ -98,6 +171,18 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
             ));
         }
 
+        // 借りたユーザーの予約は、貸出によって済んだものとして削除する
+        sqlx::query!(
+            r#"
+            DELETE FROM holds WHERE book_id = $1 AND user_id = $2
+            "#,
+            event.book_id as _,
+            event.checked_out_by as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
         tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
@@ -111,40 +196,40 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         self.set_transaction_serializable(&mut tx).await?;
 
         // 事前に以下をチェック
-        // - 指定の蔵書が存在するか
-        // - 指定の貸出が存在するか、かつ借りたユーザーが指定のユーザーと同じか
+        // - 指定の書籍が存在するか
+        // - 指定の貸出が書籍の蔵書に対して存在するか、かつ借りたユーザーが指定のユーザーと同じか
         {
             let res = sqlx::query_as!(
                 CheckoutStateRow,
                 r#"
                     SELECT 
                         b.book_id, 
+                        c.copy_id AS "copy_id?: CopyId",
                         c.checkout_id AS "checkout_id?: CheckoutId", 
                         c.user_id AS "user_id?: UserId"
                     FROM books AS b
-                    LEFT OUTER JOIN checkouts AS c USING(book_id)
-                    WHERE book_id = $1
+                    LEFT OUTER JOIN checkouts AS c
+                        ON c.book_id = b.book_id
+                        AND c.checkout_id = $2
+                    WHERE b.book_id = $1
                 "#,
-                event.book_id as _
+                event.book_id as _,
+                event.checkout_id as _,
             )
             .fetch_optional(&mut *tx)
             .await
             .map_err(AppError::SpecificOperationError)?;
 
             match res {
-                // 蔵書が存在しない場合
+                // 書籍が存在しない場合
                 None => {
                     return Err(AppError::EntityNotFound(format!(
                         "書籍（{}）が見つかりませんでした",
                         event.book_id
                     )));
                 }
-                // 指定の蔵書が貸出中であり、貸出IDまたは借りたユーザーが異なる場合
-                Some(CheckoutStateRow {
-                    checkout_id: Some(c),
-                    user_id: Some(u),
-                    ..
-                }) if (c, u) != (event.checkout_id, event.returned_by) => {
+                // 指定の貸出が存在しない、または借りたユーザーが異なる場合
+                Some(CheckoutStateRow { user_id, .. }) if user_id != Some(event.returned_by) => {
                     return Err(AppError::UnprocessableEntity(format!(
                         "指定の貸出（ID（{}）、ユーザー（{}）、書籍（{}））は返却できません",
                         event.checkout_id, event.returned_by, event.book_id
@@ -159,8 +244,8 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         // returned_atを追加して、returned_checkoutsテーブルにINSERTする
         let res = sqlx::query!(
             r#"
-            INSERT INTO returned_checkouts (checkout_id, book_id, user_id, checked_out_at, returned_at)
-            SELECT checkout_id, book_id, user_id, checked_out_at, $1
+            INSERT INTO returned_checkouts (checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, returned_at)
+            SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, $1
             FROM checkouts
             WHERE checkout_id = $2
             "#,
@@ -195,13 +280,183 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
             ));
         }
 
+        // 返却された蔵書を、予約の先頭のユーザーに取り置く
+        advance_holds(
+            &mut tx,
+            event.book_id,
+            event.returned_at,
+            self.hold_pickup_days,
+        )
+        .await?;
+
         tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
 
+    /// 貸出を延長する
+    async fn renew(&self, event: RenewCheckout) -> AppResult<Checkout> {
+        let mut tx = self.db.begin().await?;
+
+        // トランザクション分離レベルをSERIALIZABLEに設定
+        self.set_transaction_serializable(&mut tx).await?;
+
+        // 事前に以下をチェック
+        // - 指定の書籍が存在するか
+        // - 指定の貸出が書籍に対して存在するか、かつ借りたユーザーが指定のユーザーと同じか
+        // - 延長した回数が上限に達していないか
+        // - 他のユーザーが書籍を予約していないか
+        {
+            let res = sqlx::query_as!(
+                CheckoutRenewalStateRow,
+                r#"
+                    SELECT
+                        b.book_id,
+                        c.checkout_id AS "checkout_id?: CheckoutId",
+                        c.user_id AS "user_id?: UserId",
+                        (
+                            SELECT COUNT(*) FROM checkout_renewals AS cr
+                            WHERE cr.checkout_id = c.checkout_id
+                        ) AS "renewal_count!",
+                        EXISTS (
+                            SELECT 1 FROM holds AS h
+                            WHERE h.book_id = b.book_id AND h.user_id <> $3
+                        ) AS "held_by_others!"
+                    FROM books AS b
+                    LEFT OUTER JOIN checkouts AS c
+                        ON c.book_id = b.book_id
+                        AND c.checkout_id = $2
+                    WHERE b.book_id = $1
+                "#,
+                event.book_id as _,
+                event.checkout_id as _,
+                event.renewed_by as _,
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            match res {
+                // 書籍が存在しない場合
+                None => {
+                    return Err(AppError::EntityNotFound(format!(
+                        "書籍（{}）が見つかりませんでした",
+                        event.book_id
+                    )));
+                }
+                // 指定の貸出が存在しない、または借りたユーザーが異なる場合
+                Some(CheckoutRenewalStateRow { user_id, .. })
+                    if user_id != Some(event.renewed_by) =>
+                {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "指定の貸出（ID（{}）、ユーザー（{}）、書籍（{}））は延長できません",
+                        event.checkout_id, event.renewed_by, event.book_id
+                    )));
+                }
+                // 延長した回数が上限に達している場合
+                Some(CheckoutRenewalStateRow { renewal_count, .. })
+                    if renewal_count >= i64::from(self.max_renewals) =>
+                {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "貸出（{}）は延長できる回数の上限（{}回）に達しています",
+                        event.checkout_id, self.max_renewals
+                    )));
+                }
+                // 他のユーザーが書籍を予約している場合
+                Some(CheckoutRenewalStateRow {
+                    held_by_others: true,
+                    ..
+                }) => {
+                    return Err(AppError::UnprocessableEntity(format!(
+                        "書籍（{}）は他のユーザーが予約しているため、貸出（{}）を延長できません",
+                        event.book_id, event.checkout_id
+                    )));
+                }
+                // それ以外は処理を続行
+                _ => {}
+            }
+        }
+
+        // 延長を記録する
+        // 延長後の返却期限は、現在の返却期限と延長日時の遅い方に、書籍の貸出期間（未設定の場合は既定の貸出期間）を足して決める
+        let due_at = sqlx::query_scalar!(
+            r#"
+            INSERT INTO checkout_renewals (checkout_id, previous_due_at, due_at, renewed_at)
+            SELECT
+                c.checkout_id,
+                c.due_at,
+                GREATEST(c.due_at, $2::timestamptz)
+                    + make_interval(days => COALESCE(b.loan_period_days, $3)),
+                $2
+            FROM checkouts AS c
+            INNER JOIN books AS b USING(book_id)
+            WHERE c.checkout_id = $1
+            RETURNING due_at
+            "#,
+            event.checkout_id as _,
+            event.renewed_at,
+            self.loan_period_days,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // 貸出の返却期限を延長後のものに更新する
+        let res = sqlx::query!(
+            r#"
+            UPDATE checkouts SET due_at = $2 WHERE checkout_id = $1
+            "#,
+            event.checkout_id as _,
+            due_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::NoRowsAffectedError(
+                "No checkout record has been updated".into(),
+            ));
+        }
+
+        let checkout = sqlx::query_as!(
+            CheckoutRow,
+            r#"
+                SELECT
+                    c.checkout_id,
+                    c.book_id,
+                    c.user_id,
+                    c.checked_out_at,
+                    c.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
+                    b.title,
+                    b.author,
+                    b.isbn_display AS isbn,
+                    c.copy_id,
+                    bc.barcode
+                FROM checkouts AS c
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc USING(copy_id)
+                WHERE c.checkout_id = $1
+            "#,
+            event.checkout_id as _,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map(Checkout::from)
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(checkout)
+    }
+
     /// 全ての未返却の貸出情報を取得する
-    async fn find_unreturned_all(&self) -> AppResult<Vec<Checkout>> {
+    /// overdue_onlyがtrueの場合は、返却期限を過ぎたもののみとする
+    async fn find_unreturned_all(&self, overdue_only: bool) -> AppResult<Vec<Checkout>> {
         sqlx::query_as!(
             CheckoutRow,
             r#"
@@ -210,13 +465,23 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                     c.book_id,
                     c.user_id,
                     c.checked_out_at,
+                    c.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
                     b.title,
                     b.author,
-                    b.isbn
+                    b.isbn_display AS isbn,
+                    c.copy_id,
+                    bc.barcode
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
-                ORDER BY c.checked_out_at ASC
-            "#
+                INNER JOIN book_copies AS bc USING(copy_id)
+                WHERE NOT $1 OR c.due_at < CURRENT_TIMESTAMP
+                ORDER BY c.checked_out_at ASC, c.checkout_id ASC
+            "#,
+            overdue_only,
         )
         .fetch_all(self.db.inner_ref())
         .await
@@ -224,6 +489,79 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         .map_err(AppError::SpecificOperationError)
     }
 
+    /// カーソルの位置から未返却の貸出情報を貸出日時の古い順に取得する
+    /// overdue_onlyがtrueの場合は、返却期限を過ぎたもののみとする
+    async fn find_unreturned_all_by_cursor(
+        &self,
+        options: CursorOptions,
+        overdue_only: bool,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        let CursorOptions { limit, cursor } = options;
+        let cursor = cursor
+            .as_deref()
+            .map(decode_cursor::<CheckoutCursor>)
+            .transpose()?;
+        let (checked_out_at, cursor_checkout_id) = match cursor {
+            Some(c) => (Some(c.checked_out_at), Some(c.checkout_id)),
+            None => (None, None),
+        };
+
+        // 次のページの有無を判定するため、limitより1件多く取得する
+        let mut rows = sqlx::query_as!(
+            CheckoutRow,
+            r#"
+                SELECT
+                    c.checkout_id,
+                    c.book_id,
+                    c.user_id,
+                    c.checked_out_at,
+                    c.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
+                    b.title,
+                    b.author,
+                    b.isbn_display AS isbn,
+                    c.copy_id,
+                    bc.barcode
+                FROM checkouts AS c
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc USING(copy_id)
+                WHERE ($3::uuid IS NULL
+                    OR (c.checked_out_at, c.checkout_id) > ($2::timestamptz, $3::uuid))
+                AND (NOT $4 OR c.due_at < CURRENT_TIMESTAMP)
+                ORDER BY c.checked_out_at ASC, c.checkout_id ASC
+                LIMIT $1
+            "#,
+            limit + 1,
+            checked_out_at,
+            cursor_checkout_id as _,
+            overdue_only,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let next_cursor = if rows.len() as i64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().map(|row| {
+                encode_cursor(&CheckoutCursor {
+                    checked_out_at: row.checked_out_at,
+                    checkout_id: row.checkout_id,
+                })
+            })
+        } else {
+            None
+        };
+
+        Ok(CursorPaginatedList {
+            items: rows.into_iter().map(Checkout::from).collect(),
+            limit,
+            next_cursor,
+        })
+    }
+
     /// ユーザーIDに紐づく未返却の貸出情報を取得する
     async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>> {
         sqlx::query_as!(
@@ -234,11 +572,19 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                     c.book_id,
                     c.user_id,
                     c.checked_out_at,
+                    c.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
                     b.title,
                     b.author,
-                    b.isbn
+                    b.isbn_display AS isbn,
+                    c.copy_id,
+                    bc.barcode
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc USING(copy_id)
                 WHERE c.user_id = $1
                 ORDER BY c.checked_out_at ASC
             "#,
@@ -253,10 +599,10 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
     /// 蔵書の貸出履歴（返却済みも含む）を取得する
     async fn find_history_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>> {
         // 未返却の貸出情報を取得
-        let checkout: Option<Checkout> = self.find_unreturned_by_book_id(book_id).await?;
+        let checkouts: Vec<Checkout> = self.find_unreturned_by_book_id(book_id).await?;
 
         // 返却済みの貸出情報を取得
-        let mut checkout_histories: Vec<Checkout> = sqlx::query_as!(
+        let checkout_histories: Vec<Checkout> = sqlx::query_as!(
             ReturnedCheckoutRow,
             r#"
                 SELECT
@@ -264,12 +610,22 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                     rc.book_id,
                     rc.user_id,
                     rc.checked_out_at,
+                    rc.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = rc.checkout_id
+                    ) AS "renewal_count!",
                     rc.returned_at,
+                    rc.outcome,
                     b.title,
                     b.author,
-                    b.isbn
+                    b.isbn_display AS isbn,
+                    rc.copy_id,
+                    -- 取り除かれた蔵書のバーコードは残らない
+                    COALESCE(bc.barcode, '') AS "barcode!"
                 FROM returned_checkouts AS rc
                 INNER JOIN books AS b USING(book_id)
+                LEFT OUTER JOIN book_copies AS bc USING(copy_id)
                 WHERE rc.book_id = $1
                 ORDER BY rc.checked_out_at ASC
             "#,
@@ -279,15 +635,11 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         .await
         .map_err(AppError::SpecificOperationError)?
         .into_iter()
-        .map(Checkout::from)
-        .collect();
+        .map(Checkout::try_from)
+        .collect::<AppResult<_>>()?;
 
         // 未返却の貸出情報があれば、履歴の先頭に追加
-        if let Some(c) = checkout {
-            checkout_histories.insert(0, c);
-        }
-
-        Ok(checkout_histories)
+        Ok(checkouts.into_iter().chain(checkout_histories).collect())
     }
 }
 
@@ -304,8 +656,8 @@ impl CheckoutRepositoryImpl {
         Ok(())
     }
 
-    /// 蔵書IDに紐づく未返却の貸出情報を取得する
-    async fn find_unreturned_by_book_id(&self, book_id: BookId) -> AppResult<Option<Checkout>> {
+    /// 書籍IDに紐づく未返却の貸出情報を貸出日時の新しい順に取得する
+    async fn find_unreturned_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>> {
         let res = sqlx::query_as!(
             CheckoutRow,
             r#"
@@ -314,20 +666,395 @@ impl CheckoutRepositoryImpl {
                     c.book_id,
                     c.user_id,
                     c.checked_out_at,
+                    c.due_at,
+                    (
+                        SELECT COUNT(*) FROM checkout_renewals AS cr
+                        WHERE cr.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
                     b.title,
                     b.author,
-                    b.isbn
+                    b.isbn_display AS isbn,
+                    c.copy_id,
+                    bc.barcode
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc USING(copy_id)
                 WHERE c.book_id = $1
+                ORDER BY c.checked_out_at DESC
             "#,
             book_id as _
         )
-        .fetch_optional(self.db.inner_ref())
+        .fetch_all(self.db.inner_ref())
         .await
-        .map(|row| row.map(Checkout::from))
+        .map(|rows| rows.into_iter().map(Checkout::from).collect())
         .map_err(AppError::SpecificOperationError)?;
 
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::test_helper::{book_repository, checkout_repository, create_test_user};
+    use kernel::{
+        model::{
+            book::{
+                BookAvailability, BookListOptions, CopyStatus, CopyStatusAction,
+                event::{ChangeCopyStatus, CreateBookCopy, DeleteBookCopy, SetBookLoanPeriod},
+            },
+            checkout::CheckoutOutcome,
+        },
+        repository::book::BookRepository,
+    };
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_copies(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+
+        // バーコードを指定した蔵書と、連番で採番される蔵書を追加して3冊にする
+        repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: Some("T00000010".into()),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.total_copies(), 3);
+        assert_eq!(book.available_copies(), 3);
+        assert_eq!(book.copies[1].barcode, "T00000010");
+        assert!(book.copies[2].barcode.starts_with('C'));
+
+        // 使用済みのバーコードは指定できない
+        let res = repository
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: Some("T00000001".into()),
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 指定した蔵書を貸し出すと、その蔵書だけが貸出中になる
+        let copy_id = book.copies[1].id;
+        checkout_repository
+            .create(CreateCheckout::new(
+                book_id,
+                Some(copy_id),
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+        let res = checkout_repository
+            .create(CreateCheckout::new(
+                book_id,
+                Some(copy_id),
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.available_copies(), 2);
+        assert!(book.copies[1].checkout.is_some());
+
+        // 貸出可能な蔵書が残っている間は、貸出可能として絞り込まれる
+        let options = BookListOptions {
+            limit: 10,
+            availability: Some(BookAvailability::Available),
+            ..Default::default()
+        };
+        let books = repository.find_all(options).await?;
+        assert!(books.items.iter().any(|b| b.id == book_id));
+
+        // 貸出中の蔵書は取り除けない
+        let res = repository
+            .delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        repository
+            .delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id: book.copies[2].id,
+                requested_user: owner_id,
+                requested_by_admin: false,
+            })
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.total_copies(), 2);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_checkout_due_dates(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool);
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other_book_id = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+        let checked_out_at = chrono::Utc::now() - chrono::Duration::days(20);
+
+        // 所有者以外は貸出期間を変えられず、1日未満は設定できない
+        let set_loan_period =
+            |loan_period_days: Option<i32>, requested_user: UserId| SetBookLoanPeriod {
+                book_id,
+                loan_period_days,
+                requested_user,
+                requested_by_admin: false,
+            };
+        let res = repository
+            .set_loan_period(set_loan_period(Some(30), UserId::new()))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        let res = repository
+            .set_loan_period(set_loan_period(Some(0), owner_id))
+            .await;
+        assert!(res.is_err());
+        repository
+            .set_loan_period(set_loan_period(Some(30), owner_id))
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.loan_period_days, Some(30));
+
+        // 返却期限は、書籍の貸出期間か全体の既定値から決まる
+        for id in [book_id, other_book_id] {
+            checkout_repository
+                .create(CreateCheckout::new(id, None, owner_id, checked_out_at))
+                .await?;
+        }
+        let checkouts = checkout_repository.find_unreturned_all(false).await?;
+        assert_eq!(checkouts.len(), 2);
+        let due_in = |id: BookId| {
+            checkouts
+                .iter()
+                .find(|c| c.book.id == id)
+                .map(|c| (c.due_at - c.checked_out_at).num_days())
+        };
+        assert_eq!(due_in(book_id), Some(30));
+        assert_eq!(due_in(other_book_id), Some(14));
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        let checkout = book.copies[0].checkout.as_ref().unwrap();
+        assert_eq!((checkout.due_at - checkout.checked_out_at).num_days(), 30);
+
+        // 返却期限を過ぎた貸出のみに絞り込める
+        let overdue = checkout_repository.find_unreturned_all(true).await?;
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].book.id, other_book_id);
+        let overdue = checkout_repository
+            .find_unreturned_all_by_cursor(
+                CursorOptions {
+                    limit: 10,
+                    cursor: None,
+                },
+                true,
+            )
+            .await?;
+        assert_eq!(overdue.items.len(), 1);
+
+        // 貸出期間を未設定に戻すと、既定値が使われる
+        repository
+            .set_loan_period(set_loan_period(None, owner_id))
+            .await?;
+        assert!(
+            repository
+                .find_by_id(book_id)
+                .await?
+                .unwrap()
+                .loan_period_days
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_checkout_renewals(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let checkout_repository = checkout_repository(pool.clone());
+        let repository = book_repository(pool.clone());
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other = create_test_user(&pool, "Other").await?;
+
+        // 返却期限を過ぎた貸出を作る
+        let checked_out_at = chrono::Utc::now() - chrono::Duration::days(20);
+        checkout_repository
+            .create(CreateCheckout::new(book_id, None, owner_id, checked_out_at))
+            .await?;
+        let checkout = checkout_repository
+            .find_unreturned_all(false)
+            .await?
+            .pop()
+            .unwrap();
+        assert_eq!(checkout.renewal_count, 0);
+        let renew = |renewed_by: UserId| {
+            RenewCheckout::new(checkout.id, book_id, renewed_by, chrono::Utc::now())
+        };
+
+        // 借りたユーザー以外は延長できない
+        let res = checkout_repository.renew(renew(other.id)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 返却期限を過ぎている場合は、延長した日時から貸出期間の分だけ延びる
+        let renewed = checkout_repository.renew(renew(owner_id)).await?;
+        assert_eq!(renewed.renewal_count, 1);
+        assert_eq!((renewed.due_at - chrono::Utc::now()).num_days(), 13);
+
+        // 返却期限前であれば、返却期限から延びる
+        let renewed_again = checkout_repository.renew(renew(owner_id)).await?;
+        assert_eq!(renewed_again.renewal_count, 2);
+        assert_eq!((renewed_again.due_at - renewed.due_at).num_days(), 14);
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        let copy_checkout = book.copies[0].checkout.as_ref().unwrap();
+        assert_eq!(copy_checkout.renewal_count, 2);
+        assert_eq!(copy_checkout.due_at, renewed_again.due_at);
+
+        // 上限の回数を超えては延長できない
+        let res = checkout_repository.renew(renew(owner_id)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 返却後も、延長した回数は履歴に残る
+        checkout_repository
+            .update_returned(UpdateReturned::new(
+                checkout.id,
+                book_id,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await?;
+        let history = checkout_repository.find_history_by_book_id(book_id).await?;
+        assert_eq!(history[0].renewal_count, 2);
+        let res = checkout_repository.renew(renew(owner_id)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_copy_status(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let checkout_repository = checkout_repository(pool.clone());
+        let repository = book_repository(pool.clone());
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let borrower = create_test_user(&pool, "Borrower").await?;
+        let stranger = create_test_user(&pool, "Stranger").await?;
+        let copy_id = repository.find_by_id(book_id).await?.unwrap().copies[0].id;
+        let change = |action: CopyStatusAction, requested_user: UserId| ChangeCopyStatus {
+            book_id,
+            copy_id,
+            action,
+            note: format!("{} by {}", action.as_ref(), requested_user),
+            requested_user,
+            requested_by_admin: false,
+        };
+        let checkout = |user_id: UserId| {
+            CreateCheckout::new(book_id, Some(copy_id), user_id, chrono::Utc::now())
+        };
+
+        checkout_repository.create(checkout(borrower.id)).await?;
+
+        // 借りているユーザーは紛失・破損の報告のみを行え、無関係のユーザーは何も行えない
+        let res = repository
+            .change_copy_status(change(CopyStatusAction::ReportLost, stranger.id))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        let res = repository
+            .change_copy_status(change(CopyStatusAction::SendToRepair, borrower.id))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        // 貸出中の蔵書は除籍できない
+        let res = repository
+            .change_copy_status(change(CopyStatusAction::Withdraw, owner_id))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 借りているユーザーが紛失を報告すると、貸出は紛失として返却済みになる
+        repository
+            .change_copy_status(change(CopyStatusAction::ReportLost, borrower.id))
+            .await?;
+        let book = repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.copies[0].status, CopyStatus::Lost);
+        assert!(book.copies[0].checkout.is_none());
+        assert_eq!(book.available_copies(), 0);
+        assert!(
+            checkout_repository
+                .find_unreturned_by_user_id(borrower.id)
+                .await?
+                .is_empty()
+        );
+        let history = checkout_repository.find_history_by_book_id(book_id).await?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].outcome, Some(CheckoutOutcome::Lost));
+
+        // 紛失した蔵書は貸し出せず、貸出可能な書籍として絞り込まれない
+        let res = checkout_repository.create(checkout(owner_id)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let res = checkout_repository
+            .create(CreateCheckout::new(
+                book_id,
+                None,
+                owner_id,
+                chrono::Utc::now(),
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let books = repository
+            .find_all(BookListOptions {
+                limit: 10,
+                availability: Some(BookAvailability::Available),
+                ..Default::default()
+            })
+            .await?;
+        assert!(books.items.iter().all(|b| b.id != book_id));
+
+        // 見つかった蔵書は再び貸し出せる。現在の状態に合わない操作は行えない
+        repository
+            .change_copy_status(change(CopyStatusAction::MarkFound, owner_id))
+            .await?;
+        let res = repository
+            .change_copy_status(change(CopyStatusAction::MarkFound, owner_id))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        checkout_repository.create(checkout(owner_id)).await?;
+
+        let history = repository
+            .find_copy_status_history(book_id, copy_id)
+            .await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].action, CopyStatusAction::ReportLost);
+        assert_eq!(
+            (history[0].from, history[0].to),
+            (CopyStatus::Available, CopyStatus::Lost)
+        );
+        assert_eq!(
+            history[0].changed_by.as_ref().map(|u| u.id),
+            Some(borrower.id)
+        );
+        assert!(history[0].note.starts_with("report-lost"));
+        assert_eq!(history[1].to, CopyStatus::Available);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+//! お気に入りのDB操作のための具象実装をするモジュール
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::{
+    id::{BookId, UserId},
+    user::event::{AddFavoriteBook, RemoveFavoriteBook},
+};
+use kernel::repository::favorite::FavoriteRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::ConnectionPool;
+
+#[derive(new)]
+pub struct FavoriteRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl FavoriteRepository for FavoriteRepositoryImpl {
+    /// 削除済みでない書籍をお気に入りに登録する。登録済みの場合は何もしない
+    async fn add(&self, event: AddFavoriteBook) -> AppResult<()> {
+        let book_exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+            ) AS "exists!"
+            "#,
+            event.book_id as _,
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !book_exists {
+            return Err(AppError::EntityNotFound("specified book not found".into()));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO favorites (user_id, book_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            event.user_id as _,
+            event.book_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(())
+    }
+
+    /// 書籍をお気に入りから外す。登録されていない場合は何もしない
+    async fn remove(&self, event: RemoveFavoriteBook) -> AppResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM favorites WHERE user_id = $1 AND book_id = $2
+            "#,
+            event.user_id as _,
+            event.book_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(())
+    }
+
+    /// 指定した書籍のうち、ユーザーがお気に入りに登録している書籍のIDを取得する
+    async fn find_favorited(
+        &self,
+        user_id: UserId,
+        book_ids: Vec<BookId>,
+    ) -> AppResult<HashSet<BookId>> {
+        if book_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let rows = sqlx::query_scalar!(
+            r#"
+            SELECT book_id AS "book_id: BookId"
+            FROM favorites
+            WHERE user_id = $1
+            AND book_id = ANY($2)
+            "#,
+            user_id as _,
+            &book_ids as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::test_helper::book_repository;
+    use kernel::{model::book::BookListOptions, repository::book::BookRepository};
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_favorites(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = FavoriteRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repository = book_repository(pool.clone());
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other_id = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+
+        // 2回登録してもエラーにならない
+        for _ in 0..2 {
+            repository.add(AddFavoriteBook { user_id, book_id }).await?;
+        }
+        let res = repository
+            .add(AddFavoriteBook {
+                user_id,
+                book_id: BookId::new(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        let favorited = repository
+            .find_favorited(user_id, vec![book_id, other_id])
+            .await?;
+        assert_eq!(favorited, HashSet::from([book_id]));
+
+        // お気に入りの書籍に絞り込める
+        let books = book_repository
+            .find_all(BookListOptions {
+                limit: 10,
+                favorited_by: Some(user_id),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(books.total, 1);
+        assert_eq!(books.items[0].id, book_id);
+
+        repository
+            .remove(RemoveFavoriteBook { user_id, book_id })
+            .await?;
+        let favorited = repository.find_favorited(user_id, vec![book_id]).await?;
+        assert!(favorited.is_empty());
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ INSERT INTO
     title,
     author,
     isbn,
+    isbn_display,
     description,
     user_id,
     created_at,
@@ -14,6 +15,7 @@ VALUES
     '9890736e-a4e4-461a-a77d-eac3517ef11b',
     '実践Rustプログラミング入門',
     '初田直也他',
+    '9784798061702',
     '978-4798061702',
     'C/C++の代わりとなるべき最新言語その独特な仕様をわかりやすく解説。',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
@@ -24,6 +26,7 @@ VALUES
     'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
     'ゼロから学ぶRust　システムプログラミングの基礎から線形型システムまで',
     '高野祐輝',
+    '9784065301951',
     '978-4065301951',
     '通読して学習する入門書！　単なる文法解説にはとどまらない。実践的なソフトウェア実装と、Rustの安全性を支える理論の学習を通して、ゼロから徹底的にマスターできる！',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
@@ -34,9 +37,28 @@ VALUES
     '17afb850-c786-49c5-a303-a3a443a2212c',
     'RustによるWebアプリケーション開発　設計からリリース・運用まで',
     '豊田優貴他',
+    '9784065369579',
     '978-4065369579',
     '「蔵書管理アプリケーション」の実装を通じて、設計、開発、保守、運用までハンズオンで学ぶ！　今こそ現場にRustを！',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
     now(),
     now()
-  ) ON CONFLICT DO NOTHING;
\ No newline at end of file
+  ) ON CONFLICT DO NOTHING;
+INSERT INTO
+  book_copies (copy_id, book_id, barcode)
+VALUES
+  (
+    '9890736e-a4e4-461a-a77d-eac3517ef11b',
+    '9890736e-a4e4-461a-a77d-eac3517ef11b',
+    'T00000001'
+  ),
+  (
+    'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
+    'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
+    'T00000002'
+  ),
+  (
+    '17afb850-c786-49c5-a303-a3a443a2212c',
+    '17afb850-c786-49c5-a303-a3a443a2212c',
+    'T00000003'
+  ) ON CONFLICT DO NOTHING;
@@ -0,0 +1,617 @@
+//! 書籍の予約のDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use derive_new::new;
+
+use kernel::model::{
+    hold::{
+        Hold,
+        event::{CreateHold, DeleteHold},
+    },
+    id::{BookId, HoldId, UserId},
+    notification::NotificationKind,
+};
+use kernel::repository::hold::HoldRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::{
+    ConnectionPool,
+    model::hold::{HoldRow, HoldStateRow},
+};
+
+#[derive(new)]
+pub struct HoldRepositoryImpl {
+    db: ConnectionPool,
+    /// 予約した書籍を取り置いておく日数
+    pickup_days: i32,
+}
+
+#[async_trait]
+impl HoldRepository for HoldRepositoryImpl {
+    /// 貸出中の書籍を予約する
+    /// 借りている書籍や予約済みの書籍、今すぐ借りられる書籍は予約できない
+    async fn create(&self, event: CreateHold) -> AppResult<Hold> {
+        let mut tx = self.db.begin().await?;
+
+        // 貸出と同じく、トランザクション分離レベルをSERIALIZABLEに設定
+        self.set_transaction_serializable(&mut tx).await?;
+
+        let state = sqlx::query_as!(
+            HoldStateRow,
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT 1 FROM checkouts AS c
+                    WHERE c.book_id = b.book_id AND c.user_id = $2
+                ) AS "checked_out!",
+                EXISTS (
+                    SELECT 1 FROM holds AS h
+                    WHERE h.book_id = b.book_id AND h.user_id = $2
+                ) AS "held!"
+            FROM books AS b
+            WHERE b.book_id = $1
+            AND b.deleted_at IS NULL
+            "#,
+            event.book_id as _,
+            event.user_id as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| {
+            AppError::EntityNotFound(format!("書籍（{}）が見つかりませんでした", event.book_id))
+        })?;
+
+        if state.checked_out {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）は借りているため予約できません",
+                event.book_id
+            )));
+        }
+        if state.held {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）はすでに予約しています",
+                event.book_id
+            )));
+        }
+        if count_lendable_copies(&mut tx, event.book_id, event.user_id, event.created_at).await? > 0
+        {
+            return Err(AppError::UnprocessableEntity(format!(
+                "書籍（{}）には貸出可能な蔵書があるため予約できません",
+                event.book_id
+            )));
+        }
+
+        let hold_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO holds (book_id, user_id, created_at)
+            VALUES ($1, $2, $3)
+            RETURNING hold_id AS "hold_id: HoldId"
+            "#,
+            event.book_id as _,
+            event.user_id as _,
+            event.created_at,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        self.find_holds(event.user_id, Some(hold_id))
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::EntityNotFound("created hold not found".into()))
+    }
+
+    /// ユーザーの予約を、予約した順に取得する
+    async fn find_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Hold>> {
+        self.find_holds(user_id, None).await
+    }
+
+    /// 予約を取り消す
+    async fn delete(&self, event: DeleteHold) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        self.set_transaction_serializable(&mut tx).await?;
+
+        let book_id = sqlx::query_scalar!(
+            r#"
+            DELETE FROM holds
+            WHERE hold_id = $1 AND user_id = $2
+            RETURNING book_id AS "book_id: BookId"
+            "#,
+            event.hold_id as _,
+            event.user_id as _,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| {
+            AppError::EntityNotFound(format!("予約（{}）が見つかりませんでした", event.hold_id))
+        })?;
+
+        // 取り置き中の予約だった場合は、空いた蔵書を次の予約者に回す
+        advance_holds(&mut tx, book_id, event.cancelled_at, self.pickup_days).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 受け取りの期限を過ぎた取り置きを取り消し、空いた蔵書を次の予約者に回す
+    /// 定期的に実行されるため、期限切れの取り置きか取り置きにできる予約がある書籍に絞り、
+    /// 書籍ごとに貸出・返却と同じくSERIALIZABLEのトランザクションで進める
+    /// 貸出・返却と競合した書籍は、次回の実行に回す
+    async fn advance(&self, now: DateTime<Utc>) -> AppResult<()> {
+        let book_ids = sqlx::query_scalar!(
+            r#"
+            SELECT h.book_id AS "book_id!: BookId"
+            FROM holds AS h
+            WHERE h.expires_at <= $1
+            UNION
+            SELECT h.book_id
+            FROM holds AS h
+            INNER JOIN books AS b USING(book_id)
+            WHERE h.ready_at IS NULL
+            AND b.deleted_at IS NULL
+            AND (
+                SELECT COUNT(*) FROM book_copies AS bc
+                WHERE bc.book_id = h.book_id
+                AND bc.status = 'available'
+                AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+            ) > (
+                SELECT COUNT(*) FROM holds AS r
+                WHERE r.book_id = h.book_id
+                AND r.ready_at IS NOT NULL
+            )
+            "#,
+            now,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        for book_id in book_ids {
+            match self.advance_book(book_id, now).await {
+                Err(AppError::SpecificOperationError(e) | AppError::TransactionError(e))
+                    if is_serialization_failure(&e) =>
+                {
+                    tracing::warn!(%book_id, "skipped advancing holds due to a serialization failure");
+                }
+                res => res?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl HoldRepositoryImpl {
+    /// 指定の書籍の予約を、SERIALIZABLEのトランザクションで進める
+    async fn advance_book(&self, book_id: BookId, now: DateTime<Utc>) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        self.set_transaction_serializable(&mut tx).await?;
+        advance_holds(&mut tx, book_id, now, self.pickup_days).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// トランザクションの分離レベルをSERIALIZABLEに設定する
+    async fn set_transaction_serializable(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> AppResult<()> {
+        sqlx::query!("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        Ok(())
+    }
+
+    /// ユーザーの予約を順番とともに取得する。予約IDを指定した場合はその予約のみとする
+    async fn find_holds(&self, user_id: UserId, hold_id: Option<HoldId>) -> AppResult<Vec<Hold>> {
+        sqlx::query_as!(
+            HoldRow,
+            r#"
+            SELECT
+                h.hold_id,
+                h.book_id,
+                b.title,
+                b.author,
+                h.position AS "position!",
+                h.created_at,
+                h.ready_at,
+                h.expires_at
+            FROM (
+                SELECT
+                    hold_id,
+                    book_id,
+                    user_id,
+                    created_at,
+                    ready_at,
+                    expires_at,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY book_id ORDER BY created_at ASC, hold_id ASC
+                    ) AS position
+                FROM holds
+            ) AS h
+            INNER JOIN books AS b USING(book_id)
+            WHERE h.user_id = $1
+            AND ($2::uuid IS NULL OR h.hold_id = $2)
+            ORDER BY h.created_at ASC, h.hold_id ASC
+            "#,
+            user_id as _,
+            hold_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map(|rows| rows.into_iter().map(Hold::from).collect())
+        .map_err(AppError::SpecificOperationError)
+    }
+}
+
+/// シリアライズの失敗（SQLSTATE 40001）によるエラーかどうかを返す
+fn is_serialization_failure(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db) if db.code().as_deref() == Some("40001"))
+}
+
+/// 指定のユーザーが今すぐ借りられる蔵書の数を返す
+/// 貸し出せる状態で貸出中でない蔵書から、他のユーザーに取り置き中の予約と、
+/// 指定のユーザーより順番が前の順番待ちの予約の数を除いたものとする
+/// 指定のユーザー自身に取り置き中の予約がある場合は、除かずに返す
+pub(super) async fn count_lendable_copies(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    user_id: UserId,
+    now: DateTime<Utc>,
+) -> AppResult<i64> {
+    sqlx::query_scalar!(
+        r#"
+        WITH free AS (
+            SELECT COUNT(*) AS count FROM book_copies AS bc
+            WHERE bc.book_id = $1
+            AND bc.status = 'available'
+            AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+        ),
+        mine AS (
+            SELECT hold_id, created_at, ready_at, expires_at FROM holds
+            WHERE book_id = $1 AND user_id = $2
+        )
+        SELECT
+            CASE
+                WHEN EXISTS (SELECT 1 FROM mine WHERE mine.expires_at > $3)
+                THEN (SELECT count FROM free)
+                ELSE (SELECT count FROM free) - (
+                    SELECT COUNT(*) FROM holds AS h
+                    WHERE h.book_id = $1
+                    AND h.user_id <> $2
+                    AND (
+                        h.expires_at > $3
+                        OR (
+                            h.ready_at IS NULL
+                            AND NOT EXISTS (
+                                SELECT 1 FROM mine
+                                WHERE mine.ready_at IS NULL
+                                AND (mine.created_at, mine.hold_id) < (h.created_at, h.hold_id)
+                            )
+                        )
+                    )
+                )
+            END AS "count!"
+        "#,
+        book_id as _,
+        user_id as _,
+        now,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)
+}
+
+/// 指定の書籍の予約を進める
+/// - 受け取りの期限を過ぎた取り置きを取り消し、予約者に通知する
+/// - 貸し出せる蔵書の数まで、順番待ちの先頭から取り置きにし、予約者に通知する
+pub(super) async fn advance_holds(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    now: DateTime<Utc>,
+    pickup_days: i32,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+        WITH expired AS (
+            DELETE FROM holds
+            WHERE expires_at <= $2
+            AND book_id = $1
+            RETURNING book_id, user_id
+        )
+        INSERT INTO notifications (user_id, kind, book_id, message)
+        SELECT
+            e.user_id,
+            $3,
+            e.book_id,
+            '予約した「' || b.title || '」の受け取りの期限を過ぎたため、予約を取り消しました'
+        FROM expired AS e
+        INNER JOIN books AS b USING(book_id)
+        "#,
+        book_id as _,
+        now,
+        NotificationKind::HoldExpired.as_ref(),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+        WITH free AS (
+            SELECT bc.book_id, COUNT(*) AS count
+            FROM book_copies AS bc
+            INNER JOIN books AS b USING(book_id)
+            WHERE bc.status = 'available'
+            AND b.deleted_at IS NULL
+            AND bc.book_id = $1
+            AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+            GROUP BY bc.book_id
+        ),
+        reserved AS (
+            SELECT book_id, COUNT(*) AS count
+            FROM holds
+            WHERE ready_at IS NOT NULL
+            AND book_id = $1
+            GROUP BY book_id
+        ),
+        waiting AS (
+            SELECT
+                hold_id,
+                book_id,
+                ROW_NUMBER() OVER (
+                    PARTITION BY book_id ORDER BY created_at ASC, hold_id ASC
+                ) AS position
+            FROM holds
+            WHERE ready_at IS NULL
+            AND book_id = $1
+        ),
+        promoted AS (
+            UPDATE holds AS h
+            SET ready_at = $2, expires_at = $2::timestamptz + make_interval(days => $3)
+            FROM waiting AS w
+            INNER JOIN free AS f USING(book_id)
+            LEFT OUTER JOIN reserved AS r USING(book_id)
+            WHERE h.hold_id = w.hold_id
+            AND w.position <= f.count - COALESCE(r.count, 0)
+            RETURNING h.book_id, h.user_id
+        )
+        INSERT INTO notifications (user_id, kind, book_id, message)
+        SELECT
+            p.user_id,
+            $4,
+            p.book_id,
+            '予約した「' || b.title || '」を取り置きました。受け取りの期限までに借りてください'
+        FROM promoted AS p
+        INNER JOIN books AS b USING(book_id)
+        "#,
+        book_id as _,
+        now,
+        pickup_days,
+        NotificationKind::HoldReady.as_ref(),
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::{
+        notification::NotificationRepositoryImpl,
+        test_helper::{book_repository, checkout_repository, create_test_user},
+    };
+    use kernel::{
+        model::{
+            book::{CopyStatusAction, event::ChangeCopyStatus},
+            checkout::event::{CreateCheckout, RenewCheckout, UpdateReturned},
+        },
+        repository::{
+            book::BookRepository, checkout::CheckoutRepository,
+            notification::NotificationRepository,
+        },
+    };
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_hold_queue(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = HoldRepositoryImpl::new(ConnectionPool::new(pool.clone()), 3);
+        let checkout_repository = checkout_repository(pool.clone());
+        let notification_repository =
+            NotificationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let first = create_test_user(&pool, "First").await?.id;
+        let second = create_test_user(&pool, "Second").await?.id;
+        let now = chrono::Utc::now;
+        let checkout = |user_id: UserId| CreateCheckout::new(book_id, None, user_id, now());
+
+        // 今すぐ借りられる書籍は予約できない
+        let res = repository
+            .create(CreateHold::new(book_id, first, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 貸出中の書籍は、借りているユーザー以外が予約した順に並ぶ
+        checkout_repository.create(checkout(owner_id)).await?;
+        let res = repository
+            .create(CreateHold::new(book_id, owner_id, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let hold = repository
+            .create(CreateHold::new(book_id, first, now()))
+            .await?;
+        assert_eq!(hold.position, 1);
+        assert!(!hold.is_ready());
+        let res = repository
+            .create(CreateHold::new(book_id, first, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let hold = repository
+            .create(CreateHold::new(book_id, second, now()))
+            .await?;
+        assert_eq!(hold.position, 2);
+
+        // 予約がある間は延長できない
+        let checkout_id = checkout_repository.find_unreturned_all(false).await?[0].id;
+        let res = checkout_repository
+            .renew(RenewCheckout::new(checkout_id, book_id, owner_id, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 返却されると先頭の予約が取り置きになり、他のユーザーは借りられない
+        checkout_repository
+            .update_returned(UpdateReturned::new(checkout_id, book_id, owner_id, now()))
+            .await?;
+        let holds = repository.find_by_user_id(first).await?;
+        assert!(holds[0].is_ready());
+        assert!(holds[0].expires_at.unwrap() > now() + chrono::Duration::days(2));
+        let notifications = notification_repository.find_all(first).await?;
+        assert_eq!(notifications[0].kind, NotificationKind::HoldReady);
+        let res = checkout_repository.create(checkout(second)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 取り置き中の予約を取り消すと、次の予約が取り置きになる
+        let res = repository
+            .delete(DeleteHold::new(holds[0].id, second, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        repository
+            .delete(DeleteHold::new(holds[0].id, first, now()))
+            .await?;
+        let holds = repository.find_by_user_id(second).await?;
+        assert_eq!(holds[0].position, 1);
+        assert!(holds[0].is_ready());
+
+        // 受け取りの期限を過ぎた取り置きは取り消され、誰でも借りられるようになる
+        repository
+            .advance(now() + chrono::Duration::days(4))
+            .await?;
+        assert!(repository.find_by_user_id(second).await?.is_empty());
+        let notifications = notification_repository.find_all(second).await?;
+        assert!(
+            notifications
+                .iter()
+                .any(|n| n.kind == NotificationKind::HoldExpired)
+        );
+        checkout_repository.create(checkout(first)).await?;
+
+        // 取り置きの予約者が借りると、予約は済んだものとして削除される
+        repository
+            .create(CreateHold::new(book_id, second, now()))
+            .await?;
+        let checkout_id = checkout_repository.find_unreturned_all(false).await?[0].id;
+        checkout_repository
+            .update_returned(UpdateReturned::new(checkout_id, book_id, first, now()))
+            .await?;
+        checkout_repository.create(checkout(second)).await?;
+        assert!(repository.find_by_user_id(second).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_hold_queue_with_two_holders(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = HoldRepositoryImpl::new(ConnectionPool::new(pool.clone()), 3);
+        let checkout_repository = checkout_repository(pool.clone());
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let first = create_test_user(&pool, "First").await?.id;
+        let second = create_test_user(&pool, "Second").await?.id;
+        let now = chrono::Utc::now;
+        let checkout = |user_id: UserId| CreateCheckout::new(book_id, None, user_id, now());
+        let checkout_repository = &checkout_repository;
+        let return_book = |user_id: UserId| async move {
+            let checkout_id = checkout_repository.find_unreturned_all(false).await?[0].id;
+            checkout_repository
+                .update_returned(UpdateReturned::new(checkout_id, book_id, user_id, now()))
+                .await
+        };
+
+        checkout_repository.create(checkout(owner_id)).await?;
+        repository
+            .create(CreateHold::new(book_id, first, now()))
+            .await?;
+        repository
+            .create(CreateHold::new(book_id, second, now()))
+            .await?;
+
+        // 返却されると先頭の予約者が借りられ、順番待ちの予約者は借りられない
+        return_book(owner_id).await?;
+        let res = checkout_repository.create(checkout(second)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        checkout_repository.create(checkout(first)).await?;
+        assert!(repository.find_by_user_id(first).await?.is_empty());
+        assert!(!repository.find_by_user_id(second).await?[0].is_ready());
+
+        // 次に返却されると、順番待ちの予約者が取り置きになり借りられる
+        return_book(first).await?;
+        let holds = repository.find_by_user_id(second).await?;
+        assert_eq!(holds[0].position, 1);
+        assert!(holds[0].is_ready());
+        let res = checkout_repository.create(checkout(owner_id)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        checkout_repository.create(checkout(second)).await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_hold_on_repaired_copy(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = HoldRepositoryImpl::new(ConnectionPool::new(pool.clone()), 3);
+        let book_repository = book_repository(pool.clone());
+        let checkout_repository = checkout_repository(pool.clone());
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let holder = create_test_user(&pool, "Holder").await?.id;
+        let stranger = create_test_user(&pool, "Stranger").await?.id;
+        let copy_id = book_repository.find_by_id(book_id).await?.unwrap().copies[0].id;
+        let change = |action: CopyStatusAction| ChangeCopyStatus {
+            book_id,
+            copy_id,
+            action,
+            note: String::new(),
+            requested_user: owner_id,
+            requested_by_admin: false,
+        };
+        let now = chrono::Utc::now;
+
+        // 修理中で借りられない書籍は予約できる
+        book_repository
+            .change_copy_status(change(CopyStatusAction::SendToRepair))
+            .await?;
+        repository
+            .create(CreateHold::new(book_id, holder, now()))
+            .await?;
+
+        // 修理から戻った蔵書はすぐに予約者に取り置かれ、他のユーザーは借りられない
+        book_repository
+            .change_copy_status(change(CopyStatusAction::FinishRepair))
+            .await?;
+        assert!(repository.find_by_user_id(holder).await?[0].is_ready());
+        let res = checkout_repository
+            .create(CreateCheckout::new(book_id, None, stranger, now()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        checkout_repository
+            .create(CreateCheckout::new(book_id, None, holder, now()))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! 書籍の置き場所のDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::location::{
+    Location,
+    event::{CreateLocation, DeleteLocation, UpdateLocation},
+};
+use kernel::repository::location::LocationRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::{ConnectionPool, model::location::LocationRow};
+
+#[derive(new)]
+pub struct LocationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl LocationRepository for LocationRepositoryImpl {
+    /// 置き場所を登録する
+    async fn create(&self, event: CreateLocation) -> AppResult<Location> {
+        let row = sqlx::query_as!(
+            LocationRow,
+            r#"
+            INSERT INTO locations (site, room, shelf)
+            VALUES ($1, $2, $3)
+            RETURNING location_id, site, room, shelf
+            "#,
+            event.site,
+            event.room,
+            event.shelf,
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(map_duplicate_location)?;
+
+        Ok(row.into())
+    }
+
+    /// すべての置き場所を、拠点・部屋・棚の名前順に取得する
+    async fn find_all(&self) -> AppResult<Vec<Location>> {
+        let rows = sqlx::query_as!(
+            LocationRow,
+            r#"
+            SELECT location_id, site, room, shelf
+            FROM locations
+            ORDER BY site ASC, room ASC, shelf ASC
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().map(Location::from).collect())
+    }
+
+    /// 置き場所の名前を変更する
+    async fn update(&self, event: UpdateLocation) -> AppResult<Location> {
+        let row = sqlx::query_as!(
+            LocationRow,
+            r#"
+            UPDATE locations
+            SET site = $2, room = $3, shelf = $4
+            WHERE location_id = $1
+            RETURNING location_id, site, room, shelf
+            "#,
+            event.location_id as _,
+            event.site,
+            event.room,
+            event.shelf,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(map_duplicate_location)?
+        .ok_or_else(|| AppError::EntityNotFound("specified location not found".into()))?;
+
+        Ok(row.into())
+    }
+
+    /// 置き場所を削除する。書籍が置かれている場所は削除できない
+    async fn delete(&self, event: DeleteLocation) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM locations WHERE location_id = $1
+            "#,
+            event.location_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            // 削除済みの書籍も、復元時に置き場所を失わないよう対象とする
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                AppError::UnprocessableEntity(
+                    "specified location still has books; move them first".into(),
+                )
+            }
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified location not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// 拠点・部屋・棚の組み合わせが重複した場合は422とする
+fn map_duplicate_location(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            AppError::UnprocessableEntity("specified location already exists".into())
+        }
+        e => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::model::id::LocationId;
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_locations(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = LocationRepositoryImpl::new(ConnectionPool::new(pool));
+
+        let shelf_b = repository
+            .create(CreateLocation::new("本社".into(), "3F".into(), "B".into()))
+            .await?;
+        let shelf_a = repository
+            .create(CreateLocation::new("本社".into(), "3F".into(), "A".into()))
+            .await?;
+
+        // 同じ拠点・部屋・棚の組み合わせは登録できない
+        let res = repository
+            .create(CreateLocation::new("本社".into(), "3F".into(), "A".into()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 拠点・部屋・棚の名前順に取得できる
+        let locations = repository.find_all().await?;
+        assert_eq!(locations, vec![shelf_a.clone(), shelf_b.clone()]);
+
+        let updated = repository
+            .update(UpdateLocation::new(
+                shelf_b.id,
+                "本社".into(),
+                "4F".into(),
+                "B".into(),
+            ))
+            .await?;
+        assert_eq!(updated.id, shelf_b.id);
+        assert_eq!(updated.room, "4F");
+        let res = repository
+            .update(UpdateLocation::new(
+                shelf_b.id,
+                "本社".into(),
+                "3F".into(),
+                "A".into(),
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        let res = repository
+            .update(UpdateLocation::new(
+                LocationId::new(),
+                "本社".into(),
+                "3F".into(),
+                "C".into(),
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        repository.delete(DeleteLocation::new(shelf_a.id)).await?;
+        let res = repository.delete(DeleteLocation::new(shelf_a.id)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(repository.find_all().await?, vec![updated]);
+
+        Ok(())
+    }
+}
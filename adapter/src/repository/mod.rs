@@ -1,5 +1,15 @@
 pub mod auth;
 pub mod book;
 pub mod checkout;
+pub mod favorite;
 pub mod health;
+pub mod hold;
+pub mod location;
+pub mod notification;
+pub mod purchase_request;
+pub mod review;
 pub mod user;
+pub mod wishlist;
+
+#[cfg(test)]
+mod test_helper;
@@ -0,0 +1,71 @@
+//! 通知のDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::{
+    id::{BookId, UserId},
+    notification::{Notification, event::MarkNotificationRead},
+};
+use kernel::repository::notification::NotificationRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::{ConnectionPool, model::notification::NotificationRow};
+
+#[derive(new)]
+pub struct NotificationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl NotificationRepository for NotificationRepositoryImpl {
+    /// ユーザーへの通知を、新しい順に取得する
+    async fn find_all(&self, user_id: UserId) -> AppResult<Vec<Notification>> {
+        let rows = sqlx::query_as!(
+            NotificationRow,
+            r#"
+            SELECT
+                notification_id,
+                kind,
+                book_id AS "book_id: BookId",
+                message,
+                read_at,
+                created_at
+            FROM notifications
+            WHERE user_id = $1
+            ORDER BY created_at DESC, notification_id ASC
+            "#,
+            user_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        rows.into_iter().map(Notification::try_from).collect()
+    }
+
+    /// 通知を既読にする。既読の通知は既読にした日時を変えない
+    async fn mark_read(&self, event: MarkNotificationRead) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE notifications
+            SET read_at = COALESCE(read_at, CURRENT_TIMESTAMP(3))
+            WHERE notification_id = $1
+            AND user_id = $2
+            "#,
+            event.notification_id as _,
+            event.user_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified notification not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
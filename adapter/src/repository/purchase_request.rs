@@ -0,0 +1,557 @@
+//! 書籍の購入リクエストのDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::{
+    book::event::CreateBook,
+    id::{BookId, PurchaseRequestId, UserId},
+    isbn::Isbn,
+    list::PaginatedList,
+    notification::NotificationKind,
+    purchase_request::{
+        PurchaseRequest, PurchaseRequestListOptions, PurchaseRequestStatus,
+        event::{
+            ChangePurchaseRequestStatus, CreatePurchaseRequest, UnvotePurchaseRequest,
+            VotePurchaseRequest,
+        },
+    },
+};
+use kernel::repository::purchase_request::PurchaseRequestRepository;
+use shared::error::{AppError, AppResult};
+
+use super::book::insert_book;
+use crate::database::{
+    ConnectionPool,
+    model::purchase_request::{
+        PaginatedPurchaseRequestRow, PurchaseRequestLockRow, PurchaseRequestRow,
+    },
+};
+
+#[derive(new)]
+pub struct PurchaseRequestRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl PurchaseRequestRepository for PurchaseRequestRepositoryImpl {
+    /// 購入リクエストを登録する
+    /// 同じISBNの書籍が登録済みの場合や、同じISBNのリクエストが受領・却下されていない場合は422とする
+    async fn create(&self, event: CreatePurchaseRequest) -> AppResult<PurchaseRequest> {
+        let CreatePurchaseRequest {
+            requested_user,
+            isbn,
+            title,
+            author,
+            reason,
+            link,
+        } = event;
+
+        let registered = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM books WHERE isbn = $1 AND deleted_at IS NULL
+            ) AS "exists!"
+            "#,
+            isbn.as_isbn13(),
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if registered {
+            return Err(AppError::UnprocessableEntity(
+                "a book with the specified isbn is already in the library".into(),
+            ));
+        }
+
+        let purchase_request_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO purchase_requests (
+                user_id, isbn, isbn_display, title, author, reason, link
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING purchase_request_id AS "purchase_request_id: PurchaseRequestId"
+            "#,
+            requested_user as _,
+            isbn.as_isbn13(),
+            isbn.display(),
+            title,
+            author,
+            reason,
+            link,
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::UnprocessableEntity(
+                "a purchase request for the specified isbn is already in progress".into(),
+            ),
+            e => AppError::SpecificOperationError(e),
+        })?;
+
+        self.find_by_id(purchase_request_id, requested_user)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("specified purchase request not found".into()))
+    }
+
+    /// 購入リクエストを、賛成票の多い順、同数の場合は登録日時の古い順に取得する
+    async fn find_all(
+        &self,
+        options: PurchaseRequestListOptions,
+    ) -> AppResult<PaginatedList<PurchaseRequest>> {
+        let PurchaseRequestListOptions {
+            status,
+            viewer,
+            limit,
+            offset,
+        } = options;
+
+        let rows = sqlx::query_as!(
+            PaginatedPurchaseRequestRow,
+            r#"
+            SELECT
+                COUNT(*) OVER() AS "total!",
+                p.purchase_request_id,
+                p.user_id AS "user_id: UserId",
+                u.name AS "user_name?",
+                p.isbn,
+                p.isbn_display,
+                p.title,
+                p.author,
+                p.reason,
+                p.link,
+                p.status,
+                COALESCE(v.votes, 0) AS "votes!",
+                EXISTS (
+                    SELECT 1 FROM purchase_request_votes AS pv
+                    WHERE pv.purchase_request_id = p.purchase_request_id
+                    AND pv.user_id = $2
+                ) AS "voted!",
+                p.book_id AS "book_id: BookId",
+                p.created_at,
+                p.approved_at,
+                p.ordered_at,
+                p.received_at,
+                p.rejected_at
+            FROM purchase_requests AS p
+            LEFT JOIN users AS u ON u.user_id = p.user_id
+            LEFT JOIN (
+                SELECT purchase_request_id, COUNT(*) AS votes
+                FROM purchase_request_votes
+                GROUP BY purchase_request_id
+            ) AS v ON v.purchase_request_id = p.purchase_request_id
+            WHERE ($1::VARCHAR IS NULL OR p.status = $1)
+            ORDER BY COALESCE(v.votes, 0) DESC, p.created_at ASC, p.purchase_request_id ASC
+            LIMIT $3
+            OFFSET $4
+            "#,
+            status.map(|s| s.as_ref().to_string()),
+            viewer as _,
+            limit,
+            offset,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // レコードがない場合はtotalを0にする
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let items = rows
+            .into_iter()
+            .map(PurchaseRequest::try_from)
+            .collect::<AppResult<Vec<_>>>()?;
+
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        })
+    }
+
+    /// 購入リクエストを取得する
+    async fn find_by_id(
+        &self,
+        purchase_request_id: PurchaseRequestId,
+        viewer: UserId,
+    ) -> AppResult<Option<PurchaseRequest>> {
+        let row = sqlx::query_as!(
+            PurchaseRequestRow,
+            r#"
+            SELECT
+                p.purchase_request_id,
+                p.user_id AS "user_id: UserId",
+                u.name AS "user_name?",
+                p.isbn,
+                p.isbn_display,
+                p.title,
+                p.author,
+                p.reason,
+                p.link,
+                p.status,
+                (
+                    SELECT COUNT(*) FROM purchase_request_votes AS pv
+                    WHERE pv.purchase_request_id = p.purchase_request_id
+                ) AS "votes!",
+                EXISTS (
+                    SELECT 1 FROM purchase_request_votes AS pv
+                    WHERE pv.purchase_request_id = p.purchase_request_id
+                    AND pv.user_id = $2
+                ) AS "voted!",
+                p.book_id AS "book_id: BookId",
+                p.created_at,
+                p.approved_at,
+                p.ordered_at,
+                p.received_at,
+                p.rejected_at
+            FROM purchase_requests AS p
+            LEFT JOIN users AS u ON u.user_id = p.user_id
+            WHERE p.purchase_request_id = $1
+            "#,
+            purchase_request_id as _,
+            viewer as _,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        row.map(PurchaseRequest::try_from).transpose()
+    }
+
+    /// 受付中か承認済みの購入リクエストに賛成票を入れる。入れ済みの場合は何もしない
+    async fn vote(&self, event: VotePurchaseRequest) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        let row = lock_purchase_request(&mut tx, event.purchase_request_id).await?;
+
+        if row.user_id == Some(event.user_id) {
+            return Err(AppError::UnprocessableEntity(
+                "you cannot vote for your own purchase request".into(),
+            ));
+        }
+        if !parse_status(&row.status)?.is_votable() {
+            return Err(AppError::UnprocessableEntity(
+                "specified purchase request is no longer open for votes".into(),
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO purchase_request_votes (purchase_request_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            event.purchase_request_id as _,
+            event.user_id as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    /// 購入リクエストへの賛成票を取り消す。入れていない場合は何もしない
+    async fn unvote(&self, event: UnvotePurchaseRequest) -> AppResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM purchase_request_votes
+            WHERE purchase_request_id = $1
+            AND user_id = $2
+            "#,
+            event.purchase_request_id as _,
+            event.user_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(())
+    }
+
+    /// 購入リクエストの状態を変え、変わった日時を記録する
+    /// 受領済みにした場合は、リクエストの内容から書籍を登録する
+    /// リクエストしたユーザーには、状態が変わったことを通知する
+    async fn change_status(
+        &self,
+        event: ChangePurchaseRequestStatus,
+    ) -> AppResult<PurchaseRequest> {
+        let ChangePurchaseRequestStatus {
+            purchase_request_id,
+            status,
+            requested_user,
+        } = event;
+
+        let mut tx = self.db.begin().await?;
+        let row = lock_purchase_request(&mut tx, purchase_request_id).await?;
+
+        let from = parse_status(&row.status)?;
+        if !from.can_change_to(status) {
+            return Err(AppError::UnprocessableEntity(format!(
+                "cannot change purchase request from {} to {}",
+                from.as_ref(),
+                status.as_ref()
+            )));
+        }
+
+        let book_id = if status == PurchaseRequestStatus::Received {
+            let book = CreateBook {
+                title: row.title.clone(),
+                author: row.author.clone(),
+                isbn: Isbn::from_stored(row.isbn, row.isbn_display),
+                description: String::new(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+            };
+            Some(insert_book(&mut tx, book, requested_user).await?)
+        } else {
+            None
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE purchase_requests
+            SET
+                status = $2,
+                book_id = COALESCE($3, book_id),
+                approved_at = CASE WHEN $2::VARCHAR = 'approved' THEN CURRENT_TIMESTAMP(3) ELSE approved_at END,
+                ordered_at = CASE WHEN $2::VARCHAR = 'ordered' THEN CURRENT_TIMESTAMP(3) ELSE ordered_at END,
+                received_at = CASE WHEN $2::VARCHAR = 'received' THEN CURRENT_TIMESTAMP(3) ELSE received_at END,
+                rejected_at = CASE WHEN $2::VARCHAR = 'rejected' THEN CURRENT_TIMESTAMP(3) ELSE rejected_at END
+            WHERE purchase_request_id = $1
+            "#,
+            purchase_request_id as _,
+            status.as_ref(),
+            book_id as Option<BookId>,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if let Some(user_id) = row.user_id {
+            sqlx::query!(
+                r#"
+                INSERT INTO notifications (user_id, kind, book_id, message)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                user_id as _,
+                NotificationKind::PurchaseRequestStatusChanged.as_ref(),
+                book_id as Option<BookId>,
+                format!(
+                    "購入リクエスト「{}」が{}されました",
+                    row.title,
+                    status_label(status)
+                ),
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        self.find_by_id(purchase_request_id, requested_user)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("specified purchase request not found".into()))
+    }
+}
+
+/// 購入リクエストに行ロックをかけて取得する
+async fn lock_purchase_request(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    purchase_request_id: PurchaseRequestId,
+) -> AppResult<PurchaseRequestLockRow> {
+    sqlx::query_as!(
+        PurchaseRequestLockRow,
+        r#"
+        SELECT
+            user_id AS "user_id: UserId",
+            isbn,
+            isbn_display,
+            title,
+            author,
+            status
+        FROM purchase_requests
+        WHERE purchase_request_id = $1
+        FOR UPDATE
+        "#,
+        purchase_request_id as _,
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified purchase request not found".into()))
+}
+
+/// 通知のメッセージに使う、状態を変えた操作の名前
+fn status_label(status: PurchaseRequestStatus) -> &'static str {
+    match status {
+        PurchaseRequestStatus::Open => "再開",
+        PurchaseRequestStatus::Approved => "承認",
+        PurchaseRequestStatus::Ordered => "発注",
+        PurchaseRequestStatus::Received => "受領",
+        PurchaseRequestStatus::Rejected => "却下",
+    }
+}
+
+fn parse_status(status: &str) -> AppResult<PurchaseRequestStatus> {
+    status
+        .parse::<PurchaseRequestStatus>()
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::{
+        notification::NotificationRepositoryImpl,
+        test_helper::{book_repository, create_test_user},
+    };
+    use kernel::repository::{book::BookRepository, notification::NotificationRepository};
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_purchase_request_lifecycle(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = PurchaseRequestRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let notification_repository =
+            NotificationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repository = book_repository(pool.clone());
+        let requester = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let admin = create_test_user(&pool, "Admin").await?.id;
+        let request = |isbn: &str| CreatePurchaseRequest {
+            requested_user: requester,
+            isbn: Isbn::from_str(isbn).unwrap(),
+            title: "Rust本".into(),
+            author: "Test Author".into(),
+            reason: "チームで読みたい".into(),
+            link: Some("https://example.com/rust".into()),
+        };
+
+        // 登録済みの書籍のISBNはリクエストできない
+        let res = repository.create(request("9784798061702")).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let created = repository.create(request("978-4-297-14352-7")).await?;
+        assert_eq!(created.status, PurchaseRequestStatus::Open);
+        assert_eq!(created.requested_by.as_ref().map(|u| u.id), Some(requester));
+        assert_eq!(created.votes, 0);
+
+        // 同じISBNのリクエストは、受領・却下されるまで登録できない
+        let res = repository.create(request("9784297143527")).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 自分のリクエストには賛成票を入れられない
+        let res = repository
+            .vote(VotePurchaseRequest::new(created.id, requester))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        repository
+            .vote(VotePurchaseRequest::new(created.id, admin))
+            .await?;
+        repository
+            .vote(VotePurchaseRequest::new(created.id, admin))
+            .await?;
+        let found = repository.find_by_id(created.id, admin).await?.unwrap();
+        assert_eq!(found.votes, 1);
+        assert!(found.voted);
+
+        // 受付中のリクエストは、承認前に発注済みにできない
+        let res = repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                created.id,
+                PurchaseRequestStatus::Ordered,
+                admin,
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let approved = repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                created.id,
+                PurchaseRequestStatus::Approved,
+                admin,
+            ))
+            .await?;
+        assert_eq!(approved.status, PurchaseRequestStatus::Approved);
+        assert!(approved.approved_at.is_some());
+        repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                created.id,
+                PurchaseRequestStatus::Ordered,
+                admin,
+            ))
+            .await?;
+
+        // 受領すると、リクエストの内容から書籍が登録される
+        let received = repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                created.id,
+                PurchaseRequestStatus::Received,
+                admin,
+            ))
+            .await?;
+        assert_eq!(received.status, PurchaseRequestStatus::Received);
+        assert!(received.ordered_at.is_some());
+        assert!(received.received_at.is_some());
+        let book = book_repository
+            .find_by_id(received.book_id.unwrap())
+            .await?
+            .unwrap();
+        assert_eq!(book.title, "Rust本");
+        assert_eq!(book.isbn.as_isbn13(), "9784297143527");
+        assert_eq!(book.owner.id, admin);
+        assert_eq!(book.copies.len(), 1);
+
+        // 受領済みのリクエストには賛成票を入れられない
+        repository
+            .unvote(UnvotePurchaseRequest::new(created.id, admin))
+            .await?;
+        let res = repository
+            .vote(VotePurchaseRequest::new(created.id, admin))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // リクエストしたユーザーには、状態が変わるたびに通知される
+        let notifications = notification_repository.find_all(requester).await?;
+        assert_eq!(notifications.len(), 3);
+        assert!(
+            notifications
+                .iter()
+                .all(|n| n.kind == NotificationKind::PurchaseRequestStatusChanged)
+        );
+
+        // 却下されたリクエストは一覧の状態で絞り込める
+        let other = repository.create(request("9784873119786")).await?;
+        repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                other.id,
+                PurchaseRequestStatus::Rejected,
+                admin,
+            ))
+            .await?;
+        let list = repository
+            .find_all(PurchaseRequestListOptions {
+                status: Some(PurchaseRequestStatus::Rejected),
+                viewer: admin,
+                limit: 20,
+                offset: 0,
+            })
+            .await?;
+        assert_eq!(list.total, 1);
+        assert_eq!(list.items[0].id, other.id);
+        assert!(list.items[0].rejected_at.is_some());
+
+        let res = repository
+            .change_status(ChangePurchaseRequestStatus::new(
+                PurchaseRequestId::new(),
+                PurchaseRequestStatus::Approved,
+                admin,
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+}
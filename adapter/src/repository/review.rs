@@ -0,0 +1,328 @@
+//! 書籍のレビューのDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::{
+    id::{BookId, ReviewId, UserId},
+    list::PaginatedList,
+    review::{
+        Review, ReviewListOptions,
+        event::{CreateReview, DeleteReview, UpdateReview},
+    },
+};
+use kernel::repository::review::ReviewRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::{
+    ConnectionPool,
+    model::review::{PaginatedReviewRow, ReviewOwnerRow, ReviewRow},
+};
+
+#[derive(new)]
+pub struct ReviewRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl ReviewRepository for ReviewRepositoryImpl {
+    /// 削除済みでない書籍にレビューを投稿する
+    async fn create(&self, event: CreateReview) -> AppResult<Review> {
+        let row = sqlx::query_as!(
+            ReviewRow,
+            r#"
+            WITH inserted AS (
+                INSERT INTO book_reviews (book_id, user_id, rating, comment)
+                SELECT b.book_id, $2, $3, $4
+                FROM books AS b
+                WHERE b.book_id = $1
+                AND b.deleted_at IS NULL
+                RETURNING *
+            )
+            SELECT
+                i.review_id AS "review_id!: ReviewId",
+                i.book_id AS "book_id!: BookId",
+                u.user_id AS "user_id!: UserId",
+                u.name AS "user_name!",
+                i.rating AS "rating!",
+                i.comment,
+                i.created_at AS "created_at!",
+                i.updated_at AS "updated_at!"
+            FROM inserted AS i
+            INNER JOIN users AS u USING(user_id)
+            "#,
+            event.book_id as _,
+            event.requested_user as _,
+            event.rating,
+            event.comment,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::UnprocessableEntity("you have already reviewed this book".into())
+            }
+            e => AppError::SpecificOperationError(e),
+        })?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+        Ok(row.into())
+    }
+
+    /// 書籍のレビューを、投稿日時の新しい順に取得する
+    async fn find_by_book(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<Review>> {
+        let ReviewListOptions { limit, offset } = options;
+
+        let rows = sqlx::query_as!(
+            PaginatedReviewRow,
+            r#"
+            SELECT
+                COUNT(*) OVER() AS "total!",
+                r.review_id,
+                r.book_id,
+                u.user_id,
+                u.name AS user_name,
+                r.rating,
+                r.comment,
+                r.created_at,
+                r.updated_at
+            FROM book_reviews AS r
+            INNER JOIN users AS u USING(user_id)
+            WHERE r.book_id = $1
+            ORDER BY r.created_at DESC, r.review_id ASC
+            LIMIT $2
+            OFFSET $3
+            "#,
+            book_id as _,
+            limit,
+            offset,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // レコードがない場合はtotalを0にする
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let items = rows.into_iter().map(Review::from).collect();
+
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        })
+    }
+
+    /// レビューの評価と感想を変更する
+    async fn update(&self, event: UpdateReview) -> AppResult<Review> {
+        let mut tx = self.db.begin().await?;
+        lock_review(
+            &mut tx,
+            event.book_id,
+            event.review_id,
+            event.requested_user,
+            event.requested_by_admin,
+        )
+        .await?;
+
+        let row = sqlx::query_as!(
+            ReviewRow,
+            r#"
+            WITH updated AS (
+                UPDATE book_reviews
+                SET rating = $2, comment = $3
+                WHERE review_id = $1
+                RETURNING *
+            )
+            SELECT
+                r.review_id AS "review_id!: ReviewId",
+                r.book_id AS "book_id!: BookId",
+                u.user_id AS "user_id!: UserId",
+                u.name AS "user_name!",
+                r.rating AS "rating!",
+                r.comment,
+                r.created_at AS "created_at!",
+                r.updated_at AS "updated_at!"
+            FROM updated AS r
+            INNER JOIN users AS u USING(user_id)
+            "#,
+            event.review_id as _,
+            event.rating,
+            event.comment,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(row.into())
+    }
+
+    /// レビューを削除する
+    async fn delete(&self, event: DeleteReview) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+        lock_review(
+            &mut tx,
+            event.book_id,
+            event.review_id,
+            event.requested_user,
+            event.requested_by_admin,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM book_reviews WHERE review_id = $1
+            "#,
+            event.review_id as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+/// レビューに行ロックをかけ、投稿者か管理者でなければエラーとする
+async fn lock_review(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    review_id: ReviewId,
+    requested_user: UserId,
+    requested_by_admin: bool,
+) -> AppResult<()> {
+    let row = sqlx::query_as!(
+        ReviewOwnerRow,
+        r#"
+        SELECT user_id
+        FROM book_reviews
+        WHERE review_id = $1
+        AND book_id = $2
+        FOR UPDATE
+        "#,
+        review_id as _,
+        book_id as _,
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified review not found".into()))?;
+
+    if row.user_id != requested_user && !requested_by_admin {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::test_helper::{book_repository, create_test_user};
+    use kernel::{model::book::BookRating, repository::book::BookRepository};
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_reviews(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = ReviewRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repository = book_repository(pool.clone());
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = create_test_user(&pool, "Reader").await?;
+
+        let mine = repository
+            .create(CreateReview::new(
+                book_id,
+                5,
+                Some("とても良かった".into()),
+                owner_id,
+            ))
+            .await?;
+        assert_eq!(mine.reviewer.id, owner_id);
+        let theirs = repository
+            .create(CreateReview::new(book_id, 2, None, other.id))
+            .await?;
+
+        // 同じ書籍に2件目のレビューは投稿できない
+        let res = repository
+            .create(CreateReview::new(book_id, 4, None, owner_id))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+        // 存在しない書籍にはレビューを投稿できない
+        let res = repository
+            .create(CreateReview::new(BookId::new(), 4, None, owner_id))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 書籍に評価の平均とレビューの数が付く
+        let book = book_repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(
+            book.rating,
+            BookRating {
+                average: Some(3.5),
+                count: 2
+            }
+        );
+
+        // 新しい順に取得でき、limitとoffsetが効く
+        let page = repository
+            .find_by_book(
+                book_id,
+                ReviewListOptions {
+                    limit: 1,
+                    offset: 0,
+                },
+            )
+            .await?;
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, theirs.id);
+
+        // 他のユーザーのレビューは、管理者でなければ変更・削除できない
+        let res = repository
+            .update(UpdateReview::new(
+                book_id, theirs.id, 1, None, owner_id, false,
+            ))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+        let res = repository
+            .delete(DeleteReview::new(book_id, theirs.id, owner_id, false))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperationError)));
+
+        let updated = repository
+            .update(UpdateReview::new(
+                book_id,
+                theirs.id,
+                3,
+                Some("読み返したら良かった".into()),
+                owner_id,
+                true,
+            ))
+            .await?;
+        assert_eq!(updated.rating, 3);
+        assert_eq!(updated.reviewer.id, other.id);
+
+        repository
+            .delete(DeleteReview::new(book_id, mine.id, owner_id, false))
+            .await?;
+        let res = repository
+            .delete(DeleteReview::new(book_id, mine.id, owner_id, false))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        let book = book_repository.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.rating.average, Some(3.0));
+        assert_eq!(book.rating.count, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! リポジトリのテストで共通して使うヘルパー
+
+use std::sync::Arc;
+
+use kernel::{
+    model::user::{User, event::CreateUser},
+    repository::user::UserRepository,
+};
+use shared::{config::CheckoutConfig, error::AppResult};
+
+use super::{book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl, user::UserRepositoryImpl};
+use crate::{database::ConnectionPool, storage::local::LocalBlobStorage};
+
+/// テストごとに一時ディレクトリを保存先とする書籍リポジトリを作成する
+pub(crate) fn book_repository(pool: sqlx::PgPool) -> BookRepositoryImpl {
+    let root = std::env::temp_dir().join(format!("book-covers-{}", uuid::Uuid::new_v4()));
+    let blob_storage = LocalBlobStorage::new(&shared::config::StorageConfig { root });
+    BookRepositoryImpl::new(
+        ConnectionPool::new(pool),
+        Arc::new(blob_storage),
+        CheckoutConfig::DEFAULT_HOLD_PICKUP_DAYS,
+    )
+}
+
+/// 貸出期間などを既定の設定とする貸出リポジトリを作成する
+pub(crate) fn checkout_repository(pool: sqlx::PgPool) -> CheckoutRepositoryImpl {
+    CheckoutRepositoryImpl::new(
+        ConnectionPool::new(pool),
+        CheckoutConfig::DEFAULT_LOAN_PERIOD_DAYS,
+        CheckoutConfig::DEFAULT_MAX_RENEWALS,
+        CheckoutConfig::DEFAULT_HOLD_PICKUP_DAYS,
+    )
+}
+
+/// 指定した名前のユーザーを登録する。メールアドレスは名前から作る
+pub(crate) async fn create_test_user(pool: &sqlx::PgPool, name: &str) -> AppResult<User> {
+    UserRepositoryImpl::new(ConnectionPool::new(pool.clone()))
+        .create(CreateUser {
+            name: name.into(),
+            email: format!("{}@example.com", name.to_lowercase().replace(' ', ".")),
+            password: "test_password".into(),
+        })
+        .await
+}
@@ -3,16 +3,23 @@ use derive_new::new;
 
 use kernel::model::{
     id::UserId,
+    list::{CursorOptions, CursorPaginatedList},
     role::Role,
     user::{
         User,
-        event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole},
+        event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserProfile, UpdateUserRole},
     },
 };
 use kernel::repository::user::UserRepository;
 use shared::error::{AppError, AppResult};
 
-use crate::database::{ConnectionPool, model::user::UserRow};
+use crate::database::{
+    ConnectionPool,
+    model::{
+        cursor::{UserCursor, decode_cursor, encode_cursor},
+        user::UserRow,
+    },
+};
 
 #[derive(new)]
 pub struct UserRepositoryImpl {
@@ -41,7 +48,7 @@ impl UserRepository for UserRepositoryImpl {
         )
         .execute(self.db.inner_ref())
         .await
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(map_email_in_use)?;
 
         if res.rows_affected() == 0 {
             return Err(AppError::NoRowsAffectedError(
@@ -65,7 +72,7 @@ impl UserRepository for UserRepositoryImpl {
             SELECT u.user_id, u.name, u.email, r.name as role_name, u.created_at, u.updated_at
             FROM users AS u
             INNER JOIN roles AS r USING(role_id)
-            ORDER BY u.created_at DESC
+            ORDER BY u.created_at DESC, u.user_id DESC
             "#,
         )
         .fetch_all(self.db.inner_ref())
@@ -77,6 +84,63 @@ impl UserRepository for UserRepositoryImpl {
 
         Ok(users)
     }
+    /// カーソルの位置からユーザーを登録日時の新しい順に取得する
+    async fn find_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<User>> {
+        let CursorOptions { limit, cursor } = options;
+        let cursor = cursor
+            .as_deref()
+            .map(decode_cursor::<UserCursor>)
+            .transpose()?;
+        let (created_at, cursor_user_id) = match cursor {
+            Some(c) => (Some(c.created_at), Some(c.user_id)),
+            None => (None, None),
+        };
+
+        // 次のページの有無を判定するため、limitより1件多く取得する
+        let mut rows = sqlx::query_as!(
+            UserRow,
+            r#"
+            SELECT u.user_id, u.name, u.email, r.name as role_name, u.created_at, u.updated_at
+            FROM users AS u
+            INNER JOIN roles AS r USING(role_id)
+            WHERE $3::uuid IS NULL
+                OR (u.created_at, u.user_id) < ($2::timestamptz, $3::uuid)
+            ORDER BY u.created_at DESC, u.user_id DESC
+            LIMIT $1
+            "#,
+            limit + 1,
+            created_at,
+            cursor_user_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let next_cursor = if rows.len() as i64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().map(|row| {
+                encode_cursor(&UserCursor {
+                    created_at: row.created_at,
+                    user_id: row.user_id,
+                })
+            })
+        } else {
+            None
+        };
+        let items = rows
+            .into_iter()
+            .filter_map(|row| User::try_from(row).ok())
+            .collect();
+
+        Ok(CursorPaginatedList {
+            items,
+            limit,
+            next_cursor,
+        })
+    }
     /// ユーザーを取得する
     async fn find_current_user(&self, current_user_id: UserId) -> AppResult<Option<User>> {
         let row = sqlx::query_as!(
@@ -99,23 +163,51 @@ impl UserRepository for UserRepositoryImpl {
         }
     }
     /// ユーザーを削除する
+    /// 書籍を所有しているユーザーは、書籍ごと削除されないよう削除しない
     async fn delete(&self, event: DeleteUser) -> AppResult<()> {
-        let res = sqlx::query!(
+        let mut tx = self.db.begin().await?;
+
+        // 書籍の登録や譲渡はユーザーの行を外部キーで参照するため、
+        // ユーザーの行をロックして、確認から削除までの間に所有する書籍が増えないようにする
+        sqlx::query!(
             r#"
-            DELETE FROM users WHERE user_id = $1
+            SELECT user_id FROM users WHERE user_id = $1 FOR UPDATE
             "#,
             event.id as _,
         )
-        .execute(self.db.inner_ref())
+        .fetch_optional(&mut *tx)
         .await
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("Specified user not found".to_string()))?;
 
-        if res.rows_affected() == 0 {
-            return Err(AppError::EntityNotFound(
-                "Specified user not found".to_string(),
+        // 削除済みの書籍も、復元できるよう所有者を残しておく必要がある
+        let owns_books = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM books WHERE user_id = $1) AS "exists!"
+            "#,
+            event.id as _,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if owns_books {
+            return Err(AppError::UnprocessableEntity(
+                "specified user still owns books; transfer them to another user first".into(),
             ));
         }
 
+        sqlx::query!(
+            r#"
+            DELETE FROM users WHERE user_id = $1
+            "#,
+            event.id as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
         Ok(())
     }
     /// ユーザーのロールを更新する
@@ -140,6 +232,29 @@ impl UserRepository for UserRepositoryImpl {
 
         Ok(())
     }
+    /// ユーザーの名前とメールアドレスのうち、指定された項目のみを更新する
+    async fn update_profile(&self, event: UpdateUserProfile) -> AppResult<User> {
+        let row = sqlx::query_as!(
+            UserRow,
+            r#"
+            UPDATE users AS u
+            SET name = COALESCE($2, u.name), email = COALESCE($3, u.email)
+            FROM roles AS r
+            WHERE u.user_id = $1
+            AND r.role_id = u.role_id
+            RETURNING u.user_id, u.name, u.email, r.name as role_name, u.created_at, u.updated_at
+            "#,
+            event.id as _,
+            event.name,
+            event.email,
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(map_email_in_use)?
+        .ok_or_else(|| AppError::EntityNotFound("Specified user not found".to_string()))?;
+
+        User::try_from(row)
+    }
     /// ユーザーのパスワードを更新する
     async fn update_password(&self, event: UpdateUserPassword) -> AppResult<()> {
         let mut tx = self.db.begin().await?;
@@ -190,3 +305,136 @@ fn verify_password(password: &str, hashed: &str) -> AppResult<()> {
     }
     Ok(())
 }
+
+/// メールアドレスの一意制約の違反を、使用中のメールアドレスを指定したエラーとする
+/// メールアドレスはログインに使うため、他のユーザーと重複させない
+fn map_email_in_use(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            AppError::UnprocessableEntity("specified email is already in use".into())
+        }
+        e => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::repository::test_helper::create_test_user;
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_find_all_by_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+
+        for i in 0..4 {
+            create_test_user(&pool, &format!("User {i}")).await?;
+        }
+        let expected = repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|u| u.id)
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), 5);
+
+        // 2件ずつ最後まで取得し、全件取得と同じ順序になることを確認
+        let mut walked = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = repository
+                .find_all_by_cursor(CursorOptions { limit: 2, cursor })
+                .await?;
+            walked.extend(page.items.into_iter().map(|u| u.id));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(walked, expected);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_update_profile(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+
+        let user = create_test_user(&pool, "Before").await?;
+
+        // 指定した項目のみが更新される
+        let updated = repository
+            .update_profile(UpdateUserProfile {
+                id: user.id,
+                name: Some("After".into()),
+                email: None,
+            })
+            .await?;
+        assert_eq!(updated.name, "After");
+        assert_eq!(updated.email, "before@example.com");
+        assert_eq!(updated.role, Role::User);
+
+        // 他のユーザーのメールアドレスには変更できない
+        let res = repository
+            .update_profile(UpdateUserProfile {
+                id: user.id,
+                name: None,
+                email: Some("test@example.com".into()),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 自分の現在のメールアドレスはそのまま指定できる
+        let updated = repository
+            .update_profile(UpdateUserProfile {
+                id: user.id,
+                name: None,
+                email: Some("before@example.com".into()),
+            })
+            .await?;
+        assert_eq!(updated.email, "before@example.com");
+
+        // 使用中のメールアドレスでは登録できない
+        let res = create_test_user(&pool, "Before").await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_delete_waits_for_book_registration(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = Arc::new(UserRepositoryImpl::new(ConnectionPool::new(pool.clone())));
+
+        let user = create_test_user(&pool, "Owner").await?;
+
+        // 書籍の登録中に削除した場合は、登録が終わるのを待ってから所有していることを確認する
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO books (title, author, isbn, description, user_id)
+            VALUES ('Title', 'Author', '9784798061702', '', $1)
+            "#,
+            user.id as _,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let delete = tokio::spawn({
+            let repository = repository.clone();
+            async move { repository.delete(DeleteUser { id: user.id }).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!delete.is_finished());
+
+        tx.commit().await?;
+        let res = delete.await?;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        // 存在しないユーザーは404とする
+        let res = repository.delete(DeleteUser { id: UserId::new() }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+}
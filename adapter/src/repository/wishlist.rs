@@ -0,0 +1,233 @@
+//! ほしいものリストのDB操作のための具象実装をするモジュール
+
+use async_trait::async_trait;
+use derive_new::new;
+
+use kernel::model::{
+    id::{BookId, UserId},
+    wishlist::{
+        WishlistItem,
+        event::{CreateWishlistItem, DeleteWishlistItem},
+    },
+};
+use kernel::repository::wishlist::WishlistRepository;
+use shared::error::{AppError, AppResult};
+
+use crate::database::{ConnectionPool, model::wishlist::WishlistItemRow};
+
+#[derive(new)]
+pub struct WishlistRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl WishlistRepository for WishlistRepositoryImpl {
+    /// ほしいものリストに項目を追加する
+    /// 同じISBNの書籍がすでに登録されている場合は、ほしいものリストではなく書籍を借りればよいため422とする
+    async fn create(&self, event: CreateWishlistItem) -> AppResult<WishlistItem> {
+        let CreateWishlistItem {
+            user_id,
+            isbn,
+            title,
+        } = event;
+        if isbn.is_none() && title.is_none() {
+            return Err(AppError::UnprocessableEntity(
+                "either isbn or title is required".into(),
+            ));
+        }
+
+        if let Some(isbn) = &isbn {
+            let registered = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE isbn = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+                "#,
+                isbn.as_isbn13(),
+            )
+            .fetch_one(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            if registered {
+                return Err(AppError::UnprocessableEntity(
+                    "a book with the specified isbn is already in the library".into(),
+                ));
+            }
+        }
+
+        let row = sqlx::query_as!(
+            WishlistItemRow,
+            r#"
+            INSERT INTO wishlist_items (user_id, isbn, isbn_display, title)
+            VALUES ($1, $2, $3, $4)
+            RETURNING
+                wishlist_item_id,
+                isbn,
+                isbn_display,
+                title,
+                fulfilled_book_id AS "fulfilled_book_id: BookId",
+                created_at
+            "#,
+            user_id as _,
+            isbn.as_ref().map(|i| i.as_isbn13()),
+            isbn.as_ref().map(|i| i.display()),
+            title,
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(row.into())
+    }
+
+    /// ユーザーのほしいものリストを、追加した順に取得する
+    async fn find_all(&self, user_id: UserId) -> AppResult<Vec<WishlistItem>> {
+        let rows = sqlx::query_as!(
+            WishlistItemRow,
+            r#"
+            SELECT
+                wishlist_item_id,
+                isbn,
+                isbn_display,
+                title,
+                fulfilled_book_id AS "fulfilled_book_id: BookId",
+                created_at
+            FROM wishlist_items
+            WHERE user_id = $1
+            ORDER BY created_at ASC, wishlist_item_id ASC
+            "#,
+            user_id as _,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(rows.into_iter().map(WishlistItem::from).collect())
+    }
+
+    /// ほしいものリストから項目を削除する。他のユーザーの項目は存在しないものとして扱う
+    async fn delete(&self, event: DeleteWishlistItem) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM wishlist_items
+            WHERE wishlist_item_id = $1
+            AND user_id = $2
+            "#,
+            event.wishlist_item_id as _,
+            event.user_id as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(AppError::EntityNotFound(
+                "specified wishlist item not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::repository::{
+        notification::NotificationRepositoryImpl, test_helper::book_repository,
+    };
+    use kernel::{
+        model::{
+            book::event::CreateBook,
+            id::WishlistItemId,
+            isbn::Isbn,
+            notification::{NotificationKind, event::MarkNotificationRead},
+        },
+        repository::{book::BookRepository, notification::NotificationRepository},
+    };
+
+    #[sqlx::test(fixtures("common"))]
+    async fn test_wishlist_notifies_when_book_registered(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repository = WishlistRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let notification_repository =
+            NotificationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repository = book_repository(pool.clone());
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let isbn = Isbn::from_str("978-4-7980-6170-2")?;
+
+        // ISBNと書名のどちらもない項目は追加できない
+        let res = repository
+            .create(CreateWishlistItem::new(user_id, None, None))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        let by_isbn = repository
+            .create(CreateWishlistItem::new(user_id, Some(isbn.clone()), None))
+            .await?;
+        let by_title = repository
+            .create(CreateWishlistItem::new(
+                user_id,
+                None,
+                Some("まだ出ていない本".into()),
+            ))
+            .await?;
+        let items = repository.find_all(user_id).await?;
+        assert_eq!(
+            items.iter().map(|i| i.id).collect::<Vec<_>>(),
+            vec![by_isbn.id, by_title.id]
+        );
+        assert!(notification_repository.find_all(user_id).await?.is_empty());
+
+        // 同じISBNの書籍が登録されると通知され、項目に書籍が記録される
+        book_repository
+            .create(
+                CreateBook {
+                    title: "Rust本".into(),
+                    author: "Test Author".into(),
+                    isbn: isbn.clone(),
+                    description: "".into(),
+                    publisher: None,
+                    published_on: None,
+                    page_count: None,
+                },
+                user_id,
+            )
+            .await?;
+        let notifications = notification_repository.find_all(user_id).await?;
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(
+            notifications[0].kind,
+            NotificationKind::WishlistBookRegistered
+        );
+        assert!(notifications[0].message.contains("Rust本"));
+        assert!(notifications[0].read_at.is_none());
+        let items = repository.find_all(user_id).await?;
+        assert_eq!(items[0].fulfilled_book_id, notifications[0].book_id);
+        assert!(items[1].fulfilled_book_id.is_none());
+
+        // 登録済みの書籍のISBNはほしいものリストに追加できない
+        let res = repository
+            .create(CreateWishlistItem::new(user_id, Some(isbn), None))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntity(_))));
+
+        notification_repository
+            .mark_read(MarkNotificationRead::new(user_id, notifications[0].id))
+            .await?;
+        let notifications = notification_repository.find_all(user_id).await?;
+        assert!(notifications[0].read_at.is_some());
+
+        repository
+            .delete(DeleteWishlistItem::new(user_id, by_title.id))
+            .await?;
+        let res = repository
+            .delete(DeleteWishlistItem::new(user_id, WishlistItemId::new()))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(repository.find_all(user_id).await?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use async_trait::async_trait;
+use shared::{
+    config::StorageConfig,
+    error::{AppError, AppResult},
+};
+
+use super::BlobStorage;
+
+/// ローカルのファイルシステムにデータを保存するストレージ
+/// キーを保存先ディレクトリからの相対パスとして扱う
+pub struct LocalBlobStorage {
+    root: PathBuf,
+}
+
+impl LocalBlobStorage {
+    pub fn new(config: &StorageConfig) -> Self {
+        Self {
+            root: config.root.clone(),
+        }
+    }
+
+    /// キーに対応するファイルのパスを返す
+    /// キーはアプリケーション内で組み立てるが、念のため保存先の外を指すキーは受け付けない
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        if key.is_empty() || key.starts_with('/') || key.split('/').any(|s| s == "..") {
+            return Err(AppError::BlobStorageError(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid blob key: {key}"),
+            )));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStorage for LocalBlobStorage {
+    async fn put(&self, key: &str, data: Vec<u8>) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(AppError::BlobStorageError)?;
+        }
+        // 書き込み途中のファイルを読まれないよう、一時ファイルに書いてから置き換える
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, data)
+            .await
+            .map_err(AppError::BlobStorageError)?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(AppError::BlobStorageError)
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::BlobStorageError(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(AppError::BlobStorageError(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_blob_storage() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join(format!("blob-{}", uuid::Uuid::new_v4()));
+        let storage = LocalBlobStorage::new(&StorageConfig { root: root.clone() });
+
+        assert!(storage.get("covers/a/original").await?.is_none());
+
+        storage.put("covers/a/original", vec![1, 2, 3]).await?;
+        storage.put("covers/a/original", vec![4, 5]).await?;
+        assert_eq!(storage.get("covers/a/original").await?, Some(vec![4, 5]));
+
+        storage.delete("covers/a/original").await?;
+        assert!(storage.get("covers/a/original").await?.is_none());
+        // 存在しないキーの削除はエラーにしない
+        storage.delete("covers/a/original").await?;
+
+        // 保存先の外を指すキーは受け付けない
+        assert!(storage.put("../escape", vec![]).await.is_err());
+        assert!(storage.get("/etc/passwd").await.is_err());
+
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+//! 書影の画像などのバイナリデータを保存するストレージ
+pub mod local;
+
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+/// キーを指定してバイナリデータを読み書きするストレージの抽象
+/// 保存先をローカルのファイルシステムからオブジェクトストレージなどに差し替えられるようにする
+#[async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// キーにデータを保存する。既に存在する場合は上書きする
+    async fn put(&self, key: &str, data: Vec<u8>) -> AppResult<()>;
+    /// キーのデータを取得する。存在しない場合はNoneを返す
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+    /// キーのデータを削除する。存在しない場合は何もしない
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
@@ -1,20 +1,46 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Multipart, Path, Query, State},
+    http::{
+        HeaderMap, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG},
+    },
+    response::{IntoResponse, Response},
 };
 use garde::Validate;
 
-use kernel::model::{book::event::DeleteBook, id::BookId};
+use kernel::model::{
+    book::{
+        CoverSize,
+        event::{
+            AcceptBookTransfer, DeclineBookTransfer, DeleteBook, DeleteBookCopy, ImportBooks,
+            PurgeBook, RemoveBookTag, RestoreBook, RevertBook, UploadBookCover,
+        },
+    },
+    id::{BookId, BookRevisionId, CopyId, UserId},
+};
 use registry::AppRegistry;
 use shared::error::{AppError, AppResult};
 
 use crate::{
     extractor::AuthorizedUser,
     model::book::{
-        BookListQuery, BookResponse, CreateBookRequest, PaginatedBookResponse, UpdateBookRequest,
-        UpdateBookRequestWithIds,
+        AddBookTagsRequest, AddBookTagsRequestWithIds, BookListQuery, BookListResponse,
+        BookResponse, BookTagQuery, BooksResponse, CreateBookCopyRequest,
+        CreateBookCopyRequestWithIds, CreateBookRequest, DeletedBookListQuery, MoveBookRequest,
+        MoveBookRequestWithIds, PaginatedDeletedBookResponse, PatchBookRequest,
+        PatchBookRequestWithIds, SetBookLoanPeriodRequest, SetBookLoanPeriodRequestWithIds,
+        UpdateBookRequest, UpdateBookRequestWithIds, book_etag, normalize_tag, parse_if_match,
+        parse_isbn,
+    },
+    model::book_copy_status::{
+        ChangeCopyStatusRequest, ChangeCopyStatusRequestWithIds, CopyStatusChangesResponse,
     },
+    model::book_cover::read_cover,
+    model::book_export::BookExportQuery,
+    model::book_import::{BookImportQuery, BookImportReportResponse, parse_book_csv},
+    model::book_revision::BookRevisionsResponse,
+    model::book_transfer::{BookTransferResponse, TransferBookRequest, TransferBookRequestWithIds},
 };
 
 /// 書籍を登録するハンドラ
@@ -29,7 +55,8 @@ use crate::{
             (status = 201, description = "書籍の登録に成功した場合"),
             (status = 400, description = "リクエストボディに不備があった場合"),
             (status = 401, description = "認証に失敗した場合"),
-            (status = 422, description = "リクエストした蔵書の登録に失敗した場合")
+            (status = 422, description = "リクエストした蔵書の登録に失敗した場合、またはタイトルと著者を省略したがISBNから書誌情報が見つからなかった場合"),
+            (status = 502, description = "書誌情報の取得に失敗した場合")
         )
     )
 )]
@@ -40,13 +67,68 @@ pub async fn register_book(
 ) -> AppResult<StatusCode> {
     req.validate()?;
 
+    // タイトルか著者が省略された場合は、ISBNから取得した書誌情報で補う
+    let metadata = if req.needs_metadata() {
+        registry
+            .book_metadata_provider()
+            .find_by_isbn(&parse_isbn(&req.isbn)?)
+            .await?
+    } else {
+        None
+    };
+
     registry
         .book_repository()
-        .create(req.into(), user.id())
+        .create(req.into_create_book(metadata)?, user.id())
         .await
         .map(|_| StatusCode::CREATED)
 }
 
+/// CSVから書籍を一括で登録するハンドラ（管理者のみ）
+/// 所有者のメールアドレスが空の行は、リクエストしたユーザーの所有とする
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/import",
+        request_body(content = String, content_type = "text/csv", description = "title, author, isbn, description, owner_email列を持つCSV"),
+        responses(
+            (status = 200, description = "一括登録を行った場合（行ごとの結果を返す）", body = BookImportReportResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者以外が実行した場合"),
+        ),
+        params(
+            ("dryRun" = Option<bool>, Query, description = "trueの場合は登録せずに結果だけを返す")
+        )
+    )
+)]
+pub async fn import_books(
+    user: AuthorizedUser,
+    Query(query): Query<BookImportQuery>,
+    State(registry): State<AppRegistry>,
+    body: String,
+) -> AppResult<Json<BookImportReportResponse>> {
+    // 管理者のみが一括登録できる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    let (rows, invalid) = parse_book_csv(body.as_bytes());
+    let results = registry
+        .book_repository()
+        .import(ImportBooks {
+            rows,
+            default_owner: Some(user.id()),
+            dry_run: query.dry_run,
+        })
+        .await?;
+
+    Ok(Json(BookImportReportResponse::new(
+        invalid.into_iter().chain(results),
+        query.dry_run,
+    )))
+}
+
 /// 書籍を取得するハンドラ
 #[cfg_attr(
     debug_assertions,
@@ -54,81 +136,489 @@ pub async fn register_book(
         get,
         path = "/api/v1/books",
         responses(
-            (status = 200, description = "書籍一覧を取得に成功した場合", body = PaginatedBookResponse),
+            (status = 200, description = "書籍一覧を取得に成功した場合（cursorを指定した場合はlimit, nextCursor, itemsを返す）", body = crate::model::book::PaginatedBookResponse),
             (status = 400, description = "指定されたクエリに不備があった場合"),
             (status = 401, description = "認証に失敗した場合"),
         ),
         params(
             ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定"),
-            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置")
+            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置"),
+            ("q" = Option<String>, Query, description = "タイトル・著者・説明・ISBNを対象とした検索キーワード"),
+            ("author" = Option<String>, Query, description = "著者名による絞り込み（部分一致）"),
+            ("owner" = Option<String>, Query, description = "所有者のユーザーIDによる絞り込み"),
+            ("location" = Option<String>, Query, description = "置き場所のIDによる絞り込み"),
+            ("availability" = Option<String>, Query, description = "貸出状態による絞り込み（available: 貸出可能、checked-out: 貸出中）"),
+            ("tag" = Option<Vec<String>>, Query, description = "タグによる絞り込み。tag=rust&tag=asyncのように複数指定した場合はすべてのタグが付いた書籍に絞り込む"),
+            ("sort" = Option<String>, Query, description = "並び替えのキー（title, author, created-at, updated-at, most-borrowed, rating）"),
+            ("order" = Option<String>, Query, description = "昇順・降順（asc, desc）。未指定の場合はキーに応じて決まる"),
+            ("cursor" = Option<String>, Query, description = "前のページで返されたnextCursor。指定した場合はカーソルによるページネーションを行い、空文字の場合は先頭から取得する")
         )
     )
 )]
 pub async fn show_book_list(
-    _user: AuthorizedUser,
-    Query(query): Query<BookListQuery>,
+    user: AuthorizedUser,
+    Query(mut query): Query<BookListQuery>,
+    // 同じキーを複数指定できるよう、タグはaxum_extraのQueryで受け取る
+    axum_extra::extract::Query(BookTagQuery { tag }): axum_extra::extract::Query<BookTagQuery>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<PaginatedBookResponse>> {
+) -> AppResult<Json<BookListResponse>> {
+    query.tag = tag;
     query.validate()?;
 
-    registry
-        .book_repository()
-        .find_all(query.into())
-        .await
-        .map(|books| books.into())
-        .map(Json)
+    // cursorが指定された場合はカーソルによるページネーションを行う
+    let mut books = if query.cursor.is_some() {
+        let (options, cursor) = query.into_options_with_cursor();
+        registry
+            .book_repository()
+            .find_all_by_cursor(options, cursor)
+            .await
+            .map(|books| BookListResponse::Cursor(books.into()))?
+    } else {
+        registry
+            .book_repository()
+            .find_all(query.into())
+            .await
+            .map(|books| BookListResponse::Offset(books.into()))?
+    };
+    mark_favorited(&registry, user.id(), books.items_mut()).await?;
+
+    Ok(Json(books))
+}
+
+/// 全書籍の目録を、所有者と貸出状況とともにエクスポートするハンドラ
+/// 全件をメモリに載せないよう、DBから取得した行を順にレスポンスとして送る
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/export",
+        responses(
+            (status = 200, description = "目録を蔵書ごとに1行ずつ返す（formatがjsonlの場合はapplication/x-ndjson）", body = crate::model::book_export::BookExportRecord, content_type = "text/csv"),
+            (status = 400, description = "指定された形式に不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("format" = Option<String>, Query, description = "エクスポートの形式（csv, jsonl）。未指定の場合はcsv")
+        )
+    )
+)]
+pub async fn export_books(
+    _user: AuthorizedUser,
+    Query(query): Query<BookExportQuery>,
+    State(registry): State<AppRegistry>,
+) -> Response {
+    let format = query.format;
+    let entries = registry.book_repository().stream_catalog();
+
+    (
+        [
+            (CONTENT_TYPE, format.content_type()),
+            (CONTENT_DISPOSITION, format.content_disposition()),
+        ],
+        format.into_body(entries),
+    )
+        .into_response()
 }
 
 /// IDに一致する書籍を取得するハンドラ
+/// 書誌情報の版をETagとして返し、更新や削除のIf-Matchに使えるようにする
 #[tracing::instrument(
-    skip(_user, registry),
+    skip(user, registry),
     fields(
-        user_id = %_user.user.id.to_string()
+        user_id = %user.user.id.to_string()
     )
 )]
 pub async fn show_book(
-    _user: AuthorizedUser,
+    user: AuthorizedUser,
     State(registry): State<AppRegistry>,
     Path(book_id): Path<BookId>,
-) -> AppResult<Json<BookResponse>> {
+) -> AppResult<Response> {
     tracing::info!("ログを追加");
-    registry
+    let book = registry
         .book_repository()
         .find_by_id(book_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("not found".into()))?;
+    let etag = book_etag(book.version);
+    let mut book = BookResponse::from(book);
+    mark_favorited(&registry, user.id(), std::slice::from_mut(&mut book)).await?;
+
+    Ok(([(ETAG, etag)], Json(book)).into_response())
+}
+
+/// ISBNに一致する書籍を貸出状況とともにすべて取得するハンドラ
+/// バーコードスキャナから読み取ったISBN-13をそのまま指定できる
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/isbn/{isbn}",
+        responses(
+            (status = 200, description = "書籍の取得に成功した場合（一致する書籍がない場合は空の一覧を返す）", body = BooksResponse),
+            (status = 400, description = "ISBNの形式が不正な場合"),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("isbn" = String, Path, description = "ISBN-10またはISBN-13（ハイフンの有無は問わない）")
+        )
+    )
+)]
+pub async fn show_books_by_isbn(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(isbn): Path<String>,
+) -> AppResult<Json<BooksResponse>> {
+    let isbn = parse_isbn(&isbn)?;
+
+    let mut books = registry
+        .book_repository()
+        .find_by_isbn(isbn)
         .await
-        .and_then(|bc| match bc {
-            Some(bc) => Ok(Json(bc.into())),
-            None => Err(AppError::EntityNotFound("not found".into())),
-        })
+        .map(BooksResponse::from)?;
+    mark_favorited(&registry, user.id(), &mut books.items).await?;
+
+    Ok(Json(books))
+}
+
+/// 現在のユーザーがお気に入りに登録している書籍のfavoritedをtrueにする
+async fn mark_favorited(
+    registry: &AppRegistry,
+    user_id: UserId,
+    books: &mut [BookResponse],
+) -> AppResult<()> {
+    let favorited = registry
+        .favorite_repository()
+        .find_favorited(user_id, books.iter().map(|b| b.id).collect())
+        .await?;
+    for book in books {
+        book.favorited = favorited.contains(&book.id);
+    }
+
+    Ok(())
 }
 
 /// 書籍を更新するハンドラ
+/// 所有者のほか、管理者は誰の書籍でも更新できる
+/// If-Matchが指定された場合、書籍が取得時から変更されていれば412を返す
 pub async fn update_book(
     user: AuthorizedUser,
     State(registry): State<AppRegistry>,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
     Json(req): Json<UpdateBookRequest>,
 ) -> AppResult<StatusCode> {
     req.validate()?;
 
-    let update_book = UpdateBookRequestWithIds::new(book_id, user.id(), req);
+    let expected_version = parse_if_match(&headers)?;
+    let update_book =
+        UpdateBookRequestWithIds::new(book_id, user.id(), user.is_admin(), expected_version, req);
+
+    registry
+        .book_repository()
+        .update(update_book.try_into()?)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍を部分更新するハンドラ
+/// JSON Merge Patch形式で、指定された項目のみを更新する
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        patch,
+        path = "/api/v1/books/{book_id}",
+        request_body(content = PatchBookRequest, content_type = "application/merge-patch+json"),
+        responses(
+            (status = 200, description = "書籍の更新に成功した場合"),
+            (status = 400, description = "指定された項目の値が不正な場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+            (status = 412, description = "If-Matchで指定した版から書籍が変更されている場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn patch_book(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    headers: HeaderMap,
+    Json(req): Json<PatchBookRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    let expected_version = parse_if_match(&headers)?;
+    let patch_book =
+        PatchBookRequestWithIds::new(book_id, user.id(), user.is_admin(), expected_version, req);
+
+    registry
+        .book_repository()
+        .patch(patch_book.try_into()?)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍の変更履歴を新しい順に取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/revisions",
+        responses(
+            (status = 200, description = "変更履歴の取得に成功した場合", body = BookRevisionsResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn show_book_revisions(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<Json<BookRevisionsResponse>> {
+    registry
+        .book_repository()
+        .find_revisions(book_id)
+        .await
+        .map(BookRevisionsResponse::from)
+        .map(Json)
+}
+
+/// 書籍の内容を、指定した変更履歴の時点（変更後の値）に戻すハンドラ
+/// 戻せるのは書籍の所有者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/revisions/{revision_id}/revert",
+        responses(
+            (status = 200, description = "書籍の内容を指定した変更履歴の時点に戻した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍や変更履歴が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("revision_id" = BookRevisionId, Path, description = "変更履歴のID")
+        )
+    )
+)]
+pub async fn revert_book_revision(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, revision_id)): Path<(BookId, BookRevisionId)>,
+) -> AppResult<StatusCode> {
+    let revert_book = RevertBook {
+        book_id,
+        revision_id,
+        requested_user: user.id(),
+        requested_by_admin: user.is_admin(),
+    };
+
+    registry
+        .book_repository()
+        .revert(revert_book)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍の置き場所を移すハンドラ
+/// 移せるのは書籍の所有者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/location",
+        request_body = MoveBookRequest,
+        responses(
+            (status = 200, description = "置き場所を移した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍や置き場所が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn move_book(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Json(req): Json<MoveBookRequest>,
+) -> AppResult<StatusCode> {
+    let move_book = MoveBookRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+
+    registry
+        .book_repository()
+        .move_to(move_book.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍の貸出期間を変えるハンドラ
+/// 変えられるのは書籍の所有者か管理者のみ。以降の貸出の返却期限に反映される
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/loan-period",
+        request_body = SetBookLoanPeriodRequest,
+        responses(
+            (status = 200, description = "貸出期間を変えた場合"),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn set_book_loan_period(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Json(req): Json<SetBookLoanPeriodRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    let set_loan_period =
+        SetBookLoanPeriodRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+
+    registry
+        .book_repository()
+        .set_loan_period(set_loan_period.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍を他のユーザーに譲渡するハンドラ
+/// 譲渡できるのは書籍の所有者か管理者のみ
+/// 承認を求める場合は、譲渡先のユーザーが承認するまで所有者は変わらない
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/transfer",
+        request_body = TransferBookRequest,
+        responses(
+            (status = 200, description = "書籍の所有者を変更した場合"),
+            (status = 202, description = "譲渡先のユーザーの承認待ちとした場合", body = BookTransferResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍や譲渡先のユーザーが存在しない場合"),
+            (status = 422, description = "譲渡先のユーザーがすでに所有者である場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn transfer_book(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Json(req): Json<TransferBookRequest>,
+) -> AppResult<Response> {
+    let transfer = TransferBookRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+
+    let pending = registry.book_repository().transfer(transfer.into()).await?;
+
+    Ok(match pending {
+        Some(transfer) => (
+            StatusCode::ACCEPTED,
+            Json(BookTransferResponse::from(transfer)),
+        )
+            .into_response(),
+        None => StatusCode::OK.into_response(),
+    })
+}
+
+/// ログイン中のユーザー宛ての書籍の譲渡を承認するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/transfer/accept",
+        responses(
+            (status = 200, description = "譲渡を承認し、所有者が変わった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "ログイン中のユーザー宛ての譲渡が存在しない場合"),
+            (status = 422, description = "譲渡の依頼後に書籍が削除されたか所有者が変わった場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn accept_book_transfer(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
+    registry
+        .book_repository()
+        .accept_transfer(AcceptBookTransfer {
+            book_id,
+            requested_user: user.id(),
+        })
+        .await
+        .map(|_| StatusCode::OK)
+}
 
+/// ログイン中のユーザー宛ての書籍の譲渡を拒否するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/transfer/decline",
+        responses(
+            (status = 200, description = "譲渡を拒否した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "ログイン中のユーザー宛ての譲渡が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn decline_book_transfer(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
     registry
         .book_repository()
-        .update(update_book.into())
+        .decline_transfer(DeclineBookTransfer {
+            book_id,
+            requested_user: user.id(),
+        })
         .await
         .map(|_| StatusCode::OK)
 }
 
-/// 書籍を削除するハンドラ
+/// 書籍を削除済みにするハンドラ
+/// 所有者のほか、管理者は誰の書籍でも削除できる。貸出中の蔵書がある書籍は削除できない
+/// If-Matchが指定された場合、書籍が取得時から変更されていれば412を返す
 pub async fn delete_book(
     user: AuthorizedUser,
     State(registry): State<AppRegistry>,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
 ) -> AppResult<StatusCode> {
     let delete_book = DeleteBook {
         book_id,
         requested_user: user.id(),
+        requested_by_admin: user.is_admin(),
+        expected_version: parse_if_match(&headers)?,
     };
 
     registry
@@ -137,3 +627,434 @@ pub async fn delete_book(
         .await
         .map(|_| StatusCode::OK)
 }
+
+/// 削除済みの書籍を一覧で取得するハンドラ（管理者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/deleted",
+        responses(
+            (status = 200, description = "削除済みの書籍の取得に成功した場合", body = PaginatedDeletedBookResponse),
+            (status = 400, description = "指定されたクエリに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+        ),
+        params(
+            ("limit" = i64, Query, description = "一度に取得する書籍数の上限値の指定"),
+            ("offset" = i64, Query, description = "取得対象とする書籍一覧の開始位置")
+        )
+    )
+)]
+pub async fn show_deleted_book_list(
+    user: AuthorizedUser,
+    Query(query): Query<DeletedBookListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedDeletedBookResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+    query.validate()?;
+
+    registry
+        .book_repository()
+        .find_deleted(query.into())
+        .await
+        .map(PaginatedDeletedBookResponse::from)
+        .map(Json)
+}
+
+/// 削除済みの書籍を復元するハンドラ（管理者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/restore",
+        responses(
+            (status = 200, description = "書籍を復元した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 404, description = "削除済みの書籍が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn restore_book(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    registry
+        .book_repository()
+        .restore(RestoreBook { book_id })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 削除済みの書籍を完全に削除するハンドラ（管理者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/purge",
+        responses(
+            (status = 200, description = "書籍を完全に削除した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 404, description = "削除済みの書籍が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn purge_book(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    registry
+        .book_repository()
+        .purge(PurgeBook { book_id })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍に蔵書を追加するハンドラ
+/// 追加できるのは書籍の所有者か管理者のみ
+/// リクエストボディを省略した場合は、連番のバーコードで追加する
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/copies",
+        request_body(content = Option<CreateBookCopyRequest>, description = "省略した場合は連番のバーコードで追加する"),
+        responses(
+            (status = 201, description = "蔵書を追加した場合"),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+            (status = 422, description = "指定したバーコードがすでに使われている場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn add_book_copy(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    req: Option<Json<CreateBookCopyRequest>>,
+) -> AppResult<StatusCode> {
+    let Json(req) = req.unwrap_or_default();
+    req.validate()?;
+
+    let create_copy = CreateBookCopyRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+
+    registry
+        .book_repository()
+        .add_copy(create_copy.into())
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+/// 書籍から蔵書を取り除くハンドラ
+/// 取り除けるのは書籍の所有者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}",
+        responses(
+            (status = 200, description = "蔵書を取り除いた場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍や蔵書が存在しない場合"),
+            (status = 422, description = "蔵書が貸出中の場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("copy_id" = CopyId, Path, description = "蔵書ID")
+        )
+    )
+)]
+pub async fn delete_book_copy(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+) -> AppResult<StatusCode> {
+    let delete_copy = DeleteBookCopy {
+        book_id,
+        copy_id,
+        requested_user: user.id(),
+        requested_by_admin: user.is_admin(),
+    };
+
+    registry
+        .book_repository()
+        .delete_copy(delete_copy)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 蔵書の状態を変えるハンドラ
+/// 所有者と管理者はすべての操作を、借りているユーザーは紛失・破損の報告のみを行える
+/// 借りているユーザーが紛失を報告した場合は、貸出を紛失として終える
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}/status-changes",
+        request_body = ChangeCopyStatusRequest,
+        responses(
+            (status = 200, description = "蔵書の状態を変えた場合"),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "操作を行う権限がない場合"),
+            (status = 404, description = "書籍や蔵書が存在しない場合"),
+            (status = 422, description = "蔵書の現在の状態や貸出状況では行えない操作の場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("copy_id" = CopyId, Path, description = "蔵書ID")
+        )
+    )
+)]
+pub async fn change_copy_status(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    Json(req): Json<ChangeCopyStatusRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    let change =
+        ChangeCopyStatusRequestWithIds::new(book_id, copy_id, user.id(), user.is_admin(), req);
+
+    registry
+        .book_repository()
+        .change_copy_status(change.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 蔵書の状態の変更履歴を、古い順に取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/copies/{copy_id}/status-changes",
+        responses(
+            (status = 200, description = "変更履歴の取得に成功した場合", body = CopyStatusChangesResponse),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("copy_id" = CopyId, Path, description = "蔵書ID")
+        )
+    )
+)]
+pub async fn show_copy_status_history(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+) -> AppResult<Json<CopyStatusChangesResponse>> {
+    registry
+        .book_repository()
+        .find_copy_status_history(book_id, copy_id)
+        .await
+        .map(CopyStatusChangesResponse::from)
+        .map(Json)
+}
+
+/// 書籍にタグを付けるハンドラ
+/// 付けられるのは書籍の所有者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/tags",
+        request_body = AddBookTagsRequest,
+        responses(
+            (status = 200, description = "タグを付けた場合"),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn add_book_tags(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Json(req): Json<AddBookTagsRequest>,
+) -> AppResult<StatusCode> {
+    req.validate()?;
+
+    let add_tags = AddBookTagsRequestWithIds::new(book_id, user.id(), user.is_admin(), req);
+
+    registry
+        .book_repository()
+        .add_tags(add_tags.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍からタグを外すハンドラ
+/// 外せるのは書籍の所有者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/tags/{tag}",
+        responses(
+            (status = 200, description = "タグを外した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない、または書籍にタグが付いていない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("tag" = String, Path, description = "タグ名")
+        )
+    )
+)]
+pub async fn remove_book_tag(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, tag)): Path<(BookId, String)>,
+) -> AppResult<StatusCode> {
+    let remove_tag = RemoveBookTag {
+        book_id,
+        tag: normalize_tag(&tag),
+        requested_user: user.id(),
+        requested_by_admin: user.is_admin(),
+    };
+
+    registry
+        .book_repository()
+        .remove_tag(remove_tag)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍の書影をmultipartのcoverフィールドで受け取って登録するハンドラ
+/// 登録できるのは書籍の所有者か管理者のみ。登録済みの場合は置き換える
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/cover",
+        request_body(content = Vec<u8>, content_type = "multipart/form-data", description = "coverフィールドにJPEGまたはPNGの画像を指定する"),
+        responses(
+            (status = 200, description = "書影を登録した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "所有者でも管理者でもない場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+            (status = 413, description = "画像のサイズが上限を超えている場合"),
+            (status = 415, description = "画像の形式がJPEGでもPNGでもない場合"),
+            (status = 422, description = "coverフィールドがない、または画像として読み込めない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn upload_book_cover(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    mut multipart: Multipart,
+) -> AppResult<StatusCode> {
+    let (image_type, data) = read_cover(&mut multipart).await?;
+
+    registry
+        .book_repository()
+        .upload_cover(UploadBookCover {
+            book_id,
+            image_type,
+            data,
+            requested_user: user.id(),
+            requested_by_admin: user.is_admin(),
+        })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍の書影を返すハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/cover",
+        responses(
+            (status = 200, description = "書影の画像を返す", body = Vec<u8>, content_type = "image/*"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "書籍や書影が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn show_book_cover(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<Response> {
+    find_cover(registry, book_id, CoverSize::Original).await
+}
+
+/// 書籍の書影のサムネイルを返すハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/cover/thumbnail",
+        responses(
+            (status = 200, description = "書影のサムネイルの画像を返す", body = Vec<u8>, content_type = "image/*"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "書籍や書影が存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn show_book_cover_thumbnail(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<Response> {
+    find_cover(registry, book_id, CoverSize::Thumbnail).await
+}
+
+async fn find_cover(
+    registry: AppRegistry,
+    book_id: BookId,
+    size: CoverSize,
+) -> AppResult<Response> {
+    let cover = registry
+        .book_repository()
+        .find_cover(book_id, size)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("the cover was not found".into()))?;
+
+    Ok(([(CONTENT_TYPE, cover.image_type.as_ref())], cover.data).into_response())
+}
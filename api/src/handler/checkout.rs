@@ -1,25 +1,47 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
+use garde::Validate;
 
 use kernel::model::{
-    checkout::event::{CreateCheckout, UpdateReturned},
-    id::{BookId, CheckoutId},
+    checkout::event::{CreateCheckout, RenewCheckout, UpdateReturned},
+    id::{BookId, CheckoutId, CopyId},
 };
 use registry::AppRegistry;
 use shared::error::AppResult;
 
-use crate::{extractor::AuthorizedUser, model::checkout::CheckoutsResponse};
+use crate::{
+    extractor::AuthorizedUser,
+    model::checkout::{
+        CheckoutListQuery, CheckoutListResponse, CheckoutResponse, CheckoutsResponse,
+    },
+};
 
-/// 蔵書の貸出を行うハンドラ
+/// 書籍の貸出可能な蔵書のいずれかの貸出を行うハンドラ
 pub async fn checkout_book(
     user: AuthorizedUser,
     Path(book_id): Path<BookId>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<StatusCode> {
-    let create_checkout_history = CreateCheckout::new(book_id, user.id(), chrono::Utc::now());
+    let create_checkout_history = CreateCheckout::new(book_id, None, user.id(), chrono::Utc::now());
+
+    registry
+        .checkout_repository()
+        .create(create_checkout_history)
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+/// 指定した蔵書の貸出を行うハンドラ
+pub async fn checkout_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let create_checkout_history =
+        CreateCheckout::new(book_id, Some(copy_id), user.id(), chrono::Utc::now());
 
     registry
         .checkout_repository()
@@ -43,16 +65,48 @@ pub async fn return_book(
         .map(|_| StatusCode::OK)
 }
 
+/// 蔵書の貸出を延長するハンドラ
+/// 延長できるのは借りたユーザーのみで、延長できる回数には上限がある
+pub async fn renew_checkout(
+    user: AuthorizedUser,
+    Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<CheckoutResponse>> {
+    let renew_checkout = RenewCheckout::new(checkout_id, book_id, user.id(), chrono::Utc::now());
+
+    registry
+        .checkout_repository()
+        .renew(renew_checkout)
+        .await
+        .map(CheckoutResponse::from)
+        .map(Json)
+}
+
 /// 貸出中の蔵書一覧を取得するハンドラ
+/// cursorが指定された場合はカーソルによるページネーションを行う
+/// overdue=trueが指定された場合は、返却期限を過ぎた貸出のみを返す
 pub async fn show_checked_out_list(
     _user: AuthorizedUser,
+    Query(query): Query<CheckoutListQuery>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<CheckoutsResponse>> {
+) -> AppResult<Json<CheckoutListResponse>> {
+    query.validate()?;
+
+    let overdue = query.overdue;
+    if query.cursor.is_some() {
+        return registry
+            .checkout_repository()
+            .find_unreturned_all_by_cursor(query.into(), overdue)
+            .await
+            .map(|checkouts| CheckoutListResponse::Cursor(checkouts.into()))
+            .map(Json);
+    }
+
     registry
         .checkout_repository()
-        .find_unreturned_all()
+        .find_unreturned_all(overdue)
         .await
-        .map(|checkouts| CheckoutsResponse::from(checkouts))
+        .map(|checkouts| CheckoutListResponse::All(checkouts.into()))
         .map(Json)
 }
 
@@ -66,6 +120,6 @@ pub async fn checkout_history(
         .checkout_repository()
         .find_history_by_book_id(book_id)
         .await
-        .map(|checkouts| CheckoutsResponse::from(checkouts))
+        .map(CheckoutsResponse::from)
         .map(Json)
 }
@@ -0,0 +1,101 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use kernel::model::{
+    hold::event::{CreateHold, DeleteHold},
+    id::{BookId, HoldId},
+};
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::hold::{HoldResponse, HoldsResponse},
+};
+
+/// 貸出中の書籍を予約するハンドラ
+/// 蔵書が返却されると、予約した順に受け取りの期限つきで取り置かれる
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/holds",
+        responses(
+            (status = 201, description = "予約に成功した場合", body = HoldResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+            (status = 422, description = "書籍を借りているか予約済みの場合、または今すぐ借りられる場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn place_hold(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<(StatusCode, Json<HoldResponse>)> {
+    let hold = registry
+        .hold_repository()
+        .create(CreateHold::new(book_id, user.id(), chrono::Utc::now()))
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(hold.into())))
+}
+
+/// ログイン中のユーザーの予約を、順番とともに取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/users/me/holds",
+        responses(
+            (status = 200, description = "予約の一覧の取得に成功した場合", body = HoldsResponse),
+            (status = 401, description = "認証に失敗した場合"),
+        )
+    )
+)]
+pub async fn get_holds(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<HoldsResponse>> {
+    registry
+        .hold_repository()
+        .find_by_user_id(user.id())
+        .await
+        .map(HoldsResponse::from)
+        .map(Json)
+}
+
+/// 自分の予約を取り消すハンドラ
+/// 取り置き中の予約を取り消した場合は、次の予約者に取り置かれる
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/users/me/holds/{hold_id}",
+        responses(
+            (status = 200, description = "予約を取り消した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "自分の予約が存在しない場合"),
+        ),
+        params(
+            ("hold_id" = HoldId, Path, description = "予約のID")
+        )
+    )
+)]
+pub async fn cancel_hold(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(hold_id): Path<HoldId>,
+) -> AppResult<StatusCode> {
+    registry
+        .hold_repository()
+        .delete(DeleteHold::new(hold_id, user.id(), chrono::Utc::now()))
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -0,0 +1,152 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use garde::Validate;
+
+use kernel::model::{id::LocationId, location::event::DeleteLocation};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::location::{
+        CreateLocationRequest, LocationResponse, LocationsResponse, UpdateLocationRequest,
+        UpdateLocationRequestWithId,
+    },
+};
+
+/// すべての置き場所を、拠点・部屋・棚の名前順に取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/locations",
+        responses(
+            (status = 200, description = "置き場所の一覧の取得に成功した場合", body = LocationsResponse),
+            (status = 401, description = "認証に失敗した場合"),
+        )
+    )
+)]
+pub async fn list_locations(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<LocationsResponse>> {
+    registry
+        .location_repository()
+        .find_all()
+        .await
+        .map(LocationsResponse::from)
+        .map(Json)
+}
+
+/// 置き場所を登録するハンドラ（管理者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/locations",
+        request_body = CreateLocationRequest,
+        responses(
+            (status = 201, description = "置き場所の登録に成功した場合", body = LocationResponse),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 422, description = "同じ拠点・部屋・棚の置き場所がすでにある場合"),
+        )
+    )
+)]
+pub async fn register_location(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateLocationRequest>,
+) -> AppResult<(StatusCode, Json<LocationResponse>)> {
+    // 管理者のみが置き場所を登録できる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    req.validate()?;
+
+    let location = registry.location_repository().create(req.into()).await?;
+
+    Ok((StatusCode::CREATED, Json(location.into())))
+}
+
+/// 置き場所の名前を変更するハンドラ（管理者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/locations/{location_id}",
+        request_body = UpdateLocationRequest,
+        responses(
+            (status = 200, description = "置き場所の更新に成功した場合", body = LocationResponse),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 404, description = "置き場所が存在しない場合"),
+            (status = 422, description = "同じ拠点・部屋・棚の置き場所がすでにある場合"),
+        ),
+        params(
+            ("location_id" = LocationId, Path, description = "置き場所のID")
+        )
+    )
+)]
+pub async fn update_location(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(location_id): Path<LocationId>,
+    Json(req): Json<UpdateLocationRequest>,
+) -> AppResult<Json<LocationResponse>> {
+    // 管理者のみが置き場所を変更できる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    req.validate()?;
+
+    registry
+        .location_repository()
+        .update(UpdateLocationRequestWithId::new(location_id, req).into())
+        .await
+        .map(LocationResponse::from)
+        .map(Json)
+}
+
+/// 置き場所を削除するハンドラ（管理者のみ）
+/// 書籍が置かれている場所は、書籍を移してからでないと削除できない
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/locations/{location_id}",
+        responses(
+            (status = 200, description = "置き場所の削除に成功した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 404, description = "置き場所が存在しない場合"),
+            (status = 422, description = "置き場所に書籍が置かれている場合"),
+        ),
+        params(
+            ("location_id" = LocationId, Path, description = "置き場所のID")
+        )
+    )
+)]
+pub async fn delete_location(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(location_id): Path<LocationId>,
+) -> AppResult<StatusCode> {
+    // 管理者のみが置き場所を削除できる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    registry
+        .location_repository()
+        .delete(DeleteLocation::new(location_id))
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -2,4 +2,9 @@ pub mod auth;
 pub mod book;
 pub mod checkout;
 pub mod health;
+pub mod hold;
+pub mod location;
+pub mod purchase_request;
+pub mod review;
+pub mod tag;
 pub mod user;
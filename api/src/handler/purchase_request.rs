@@ -0,0 +1,228 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+
+use kernel::model::{
+    id::PurchaseRequestId,
+    purchase_request::event::{UnvotePurchaseRequest, VotePurchaseRequest},
+};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::{
+        book::parse_isbn,
+        purchase_request::{
+            ChangePurchaseRequestStatusRequest, ChangePurchaseRequestStatusRequestWithIds,
+            CreatePurchaseRequestRequest, PaginatedPurchaseRequestResponse,
+            PurchaseRequestListQuery, PurchaseRequestResponse,
+        },
+    },
+};
+
+/// 書籍の購入をリクエストするハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/purchase-requests",
+        request_body = CreatePurchaseRequestRequest,
+        responses(
+            (status = 201, description = "購入リクエストの登録に成功した場合", body = PurchaseRequestResponse),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 422, description = "同じISBNの書籍か購入リクエストがすでにある場合、またはタイトルと著者を省略したがISBNから書誌情報が見つからなかった場合"),
+            (status = 502, description = "書誌情報の取得に失敗した場合")
+        )
+    )
+)]
+pub async fn register_purchase_request(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreatePurchaseRequestRequest>,
+) -> AppResult<(StatusCode, Json<PurchaseRequestResponse>)> {
+    req.validate()?;
+
+    // タイトルか著者が省略された場合は、ISBNから取得した書誌情報で補う
+    let metadata = if req.needs_metadata() {
+        registry
+            .book_metadata_provider()
+            .find_by_isbn(&parse_isbn(&req.isbn)?)
+            .await?
+    } else {
+        None
+    };
+
+    let purchase_request = registry
+        .purchase_request_repository()
+        .create(req.into_create_purchase_request(user.id(), metadata)?)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(purchase_request.into())))
+}
+
+/// 購入リクエストを、賛成票の多い順に取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/purchase-requests",
+        responses(
+            (status = 200, description = "購入リクエストの一覧の取得に成功した場合", body = PaginatedPurchaseRequestResponse),
+            (status = 400, description = "クエリに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("status" = Option<String>, Query, description = "状態による絞り込み（open, approved, ordered, received, rejected）"),
+            ("limit" = i64, Query, description = "一度に取得する購入リクエスト数の上限値の指定"),
+            ("offset" = i64, Query, description = "取得対象とする購入リクエスト一覧の開始位置")
+        )
+    )
+)]
+pub async fn show_purchase_request_list(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Query(query): Query<PurchaseRequestListQuery>,
+) -> AppResult<Json<PaginatedPurchaseRequestResponse>> {
+    query.validate()?;
+
+    registry
+        .purchase_request_repository()
+        .find_all(query.into_options(user.id()))
+        .await
+        .map(PaginatedPurchaseRequestResponse::from)
+        .map(Json)
+}
+
+/// 購入リクエストを取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/purchase-requests/{purchase_request_id}",
+        responses(
+            (status = 200, description = "購入リクエストの取得に成功した場合", body = PurchaseRequestResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "購入リクエストが存在しない場合"),
+        ),
+        params(
+            ("purchase_request_id" = PurchaseRequestId, Path, description = "購入リクエストのID")
+        )
+    )
+)]
+pub async fn show_purchase_request(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(purchase_request_id): Path<PurchaseRequestId>,
+) -> AppResult<Json<PurchaseRequestResponse>> {
+    let purchase_request = registry
+        .purchase_request_repository()
+        .find_by_id(purchase_request_id, user.id())
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified purchase request not found".into()))?;
+
+    Ok(Json(purchase_request.into()))
+}
+
+/// 購入リクエストに賛成票を入れるハンドラ
+/// 自分のリクエストや、発注済み・受領済み・却下されたリクエストには入れられない
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/purchase-requests/{purchase_request_id}/vote",
+        responses(
+            (status = 200, description = "賛成票を入れた場合、または入れ済みの場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "購入リクエストが存在しない場合"),
+            (status = 422, description = "自分のリクエストか、賛成票を受け付けていないリクエストの場合"),
+        ),
+        params(
+            ("purchase_request_id" = PurchaseRequestId, Path, description = "購入リクエストのID")
+        )
+    )
+)]
+pub async fn vote_purchase_request(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(purchase_request_id): Path<PurchaseRequestId>,
+) -> AppResult<StatusCode> {
+    registry
+        .purchase_request_repository()
+        .vote(VotePurchaseRequest::new(purchase_request_id, user.id()))
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 購入リクエストへの賛成票を取り消すハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/purchase-requests/{purchase_request_id}/vote",
+        responses(
+            (status = 200, description = "賛成票を取り消した場合、または入れていない場合"),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("purchase_request_id" = PurchaseRequestId, Path, description = "購入リクエストのID")
+        )
+    )
+)]
+pub async fn unvote_purchase_request(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(purchase_request_id): Path<PurchaseRequestId>,
+) -> AppResult<StatusCode> {
+    registry
+        .purchase_request_repository()
+        .unvote(UnvotePurchaseRequest::new(purchase_request_id, user.id()))
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 購入リクエストの状態を変えるハンドラ（管理者のみ）
+/// 受領済みにすると、リクエストの内容から書籍を登録する。登録した書籍の所有者は操作した管理者とする
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/purchase-requests/{purchase_request_id}/status",
+        request_body = ChangePurchaseRequestStatusRequest,
+        responses(
+            (status = 200, description = "状態の変更に成功した場合", body = PurchaseRequestResponse),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "管理者でない場合"),
+            (status = 404, description = "購入リクエストが存在しない場合"),
+            (status = 422, description = "現在の状態から指定した状態に変えられない場合"),
+        ),
+        params(
+            ("purchase_request_id" = PurchaseRequestId, Path, description = "購入リクエストのID")
+        )
+    )
+)]
+pub async fn change_purchase_request_status(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(purchase_request_id): Path<PurchaseRequestId>,
+    Json(req): Json<ChangePurchaseRequestStatusRequest>,
+) -> AppResult<Json<PurchaseRequestResponse>> {
+    // 管理者のみが購入リクエストの状態を変えられる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    registry
+        .purchase_request_repository()
+        .change_status(
+            ChangePurchaseRequestStatusRequestWithIds::new(purchase_request_id, user.id(), req)
+                .into(),
+        )
+        .await
+        .map(PurchaseRequestResponse::from)
+        .map(Json)
+}
@@ -0,0 +1,164 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use garde::Validate;
+
+use kernel::model::{
+    id::{BookId, ReviewId},
+    review::event::DeleteReview,
+};
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::review::{
+        CreateReviewRequest, CreateReviewRequestWithIds, PaginatedReviewResponse, ReviewListQuery,
+        ReviewResponse, UpdateReviewRequest, UpdateReviewRequestWithIds,
+    },
+};
+
+/// 書籍にレビューを投稿するハンドラ
+/// レビューは1人1冊につき1件までとする
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        post,
+        path = "/api/v1/books/{book_id}/reviews",
+        request_body = CreateReviewRequest,
+        responses(
+            (status = 201, description = "レビューの投稿に成功した場合", body = ReviewResponse),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 404, description = "書籍が存在しない場合"),
+            (status = 422, description = "すでにレビューを投稿している場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID")
+        )
+    )
+)]
+pub async fn register_review(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Json(req): Json<CreateReviewRequest>,
+) -> AppResult<(StatusCode, Json<ReviewResponse>)> {
+    req.validate()?;
+
+    let review = registry
+        .review_repository()
+        .create(CreateReviewRequestWithIds::new(book_id, user.id(), req).into())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(review.into())))
+}
+
+/// 書籍のレビューを、新しい順に取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/books/{book_id}/reviews",
+        responses(
+            (status = 200, description = "レビューの一覧の取得に成功した場合", body = PaginatedReviewResponse),
+            (status = 400, description = "クエリに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("limit" = i64, Query, description = "一度に取得するレビュー数の上限値の指定"),
+            ("offset" = i64, Query, description = "取得対象とするレビュー一覧の開始位置")
+        )
+    )
+)]
+pub async fn show_review_list(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<ReviewListQuery>,
+) -> AppResult<Json<PaginatedReviewResponse>> {
+    query.validate()?;
+
+    registry
+        .review_repository()
+        .find_by_book(book_id, query.into())
+        .await
+        .map(PaginatedReviewResponse::from)
+        .map(Json)
+}
+
+/// レビューを変更するハンドラ
+/// 変更できるのは投稿者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        put,
+        path = "/api/v1/books/{book_id}/reviews/{review_id}",
+        request_body = UpdateReviewRequest,
+        responses(
+            (status = 200, description = "レビューの変更に成功した場合", body = ReviewResponse),
+            (status = 400, description = "リクエストボディに不備があった場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "投稿者でも管理者でもない場合"),
+            (status = 404, description = "レビューが存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("review_id" = ReviewId, Path, description = "レビューID")
+        )
+    )
+)]
+pub async fn update_review(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+    Json(req): Json<UpdateReviewRequest>,
+) -> AppResult<Json<ReviewResponse>> {
+    req.validate()?;
+
+    let update_review =
+        UpdateReviewRequestWithIds::new(book_id, review_id, user.id(), user.is_admin(), req);
+
+    registry
+        .review_repository()
+        .update(update_review.into())
+        .await
+        .map(ReviewResponse::from)
+        .map(Json)
+}
+
+/// レビューを削除するハンドラ
+/// 削除できるのは投稿者か管理者のみ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        delete,
+        path = "/api/v1/books/{book_id}/reviews/{review_id}",
+        responses(
+            (status = 200, description = "レビューの削除に成功した場合"),
+            (status = 401, description = "認証に失敗した場合"),
+            (status = 403, description = "投稿者でも管理者でもない場合"),
+            (status = 404, description = "レビューが存在しない場合"),
+        ),
+        params(
+            ("book_id" = BookId, Path, description = "書籍ID"),
+            ("review_id" = ReviewId, Path, description = "レビューID")
+        )
+    )
+)]
+pub async fn delete_review(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+) -> AppResult<StatusCode> {
+    let delete_review = DeleteReview::new(book_id, review_id, user.id(), user.is_admin());
+
+    registry
+        .review_repository()
+        .delete(delete_review)
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -0,0 +1,30 @@
+use axum::{Json, extract::State};
+
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{extractor::AuthorizedUser, model::tag::TagsResponse};
+
+/// 書籍に付いているすべてのタグを、付いている書籍の数とともに取得するハンドラ
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(
+        get,
+        path = "/api/v1/tags",
+        responses(
+            (status = 200, description = "タグ一覧の取得に成功した場合", body = TagsResponse),
+            (status = 401, description = "認証に失敗した場合"),
+        )
+    )
+)]
+pub async fn list_tags(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<TagsResponse>> {
+    registry
+        .book_repository()
+        .find_all_tags()
+        .await
+        .map(TagsResponse::from)
+        .map(Json)
+}
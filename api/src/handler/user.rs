@@ -1,20 +1,38 @@
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
 use garde::Validate;
 
-use kernel::model::{id::UserId, user::event::DeleteUser};
+use kernel::model::{
+    id::{BookId, NotificationId, UserId, WishlistItemId},
+    notification::event::MarkNotificationRead,
+    user::event::{AddFavoriteBook, DeleteUser, RemoveFavoriteBook},
+    wishlist::event::DeleteWishlistItem,
+};
 use registry::AppRegistry;
 use shared::error::{AppError, AppResult};
 
 use crate::{
     extractor::AuthorizedUser,
+    model::book::{FavoriteBookListQuery, PaginatedBookResponse},
+    model::book_admin_action::BookAdminActionsResponse,
+    model::book_transfer::{
+        BookTransfersResponse, TransferAllBooksRequest, TransferAllBooksRequestWithIds,
+        TransferAllBooksResponse,
+    },
     model::checkout::CheckoutsResponse,
+    model::list::CursorListQuery,
+    model::notification::NotificationsResponse,
     model::user::{
-        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserPasswordRequestWithUserId,
-        UpdateUserRoleRequest, UpdateUserRoleRequestWithUserId, UserResponse, UsersResponse,
+        CreateUserRequest, PatchUserRequest, PatchUserRequestWithUserId, UpdateUserPasswordRequest,
+        UpdateUserPasswordRequestWithUserId, UpdateUserRoleRequest,
+        UpdateUserRoleRequestWithUserId, UserListResponse, UserResponse, UsersResponse,
+    },
+    model::wishlist::{
+        CreateWishlistItemRequest, CreateWishlistItemRequestWithUserId, WishlistItemResponse,
+        WishlistItemsResponse,
     },
 };
 
@@ -37,10 +55,23 @@ pub async fn register_user(
 }
 
 /// ユーザーを全件取得するハンドラ
+/// cursorが指定された場合はカーソルによるページネーションを行う
 pub async fn list_users(
     _user: AuthorizedUser,
+    Query(query): Query<CursorListQuery>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<UsersResponse>> {
+) -> AppResult<Json<UserListResponse>> {
+    query.validate()?;
+
+    if query.cursor.is_some() {
+        return registry
+            .user_repository()
+            .find_all_by_cursor(query.into())
+            .await
+            .map(|users| UserListResponse::Cursor(users.into()))
+            .map(Json);
+    }
+
     let users = registry
         .user_repository()
         .find_all()
@@ -49,10 +80,11 @@ pub async fn list_users(
         .map(UserResponse::from)
         .collect();
 
-    Ok(Json(UsersResponse { users }))
+    Ok(Json(UserListResponse::All(UsersResponse { users })))
 }
 
 /// ユーザーを削除するハンドラ（管理者のみ）
+/// 書籍を所有しているユーザーは、先に書籍を譲渡しないと削除できない
 pub async fn delete_user(
     user: AuthorizedUser,
     State(registry): State<AppRegistry>,
@@ -96,6 +128,23 @@ pub async fn get_current_user(user: AuthorizedUser) -> Json<UserResponse> {
     Json(UserResponse::from(user.user))
 }
 
+/// ログイン中のユーザーの名前とメールアドレスを部分更新するハンドラ
+/// JSON Merge Patch形式で、指定された項目のみを更新する
+pub async fn patch_current_user(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<PatchUserRequest>,
+) -> AppResult<Json<UserResponse>> {
+    req.validate()?;
+
+    registry
+        .user_repository()
+        .update_profile(PatchUserRequestWithUserId::new(user.id(), req).into())
+        .await
+        .map(UserResponse::from)
+        .map(Json)
+}
+
 /// ユーザーのパスワードを変更するハンドラ
 pub async fn update_user_password(
     user: AuthorizedUser,
@@ -121,6 +170,174 @@ pub async fn get_checkouts(
         .checkout_repository()
         .find_unreturned_by_user_id(user.id())
         .await
-        .map(|checkouts| CheckoutsResponse::from(checkouts))
+        .map(CheckoutsResponse::from)
         .map(Json)
 }
+
+/// 管理者がログイン中のユーザーの書籍に対して行った更新・削除・譲渡を、新しい順に取得するハンドラ
+pub async fn get_book_admin_actions(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookAdminActionsResponse>> {
+    registry
+        .book_repository()
+        .find_admin_actions(user.id())
+        .await
+        .map(BookAdminActionsResponse::from)
+        .map(Json)
+}
+
+/// ログイン中のユーザー宛ての承認待ちの書籍の譲渡を、古い順に取得するハンドラ
+pub async fn get_book_transfers(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookTransfersResponse>> {
+    registry
+        .book_repository()
+        .find_incoming_transfers(user.id())
+        .await
+        .map(BookTransfersResponse::from)
+        .map(Json)
+}
+
+/// 指定したユーザーが所有するすべての書籍を、他のユーザーに譲渡するハンドラ（管理者のみ）
+/// 削除済みの書籍も譲渡し、譲渡元のユーザーが関わる承認待ちの譲渡は取り消す
+pub async fn transfer_user_books(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(user_id): Path<UserId>,
+    Json(req): Json<TransferAllBooksRequest>,
+) -> AppResult<Json<TransferAllBooksResponse>> {
+    // 管理者のみがユーザーの書籍をまとめて譲渡できる
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperationError);
+    }
+
+    let transferred = registry
+        .book_repository()
+        .transfer_all(TransferAllBooksRequestWithIds::new(user_id, user.id(), req).into())
+        .await?;
+
+    Ok(Json(TransferAllBooksResponse { transferred }))
+}
+
+/// ログイン中のユーザーがお気に入りに登録した書籍を、登録日時の新しい順に取得するハンドラ
+pub async fn get_favorites(
+    user: AuthorizedUser,
+    Query(query): Query<FavoriteBookListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedBookResponse>> {
+    query.validate()?;
+
+    let mut books = registry
+        .book_repository()
+        .find_all(query.into_options(user.id()))
+        .await
+        .map(PaginatedBookResponse::from)?;
+    for book in &mut books.items {
+        book.favorited = true;
+    }
+
+    Ok(Json(books))
+}
+
+/// 書籍をお気に入りに登録するハンドラ。登録済みの場合も成功とする
+pub async fn add_favorite(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
+    registry
+        .favorite_repository()
+        .add(AddFavoriteBook {
+            user_id: user.id(),
+            book_id,
+        })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 書籍をお気に入りから外すハンドラ。登録されていない場合も成功とする
+pub async fn remove_favorite(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(book_id): Path<BookId>,
+) -> AppResult<StatusCode> {
+    registry
+        .favorite_repository()
+        .remove(RemoveFavoriteBook {
+            user_id: user.id(),
+            book_id,
+        })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// ログイン中のユーザーのほしいものリストを、追加した順に取得するハンドラ
+pub async fn get_wishlist(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<WishlistItemsResponse>> {
+    registry
+        .wishlist_repository()
+        .find_all(user.id())
+        .await
+        .map(WishlistItemsResponse::from)
+        .map(Json)
+}
+
+/// ほしいものリストに項目を追加するハンドラ
+/// ISBNを指定した項目は、同じISBNの書籍が登録されたときに通知される
+pub async fn add_wishlist_item(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateWishlistItemRequest>,
+) -> AppResult<(StatusCode, Json<WishlistItemResponse>)> {
+    req.validate()?;
+
+    let item = registry
+        .wishlist_repository()
+        .create(CreateWishlistItemRequestWithUserId::new(user.id(), req).try_into()?)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(item.into())))
+}
+
+/// ほしいものリストから項目を削除するハンドラ
+pub async fn delete_wishlist_item(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(wishlist_item_id): Path<WishlistItemId>,
+) -> AppResult<StatusCode> {
+    registry
+        .wishlist_repository()
+        .delete(DeleteWishlistItem::new(user.id(), wishlist_item_id))
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// ログイン中のユーザーへの通知を、新しい順に取得するハンドラ
+pub async fn get_notifications(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<NotificationsResponse>> {
+    registry
+        .notification_repository()
+        .find_all(user.id())
+        .await
+        .map(NotificationsResponse::from)
+        .map(Json)
+}
+
+/// 通知を既読にするハンドラ
+pub async fn read_notification(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Path(notification_id): Path<NotificationId>,
+) -> AppResult<StatusCode> {
+    registry
+        .notification_repository()
+        .mark_read(MarkNotificationRead::new(user.id(), notification_id))
+        .await
+        .map(|_| StatusCode::OK)
+}
@@ -1,53 +1,124 @@
 use core::str;
+use std::str::FromStr;
 
-use chrono::{DateTime, Utc};
+use axum::http::{HeaderMap, header::IF_MATCH};
+use chrono::{DateTime, NaiveDate, Utc};
 use derive_new::new;
 use garde::Validate;
 use serde::{Deserialize, Serialize};
 #[cfg(debug_assertions)]
 use utoipa::ToSchema;
 
-use super::user::{BookOwner, CheckoutUser};
+use super::{
+    book_copy_status::CopyStatusName,
+    book_cover::{cover_url, thumbnail_url},
+    list::{CursorPaginatedResponse, SortOrderName, default_limit},
+    location::LocationResponse,
+    merge_patch::{not_null, patch_field},
+    user::{BookOwner, CheckoutUser},
+};
 use kernel::model::{
     book::{
-        Book, BookListOptions, Checkout,
-        event::{CreateBook, UpdateBook},
+        Book, BookAvailability, BookCopy, BookListOptions, BookMetadata, BookSort, BookSortKey,
+        Checkout, DeletedBook, DeletedBookListOptions,
+        event::{
+            AddBookTags, CreateBook, CreateBookCopy, MoveBook, PatchBook, SetBookLoanPeriod,
+            UpdateBook,
+        },
     },
-    id::{BookId, CheckoutId, UserId},
-    list::PaginatedList,
+    id::{BookId, CheckoutId, CopyId, LocationId, UserId},
+    isbn::Isbn,
+    list::{PaginatedList, SortOrder},
 };
+use shared::error::{AppError, AppResult};
 
 #[derive(Debug, Deserialize, Validate)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBookRequest {
-    #[garde(length(min = 1))]
-    pub title: String,
-    #[garde(length(min = 1))]
-    pub author: String,
-    #[garde(length(min = 1))]
+    /// 省略した場合はISBNから取得した書誌情報で補う
+    #[garde(inner(length(min = 1)))]
+    pub title: Option<String>,
+    /// 省略した場合はISBNから取得した書誌情報で補う
+    #[garde(inner(length(min = 1)))]
+    pub author: Option<String>,
+    /// ISBN-10またはISBN-13（ハイフンの有無は問わない）
+    #[garde(custom(validate_isbn))]
     pub isbn: String,
     #[garde(skip)]
+    #[serde(default)]
     pub description: String,
+    #[garde(inner(length(min = 1)))]
+    pub publisher: Option<String>,
+    #[garde(skip)]
+    pub published_on: Option<NaiveDate>,
+    #[garde(inner(range(min = 1)))]
+    pub page_count: Option<i32>,
 }
 
-impl From<CreateBookRequest> for CreateBook {
-    fn from(value: CreateBookRequest) -> Self {
+impl CreateBookRequest {
+    /// タイトルか著者が省略されており、書誌情報で補う必要があるかどうか
+    pub fn needs_metadata(&self) -> bool {
+        self.title.is_none() || self.author.is_none()
+    }
+
+    /// 省略された項目を書誌情報で補ってCreateBookに変換する
+    /// リクエストで指定された項目は書誌情報より優先する
+    pub fn into_create_book(self, metadata: Option<BookMetadata>) -> AppResult<CreateBook> {
         let CreateBookRequest {
-            title,
-            author,
+            mut title,
+            mut author,
             isbn,
             description,
-        } = value;
-        CreateBook {
+            mut publisher,
+            mut published_on,
+            mut page_count,
+        } = self;
+        let isbn = parse_isbn(&isbn)?;
+
+        if let Some(metadata) = metadata {
+            title = title.or(Some(metadata.title));
+            author = author.or(metadata.author);
+            publisher = publisher.or(metadata.publisher);
+            published_on = published_on.or(metadata.published_on);
+            page_count = page_count.or(metadata.page_count);
+        }
+
+        let (Some(title), Some(author)) = (title, author) else {
+            return Err(AppError::UnprocessableEntity(format!(
+                "title and author are required because no book metadata was found for ISBN {}",
+                isbn.as_isbn13()
+            )));
+        };
+
+        Ok(CreateBook {
             title,
             author,
             isbn,
             description,
-        }
+            publisher,
+            published_on,
+            page_count,
+        })
     }
 }
 
+/// ISBNの形式とチェックディジットを検証する
+pub(crate) fn validate_isbn(value: &str, _: &()) -> garde::Result {
+    Isbn::from_str(value)
+        .map(|_| ())
+        .map_err(|e| garde::Error::new(e.to_string()))
+}
+
+/// ISBN文字列をIsbnに変換する。失敗した場合はisbnフィールドの検証エラーとする
+pub(crate) fn parse_isbn(value: &str) -> AppResult<Isbn> {
+    Isbn::from_str(value).map_err(|e| {
+        let mut report = garde::Report::new();
+        report.append(garde::Path::new("isbn"), garde::Error::new(e.to_string()));
+        AppError::ValidationError(report)
+    })
+}
+
 #[derive(Debug, Deserialize, Validate)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
@@ -56,61 +127,348 @@ pub struct UpdateBookRequest {
     pub title: String,
     #[garde(length(min = 1))]
     pub author: String,
-    #[garde(length(min = 1))]
+    /// ISBN-10またはISBN-13（ハイフンの有無は問わない）
+    #[garde(custom(validate_isbn))]
     pub isbn: String,
     #[garde(skip)]
     pub description: String,
+    #[garde(inner(length(min = 1)))]
+    pub publisher: Option<String>,
+    #[garde(skip)]
+    pub published_on: Option<NaiveDate>,
+    #[garde(inner(range(min = 1)))]
+    pub page_count: Option<i32>,
 }
 
-/// UpdateBookRequestWithIdsは、UpdateBookRequestに加えて、book_idとuser_idを持つ
+/// UpdateBookRequestWithIdsは、UpdateBookRequestに加えて、book_idとuser_id、
+/// リクエストしたユーザーが管理者かどうかと、If-Matchで指定された書誌情報の版を持つ
 /// RequestからUpdateBookを生成するための一時的な構造体
 #[derive(new)]
-pub struct UpdateBookRequestWithIds(BookId, UserId, UpdateBookRequest);
+pub struct UpdateBookRequestWithIds(BookId, UserId, bool, Option<i32>, UpdateBookRequest);
+
+impl TryFrom<UpdateBookRequestWithIds> for UpdateBook {
+    type Error = AppError;
 
-impl From<UpdateBookRequestWithIds> for UpdateBook {
-    fn from(value: UpdateBookRequestWithIds) -> Self {
+    fn try_from(value: UpdateBookRequestWithIds) -> AppResult<Self> {
         let UpdateBookRequestWithIds(
             book_id,
             user_id,
+            requested_by_admin,
+            expected_version,
             UpdateBookRequest {
                 title,
                 author,
                 isbn,
                 description,
+                publisher,
+                published_on,
+                page_count,
             },
         ) = value;
 
-        UpdateBook {
+        Ok(UpdateBook {
             book_id,
             title,
             author,
-            isbn,
+            isbn: parse_isbn(&isbn)?,
             description,
+            publisher,
+            published_on,
+            page_count,
             requested_user: user_id,
-        }
+            requested_by_admin,
+            expected_version,
+        })
     }
 }
 
-/// クエリでlimitとoffsetを受け取るための構造体
+/// 書籍の部分更新のリクエスト（JSON Merge Patch）
+/// 省略した項目は変更せず、nullを指定した任意項目は値を削除する
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PatchBookRequest {
+    #[garde(custom(not_null), inner(inner(length(min = 1))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub title: Option<Option<String>>,
+    #[garde(custom(not_null), inner(inner(length(min = 1))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub author: Option<Option<String>>,
+    /// ISBN-10またはISBN-13（ハイフンの有無は問わない）
+    #[garde(custom(not_null), inner(inner(custom(validate_isbn))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub isbn: Option<Option<String>>,
+    #[garde(custom(not_null))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub description: Option<Option<String>>,
+    #[garde(inner(inner(length(min = 1))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub publisher: Option<Option<String>>,
+    #[garde(skip)]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub published_on: Option<Option<NaiveDate>>,
+    #[garde(inner(inner(range(min = 1))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    pub page_count: Option<Option<i32>>,
+}
+
+/// PatchBookRequestWithIdsは、PatchBookRequestに加えて、book_idとuser_id、
+/// リクエストしたユーザーが管理者かどうかと、If-Matchで指定された書誌情報の版を持つ
+#[derive(new)]
+pub struct PatchBookRequestWithIds(BookId, UserId, bool, Option<i32>, PatchBookRequest);
+
+impl TryFrom<PatchBookRequestWithIds> for PatchBook {
+    type Error = AppError;
+
+    /// 削除できない項目のnullは検証で弾いているため、Option::flattenで値の有無のみにする
+    fn try_from(value: PatchBookRequestWithIds) -> AppResult<Self> {
+        let PatchBookRequestWithIds(
+            book_id,
+            user_id,
+            requested_by_admin,
+            expected_version,
+            PatchBookRequest {
+                title,
+                author,
+                isbn,
+                description,
+                publisher,
+                published_on,
+                page_count,
+            },
+        ) = value;
+
+        Ok(PatchBook {
+            book_id,
+            title: title.flatten(),
+            author: author.flatten(),
+            isbn: isbn.flatten().as_deref().map(parse_isbn).transpose()?,
+            description: description.flatten(),
+            publisher,
+            published_on,
+            page_count,
+            requested_user: user_id,
+            requested_by_admin,
+            expected_version,
+        })
+    }
+}
+
+/// 書誌情報の版から、書籍のETagを作成する
+pub fn book_etag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// If-Matchヘッダーから、更新や削除の前提とする書誌情報の版を取り出す
+/// ヘッダーがない場合と`*`の場合は版を確認しない
+/// 弱いETagや、このAPIが返した形式でない値はどの版とも一致しないため412とする
+pub fn parse_if_match(headers: &HeaderMap) -> AppResult<Option<i32>> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .and_then(|v| v.parse::<i32>().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::PreconditionFailed("If-Match does not match the current ETag".into())
+        })
+}
+
+/// クエリでlimitとoffset、検索・絞り込みの条件を受け取るための構造体
 #[derive(Debug, Deserialize, Validate)]
 pub struct BookListQuery {
     #[garde(range(min = 0))]
     #[serde(default = "default_limit")]
     pub limit: i64,
-    #[garde(range(min = 0))]
+    #[garde(range(min = 0), custom(offset_without_cursor(&self.cursor)))]
     #[serde(default)] // defaultは0
     pub offset: i64,
+    #[garde(length(min = 1))]
+    pub q: Option<String>,
+    #[garde(length(min = 1))]
+    pub author: Option<String>,
+    #[garde(skip)]
+    pub owner: Option<UserId>,
+    #[garde(skip)]
+    pub location: Option<LocationId>,
+    #[garde(skip)]
+    pub availability: Option<BookAvailabilityName>,
+    /// 同じキーの繰り返しはserde_urlencodedで受け取れないため、BookTagQueryから設定する
+    #[garde(inner(custom(validate_tag)))]
+    #[serde(skip)]
+    pub tag: Vec<String>,
+    #[garde(skip)]
+    pub sort: Option<BookSortKeyName>,
+    #[garde(skip)]
+    pub order: Option<SortOrderName>,
+    /// 指定された場合はカーソルによるページネーションを行う。空文字の場合は先頭から取得する
+    #[garde(skip)]
+    pub cursor: Option<String>,
 }
 
-const DEFAULT_LIMIT: i64 = 20;
-const fn default_limit() -> i64 {
-    DEFAULT_LIMIT
+/// tag=rust&tag=asyncのように複数指定されたタグを受け取るための構造体
+/// すべてのタグが付いた書籍に絞り込む
+#[derive(Debug, Deserialize)]
+pub struct BookTagQuery {
+    #[serde(default)]
+    pub tag: Vec<String>,
+}
+
+/// カーソルによるページネーションではoffsetを指定できない
+fn offset_without_cursor(cursor: &Option<String>) -> impl FnOnce(&i64, &()) -> garde::Result + '_ {
+    move |offset, _| {
+        if cursor.is_some() && *offset != 0 {
+            return Err(garde::Error::new("offset cannot be used with cursor"));
+        }
+        Ok(())
+    }
+}
+
+impl BookListQuery {
+    /// 書籍一覧の取得条件とカーソルに分解する
+    pub fn into_options_with_cursor(mut self) -> (BookListOptions, Option<String>) {
+        let cursor = self.cursor.take().filter(|c| !c.is_empty());
+        (self.into(), cursor)
+    }
 }
 
 impl From<BookListQuery> for BookListOptions {
     fn from(value: BookListQuery) -> Self {
-        let BookListQuery { limit, offset } = value;
-        Self { limit, offset }
+        let BookListQuery {
+            limit,
+            offset,
+            q,
+            author,
+            owner,
+            location,
+            availability,
+            tag,
+            sort,
+            order,
+            ..
+        } = value;
+        // 並び替えのキーがなく昇順・降順だけが指定された場合は、登録日時で並べる
+        let sort = match (sort, order) {
+            (None, None) => None,
+            (key, order) => {
+                let key = key.map_or(BookSortKey::CreatedAt, BookSortKey::from);
+                Some(BookSort {
+                    key,
+                    order: order.map_or(key.default_order(), SortOrder::from),
+                })
+            }
+        };
+        Self {
+            limit,
+            offset,
+            q,
+            author,
+            owner,
+            location,
+            favorited_by: None,
+            availability: availability.map(BookAvailability::from),
+            tags: normalize_tags(tag),
+            sort,
+        }
+    }
+}
+
+/// タグ名は前後の空白を除いて1〜50文字とする
+fn validate_tag(value: &str, _: &()) -> garde::Result {
+    let len = value.trim().chars().count();
+    if !(1..=50).contains(&len) {
+        return Err(garde::Error::new("tag must be 1 to 50 characters"));
+    }
+    Ok(())
+}
+
+/// タグ名を前後の空白を除いた小文字に正規化し、重複を取り除く
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// 書籍にタグを付けるリクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AddBookTagsRequest {
+    #[garde(length(min = 1), inner(custom(validate_tag)))]
+    pub tags: Vec<String>,
+}
+
+#[derive(new)]
+pub struct AddBookTagsRequestWithIds(BookId, UserId, bool, AddBookTagsRequest);
+
+impl From<AddBookTagsRequestWithIds> for AddBookTags {
+    fn from(value: AddBookTagsRequestWithIds) -> Self {
+        let AddBookTagsRequestWithIds(book_id, user_id, is_admin, AddBookTagsRequest { tags }) =
+            value;
+        AddBookTags {
+            book_id,
+            tags: normalize_tags(tags),
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+/// パスで受け取ったタグ名を正規化する
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// クエリで貸出状態を受け取るための型
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookAvailabilityName {
+    Available,
+    CheckedOut,
+}
+
+impl From<BookAvailabilityName> for BookAvailability {
+    fn from(value: BookAvailabilityName) -> Self {
+        match value {
+            BookAvailabilityName::Available => Self::Available,
+            BookAvailabilityName::CheckedOut => Self::CheckedOut,
+        }
+    }
+}
+
+/// クエリで並び替えのキーを受け取るための型
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookSortKeyName {
+    Title,
+    Author,
+    CreatedAt,
+    UpdatedAt,
+    MostBorrowed,
+    Rating,
+}
+
+impl From<BookSortKeyName> for BookSortKey {
+    fn from(value: BookSortKeyName) -> Self {
+        match value {
+            BookSortKeyName::Title => Self::Title,
+            BookSortKeyName::Author => Self::Author,
+            BookSortKeyName::CreatedAt => Self::CreatedAt,
+            BookSortKeyName::UpdatedAt => Self::UpdatedAt,
+            BookSortKeyName::MostBorrowed => Self::MostBorrowed,
+            BookSortKeyName::Rating => Self::Rating,
+        }
     }
 }
 
@@ -121,34 +479,196 @@ pub struct BookResponse {
     pub id: BookId,
     pub title: String,
     pub author: String,
+    /// 表示用のISBN
     pub isbn: String,
+    /// ハイフンなしのISBN-13
+    pub isbn13: String,
     pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
     pub owner: BookOwner,
-    pub checkout: Option<BookCheckoutResponse>,
+    /// 蔵書の総数
+    pub total_copies: usize,
+    /// 貸出可能な蔵書の数
+    pub available_copies: usize,
+    pub copies: Vec<BookCopyResponse>,
+    pub tags: Vec<String>,
+    /// 書影の画像のURL。書影が登録されていない場合はnull
+    pub cover_url: Option<String>,
+    /// 書影のサムネイルのURL。書影が登録されていない場合はnull
+    pub thumbnail_url: Option<String>,
+    /// 書籍の置き場所。未設定の場合はnull
+    pub location: Option<LocationResponse>,
+    /// 評価の平均。レビューがない場合はnull
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    /// 現在のユーザーがお気に入りに登録しているかどうか
+    pub favorited: bool,
+    /// 書籍ごとの貸出期間（日数）。未設定の場合はnullで、全体の既定値を使う
+    pub loan_period_days: Option<i32>,
 }
 impl From<Book> for BookResponse {
     fn from(value: Book) -> Self {
+        let total_copies = value.total_copies();
+        let available_copies = value.available_copies();
         let Book {
             id,
             title,
             author,
             isbn,
             description,
+            publisher,
+            published_on,
+            page_count,
             owner,
-            checkout,
+            copies,
+            tags,
+            cover,
+            version: _,
+            location,
+            rating,
+            loan_period_days,
         } = value;
         BookResponse {
             id,
             title,
             author,
-            isbn,
+            isbn: isbn.display().to_string(),
+            isbn13: isbn.as_isbn13().to_string(),
             description,
+            publisher,
+            published_on,
+            page_count,
             owner: owner.into(),
+            total_copies,
+            available_copies,
+            copies: copies.into_iter().map(BookCopyResponse::from).collect(),
+            tags,
+            cover_url: cover.map(|_| cover_url(id)),
+            thumbnail_url: cover.map(|_| thumbnail_url(id)),
+            location: location.map(LocationResponse::from),
+            average_rating: rating.average,
+            review_count: rating.count,
+            // ユーザーごとの値のため、ハンドラで設定する
+            favorited: false,
+            loan_period_days,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookCopyResponse {
+    pub id: CopyId,
+    pub barcode: String,
+    pub status: CopyStatusName,
+    pub checkout: Option<BookCheckoutResponse>,
+}
+
+impl From<BookCopy> for BookCopyResponse {
+    fn from(value: BookCopy) -> Self {
+        let BookCopy {
+            id,
+            barcode,
+            status,
+            checkout,
+        } = value;
+        Self {
+            id,
+            barcode,
+            status: status.into(),
             checkout: checkout.map(BookCheckoutResponse::from),
         }
     }
 }
 
+/// 書籍の置き場所を移すリクエスト
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MoveBookRequest {
+    /// 移動先の置き場所。nullの場合は置き場所を未設定に戻す
+    pub location_id: Option<LocationId>,
+}
+
+#[derive(new)]
+pub struct MoveBookRequestWithIds(BookId, UserId, bool, MoveBookRequest);
+
+impl From<MoveBookRequestWithIds> for MoveBook {
+    fn from(value: MoveBookRequestWithIds) -> Self {
+        let MoveBookRequestWithIds(book_id, user_id, is_admin, MoveBookRequest { location_id }) =
+            value;
+        MoveBook {
+            book_id,
+            location_id,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+/// 書籍の貸出期間を変えるリクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetBookLoanPeriodRequest {
+    /// 貸出期間（日数）。nullの場合は全体の既定値に戻す
+    #[garde(inner(range(min = 1)))]
+    pub loan_period_days: Option<i32>,
+}
+
+#[derive(new)]
+pub struct SetBookLoanPeriodRequestWithIds(BookId, UserId, bool, SetBookLoanPeriodRequest);
+
+impl From<SetBookLoanPeriodRequestWithIds> for SetBookLoanPeriod {
+    fn from(value: SetBookLoanPeriodRequestWithIds) -> Self {
+        let SetBookLoanPeriodRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            SetBookLoanPeriodRequest { loan_period_days },
+        ) = value;
+        SetBookLoanPeriod {
+            book_id,
+            loan_period_days,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+/// 書籍に蔵書を追加するリクエスト
+#[derive(Debug, Default, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBookCopyRequest {
+    /// 未指定の場合は連番で採番する
+    #[garde(inner(length(min = 1)))]
+    pub barcode: Option<String>,
+}
+
+#[derive(new)]
+pub struct CreateBookCopyRequestWithIds(BookId, UserId, bool, CreateBookCopyRequest);
+
+impl From<CreateBookCopyRequestWithIds> for CreateBookCopy {
+    fn from(value: CreateBookCopyRequestWithIds) -> Self {
+        let CreateBookCopyRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            CreateBookCopyRequest { barcode },
+        ) = value;
+        CreateBookCopy {
+            book_id,
+            barcode,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
 /// apiレイヤーでのページネーション表現用の型
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
@@ -177,6 +697,127 @@ impl From<PaginatedList<Book>> for PaginatedBookResponse {
     }
 }
 
+/// クエリで削除済みの書籍一覧のlimitとoffsetを受け取るための構造体
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeletedBookListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl From<DeletedBookListQuery> for DeletedBookListOptions {
+    fn from(value: DeletedBookListQuery) -> Self {
+        let DeletedBookListQuery { limit, offset } = value;
+        Self { limit, offset }
+    }
+}
+
+/// クエリでお気に入りの書籍一覧のlimitとoffsetを受け取るための構造体
+#[derive(Debug, Deserialize, Validate)]
+pub struct FavoriteBookListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl FavoriteBookListQuery {
+    /// 指定したユーザーのお気に入りの書籍を、登録日時の新しい順に取得する設定値に変換する
+    pub fn into_options(self, user_id: UserId) -> BookListOptions {
+        let FavoriteBookListQuery { limit, offset } = self;
+        BookListOptions {
+            limit,
+            offset,
+            favorited_by: Some(user_id),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedBookResponse {
+    #[serde(flatten)]
+    pub book: BookResponse,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl From<DeletedBook> for DeletedBookResponse {
+    fn from(value: DeletedBook) -> Self {
+        let DeletedBook { book, deleted_at } = value;
+        Self {
+            book: book.into(),
+            deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedDeletedBookResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<DeletedBookResponse>,
+}
+
+impl From<PaginatedList<DeletedBook>> for PaginatedDeletedBookResponse {
+    fn from(value: PaginatedList<DeletedBook>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = value;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(DeletedBookResponse::from).collect(),
+        }
+    }
+}
+
+/// ISBNで検索した書籍一覧のレスポンス
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BooksResponse {
+    pub items: Vec<BookResponse>,
+}
+
+impl From<Vec<Book>> for BooksResponse {
+    fn from(value: Vec<Book>) -> Self {
+        Self {
+            items: value.into_iter().map(BookResponse::from).collect(),
+        }
+    }
+}
+
+/// 書籍一覧のレスポンス。ページネーションの方式に応じて形が変わる
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BookListResponse {
+    Offset(PaginatedBookResponse),
+    Cursor(CursorPaginatedResponse<BookResponse>),
+}
+
+impl BookListResponse {
+    pub fn items_mut(&mut self) -> &mut [BookResponse] {
+        match self {
+            Self::Offset(page) => &mut page.items,
+            Self::Cursor(page) => &mut page.items,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
@@ -184,6 +825,10 @@ pub struct BookCheckoutResponse {
     pub id: CheckoutId,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
+    /// 返却期限
+    pub due_at: DateTime<Utc>,
+    /// 延長した回数
+    pub renewal_count: i64,
 }
 
 impl From<Checkout> for BookCheckoutResponse {
@@ -192,11 +837,15 @@ impl From<Checkout> for BookCheckoutResponse {
             checkout_id,
             checked_out_by,
             checked_out_at,
+            due_at,
+            renewal_count,
         } = value;
         Self {
             id: checkout_id,
             checked_out_by: checked_out_by.into(),
             checked_out_at,
+            due_at,
+            renewal_count,
         }
     }
 }
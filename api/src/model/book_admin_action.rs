@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::{BookAdminAction, BookAdminActionKind},
+    id::{BookAdminActionId, BookId},
+};
+
+use super::user::BookEditor;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookAdminActionsResponse {
+    pub items: Vec<BookAdminActionResponse>,
+}
+
+impl From<Vec<BookAdminAction>> for BookAdminActionsResponse {
+    fn from(value: Vec<BookAdminAction>) -> Self {
+        Self {
+            items: value
+                .into_iter()
+                .map(BookAdminActionResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookAdminActionResponse {
+    pub id: BookAdminActionId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub action: BookAdminActionName,
+    /// 操作した管理者。管理者のユーザーが削除済みの場合はnull
+    pub admin: Option<BookEditor>,
+    pub acted_at: DateTime<Utc>,
+}
+
+impl From<BookAdminAction> for BookAdminActionResponse {
+    fn from(value: BookAdminAction) -> Self {
+        let BookAdminAction {
+            id,
+            book_id,
+            book_title,
+            action,
+            admin,
+            acted_at,
+        } = value;
+        Self {
+            id,
+            book_id,
+            book_title,
+            action: action.into(),
+            admin: admin.map(BookEditor::from),
+            acted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum BookAdminActionName {
+    Update,
+    Delete,
+    Transfer,
+    Move,
+    SetLoanPeriod,
+    AddCopy,
+    DeleteCopy,
+    AddTags,
+    RemoveTag,
+    UploadCover,
+}
+
+impl From<BookAdminActionKind> for BookAdminActionName {
+    fn from(value: BookAdminActionKind) -> Self {
+        match value {
+            BookAdminActionKind::Update => Self::Update,
+            BookAdminActionKind::Delete => Self::Delete,
+            BookAdminActionKind::Transfer => Self::Transfer,
+            BookAdminActionKind::Move => Self::Move,
+            BookAdminActionKind::SetLoanPeriod => Self::SetLoanPeriod,
+            BookAdminActionKind::AddCopy => Self::AddCopy,
+            BookAdminActionKind::DeleteCopy => Self::DeleteCopy,
+            BookAdminActionKind::AddTags => Self::AddTags,
+            BookAdminActionKind::RemoveTag => Self::RemoveTag,
+            BookAdminActionKind::UploadCover => Self::UploadCover,
+        }
+    }
+}
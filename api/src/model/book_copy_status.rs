@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::{CopyStatus, CopyStatusAction, CopyStatusChange, event::ChangeCopyStatus},
+    id::{BookId, CopyId, CopyStatusChangeId, UserId},
+};
+
+use super::user::BookEditor;
+
+/// 蔵書の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum CopyStatusName {
+    Available,
+    InRepair,
+    Lost,
+    Damaged,
+    Withdrawn,
+}
+
+impl From<CopyStatus> for CopyStatusName {
+    fn from(value: CopyStatus) -> Self {
+        match value {
+            CopyStatus::Available => Self::Available,
+            CopyStatus::InRepair => Self::InRepair,
+            CopyStatus::Lost => Self::Lost,
+            CopyStatus::Damaged => Self::Damaged,
+            CopyStatus::Withdrawn => Self::Withdrawn,
+        }
+    }
+}
+
+/// 蔵書の状態を変える操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum CopyStatusActionName {
+    ReportLost,
+    MarkFound,
+    ReportDamaged,
+    SendToRepair,
+    FinishRepair,
+    Withdraw,
+}
+
+impl From<CopyStatusActionName> for CopyStatusAction {
+    fn from(value: CopyStatusActionName) -> Self {
+        match value {
+            CopyStatusActionName::ReportLost => Self::ReportLost,
+            CopyStatusActionName::MarkFound => Self::MarkFound,
+            CopyStatusActionName::ReportDamaged => Self::ReportDamaged,
+            CopyStatusActionName::SendToRepair => Self::SendToRepair,
+            CopyStatusActionName::FinishRepair => Self::FinishRepair,
+            CopyStatusActionName::Withdraw => Self::Withdraw,
+        }
+    }
+}
+
+impl From<CopyStatusAction> for CopyStatusActionName {
+    fn from(value: CopyStatusAction) -> Self {
+        match value {
+            CopyStatusAction::ReportLost => Self::ReportLost,
+            CopyStatusAction::MarkFound => Self::MarkFound,
+            CopyStatusAction::ReportDamaged => Self::ReportDamaged,
+            CopyStatusAction::SendToRepair => Self::SendToRepair,
+            CopyStatusAction::FinishRepair => Self::FinishRepair,
+            CopyStatusAction::Withdraw => Self::Withdraw,
+        }
+    }
+}
+
+/// 蔵書の状態を変えるリクエスト
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeCopyStatusRequest {
+    #[garde(skip)]
+    pub action: CopyStatusActionName,
+    /// 状態を変えた理由や経緯
+    #[garde(length(min = 1))]
+    pub note: String,
+}
+
+#[derive(new)]
+pub struct ChangeCopyStatusRequestWithIds(BookId, CopyId, UserId, bool, ChangeCopyStatusRequest);
+
+impl From<ChangeCopyStatusRequestWithIds> for ChangeCopyStatus {
+    fn from(value: ChangeCopyStatusRequestWithIds) -> Self {
+        let ChangeCopyStatusRequestWithIds(
+            book_id,
+            copy_id,
+            user_id,
+            is_admin,
+            ChangeCopyStatusRequest { action, note },
+        ) = value;
+        ChangeCopyStatus {
+            book_id,
+            copy_id,
+            action: action.into(),
+            note,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CopyStatusChangesResponse {
+    pub items: Vec<CopyStatusChangeResponse>,
+}
+
+impl From<Vec<CopyStatusChange>> for CopyStatusChangesResponse {
+    fn from(value: Vec<CopyStatusChange>) -> Self {
+        Self {
+            items: value
+                .into_iter()
+                .map(CopyStatusChangeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CopyStatusChangeResponse {
+    pub id: CopyStatusChangeId,
+    pub copy_id: CopyId,
+    pub action: CopyStatusActionName,
+    pub from: CopyStatusName,
+    pub to: CopyStatusName,
+    pub note: String,
+    /// 変更したユーザー。ユーザーが削除済みの場合はnull
+    pub changed_by: Option<BookEditor>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<CopyStatusChange> for CopyStatusChangeResponse {
+    fn from(value: CopyStatusChange) -> Self {
+        let CopyStatusChange {
+            id,
+            copy_id,
+            action,
+            from,
+            to,
+            note,
+            changed_by,
+            changed_at,
+        } = value;
+        Self {
+            id,
+            copy_id,
+            action: action.into(),
+            from: from.into(),
+            to: to.into(),
+            note,
+            changed_by: changed_by.map(BookEditor::from),
+            changed_at,
+        }
+    }
+}
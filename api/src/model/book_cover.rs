@@ -0,0 +1,78 @@
+use axum::{
+    extract::{
+        Multipart,
+        multipart::{Field, MultipartError},
+    },
+    http::StatusCode,
+};
+
+use kernel::model::{book::CoverImageType, id::BookId};
+use shared::error::{AppError, AppResult};
+
+/// 書影として受け付ける画像ファイルのサイズの上限（5MiB）
+pub const MAX_COVER_SIZE: usize = 5 * 1024 * 1024;
+/// multipartの境界やヘッダーの分を見込んだリクエストボディのサイズの上限
+pub const MAX_COVER_REQUEST_SIZE: usize = MAX_COVER_SIZE + 64 * 1024;
+/// 書影の画像を受け取るmultipartのフィールド名
+const COVER_FIELD_NAME: &str = "cover";
+
+/// 書影の元画像のURL
+pub fn cover_url(book_id: BookId) -> String {
+    format!("/api/v1/books/{book_id}/cover")
+}
+
+/// 書影のサムネイルのURL
+pub fn thumbnail_url(book_id: BookId) -> String {
+    format!("/api/v1/books/{book_id}/cover/thumbnail")
+}
+
+/// multipartのcoverフィールドから書影の画像を読み取る
+/// Content-Typeが書影として受け付ける形式でない場合や、サイズが上限を超える場合はエラーを返す
+pub async fn read_cover(multipart: &mut Multipart) -> AppResult<(CoverImageType, Vec<u8>)> {
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some(COVER_FIELD_NAME) {
+            continue;
+        }
+
+        let image_type = field
+            .content_type()
+            .and_then(|c| c.parse::<CoverImageType>().ok())
+            .ok_or_else(|| {
+                AppError::UnsupportedMediaType("cover must be image/jpeg or image/png".into())
+            })?;
+        let data = read_limited(field).await?;
+        if data.is_empty() {
+            return Err(AppError::UnprocessableEntity(
+                "cover must not be empty".into(),
+            ));
+        }
+
+        return Ok((image_type, data));
+    }
+
+    Err(AppError::UnprocessableEntity(format!(
+        "multipart field `{COVER_FIELD_NAME}` is required"
+    )))
+}
+
+/// 上限を超えた時点で読み込みをやめるよう、フィールドをチャンクごとに読み込む
+async fn read_limited(mut field: Field<'_>) -> AppResult<Vec<u8>> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        if data.len() + chunk.len() > MAX_COVER_SIZE {
+            return Err(AppError::PayloadTooLarge(format!(
+                "cover must be at most {MAX_COVER_SIZE} bytes"
+            )));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+fn multipart_error(e: MultipartError) -> AppError {
+    if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge(e.body_text())
+    } else {
+        AppError::UnprocessableEntity(e.body_text())
+    }
+}
@@ -0,0 +1,158 @@
+//! 書籍の目録をエクスポートするための型を定義するモジュール
+
+use std::io;
+
+use axum::body::Body;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::{BookCatalogEntry, BookCopy, Checkout},
+    id::{BookId, CheckoutId, CopyId, UserId},
+};
+use shared::error::AppResult;
+
+use super::book_copy_status::CopyStatusName;
+
+/// クエリでエクスポートの形式を受け取るための型
+#[derive(Debug, Deserialize)]
+pub struct BookExportQuery {
+    #[serde(default)]
+    pub format: BookExportFormat,
+}
+
+/// エクスポートの形式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookExportFormat {
+    /// ヘッダー行付きのCSV
+    #[default]
+    Csv,
+    /// 1行に1つのJSONオブジェクトを書くJSON Lines
+    Jsonl,
+}
+
+impl BookExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn content_disposition(self) -> &'static str {
+        match self {
+            Self::Csv => "attachment; filename=\"books.csv\"",
+            Self::Jsonl => "attachment; filename=\"books.jsonl\"",
+        }
+    }
+
+    /// 目録のストリームを、1行ずつ変換しながら送るレスポンスボディにする
+    pub fn into_body(self, entries: BoxStream<'static, AppResult<BookCatalogEntry>>) -> Body {
+        let records = entries.map(|entry| entry.map(BookExportRecord::from));
+        match self {
+            Self::Csv => {
+                // ヘッダー行は最初の行を書くときにだけ出力する
+                let mut has_headers = true;
+                Body::from_stream(records.map(move |record| -> io::Result<Vec<u8>> {
+                    let mut writer = csv::WriterBuilder::new()
+                        .has_headers(std::mem::take(&mut has_headers))
+                        .from_writer(Vec::new());
+                    writer
+                        .serialize(record.map_err(io::Error::other)?)
+                        .map_err(io::Error::other)?;
+                    writer.into_inner().map_err(io::Error::other)
+                }))
+            }
+            Self::Jsonl => Body::from_stream(records.map(|record| -> io::Result<Vec<u8>> {
+                let mut line = serde_json::to_vec(&record.map_err(io::Error::other)?)?;
+                line.push(b'\n');
+                Ok(line)
+            })),
+        }
+    }
+}
+
+/// エクスポートする目録の1行分。蔵書ごとに1行とする
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookExportRecord {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    /// 表示用のISBN
+    pub isbn: String,
+    /// ハイフンなしのISBN-13
+    pub isbn13: String,
+    pub description: String,
+    pub owner_id: UserId,
+    pub owner_name: String,
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
+    pub copy_status: Option<CopyStatusName>,
+    pub checkout_id: Option<CheckoutId>,
+    pub checked_out_by_id: Option<UserId>,
+    pub checked_out_by_name: Option<String>,
+    pub checked_out_at: Option<DateTime<Utc>>,
+}
+
+impl From<BookCatalogEntry> for BookExportRecord {
+    fn from(value: BookCatalogEntry) -> Self {
+        let BookCatalogEntry {
+            book_id,
+            title,
+            author,
+            isbn,
+            description,
+            owner,
+            copy,
+        } = value;
+        let (copy_id, barcode, copy_status, checkout) = match copy {
+            Some(BookCopy {
+                id,
+                barcode,
+                status,
+                checkout,
+            }) => (Some(id), Some(barcode), Some(status.into()), checkout),
+            None => (None, None, None, None),
+        };
+        let (checkout_id, checked_out_by, checked_out_at) = match checkout {
+            Some(Checkout {
+                checkout_id,
+                checked_out_by,
+                checked_out_at,
+                due_at: _,
+                renewal_count: _,
+            }) => (
+                Some(checkout_id),
+                Some(checked_out_by),
+                Some(checked_out_at),
+            ),
+            None => (None, None, None),
+        };
+        let (checked_out_by_id, checked_out_by_name) =
+            checked_out_by.map(|user| (user.id, user.name)).unzip();
+
+        Self {
+            book_id,
+            title,
+            author,
+            isbn: isbn.display().to_string(),
+            isbn13: isbn.as_isbn13().to_string(),
+            description,
+            owner_id: owner.id,
+            owner_name: owner.name,
+            copy_id,
+            barcode,
+            copy_status,
+            checkout_id,
+            checked_out_by_id,
+            checked_out_by_name,
+            checked_out_at,
+        }
+    }
+}
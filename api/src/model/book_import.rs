@@ -0,0 +1,179 @@
+//! CSVによる書籍の一括登録のための型を定義するモジュール
+
+use std::io;
+
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::book::CreateBookRequest;
+use kernel::model::{
+    book::{BookImportResult, BookImportStatus, event::ImportBookRow},
+    id::BookId,
+};
+
+/// CSVの1行分。ヘッダー行の列名で各列を対応付ける
+#[derive(Debug, Deserialize)]
+struct BookCsvRecord {
+    title: String,
+    author: String,
+    isbn: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    owner_email: Option<String>,
+}
+
+/// title, author, isbn, description, owner_email列を持つCSVを読み込み、
+/// 1行ずつCreateBookRequestと同じ規則で検証する
+/// 検証を通過した行は登録する行として、通過しなかった行は不備ありの結果として返す
+pub fn parse_book_csv<R: io::Read>(reader: R) -> (Vec<ImportBookRow>, Vec<BookImportResult>) {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers = reader.headers().cloned().unwrap_or_default();
+
+    let mut rows = Vec::new();
+    let mut invalid = Vec::new();
+    for record in reader.records() {
+        let parsed = record.map_err(|e| (e.position().map_or(0, |p| p.line()), e.to_string()));
+        let parsed = parsed.and_then(|record| {
+            let line = record.position().map_or(0, |p| p.line());
+            record
+                .deserialize::<BookCsvRecord>(Some(&headers))
+                .map_err(|e| e.to_string())
+                .and_then(|record| into_import_row(line, record))
+                .map_err(|message| (line, message))
+        });
+        match parsed {
+            Ok(row) => rows.push(row),
+            Err((line, message)) => invalid.push(BookImportResult {
+                line,
+                status: BookImportStatus::Invalid(message),
+            }),
+        }
+    }
+
+    (rows, invalid)
+}
+
+/// CSVの1行を検証し、一括登録する行に変換する
+fn into_import_row(line: u64, record: BookCsvRecord) -> Result<ImportBookRow, String> {
+    let BookCsvRecord {
+        title,
+        author,
+        isbn,
+        description,
+        owner_email,
+    } = record;
+    // 一括登録では書誌情報による補完は行わないため、タイトルと著者は必須とする
+    let req = CreateBookRequest {
+        title: Some(title),
+        author: Some(author),
+        isbn,
+        description,
+        publisher: None,
+        published_on: None,
+        page_count: None,
+    };
+    req.validate().map_err(|report| {
+        report
+            .iter()
+            .map(|(path, error)| format!("{path}: {}", error.message()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })?;
+
+    Ok(ImportBookRow {
+        line,
+        book: req.into_create_book(None).map_err(|e| e.to_string())?,
+        owner_email: owner_email.filter(|email| !email.is_empty()),
+    })
+}
+
+/// クエリで一括登録の方法を受け取るための型
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportQuery {
+    /// trueの場合は登録せずに結果だけを返す
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 一括登録の結果を集計したレスポンス
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportReportResponse {
+    pub dry_run: bool,
+    pub created: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    /// 行番号順の行ごとの結果
+    pub rows: Vec<BookImportRowResponse>,
+}
+
+impl BookImportReportResponse {
+    pub fn new(results: impl IntoIterator<Item = BookImportResult>, dry_run: bool) -> Self {
+        let mut rows = results
+            .into_iter()
+            .map(|result| BookImportRowResponse::new(result, dry_run))
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|row| row.line);
+        let count =
+            |status: BookImportStatusName| rows.iter().filter(|r| r.status == status).count();
+
+        Self {
+            dry_run,
+            created: count(BookImportStatusName::Created),
+            skipped: count(BookImportStatusName::Duplicate),
+            invalid: count(BookImportStatusName::Invalid),
+            rows,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportRowResponse {
+    pub line: u64,
+    pub status: BookImportStatusName,
+    /// 登録した書籍のID。ドライランの場合は返さない
+    pub book_id: Option<BookId>,
+    /// 不備の内容
+    pub message: Option<String>,
+}
+
+impl BookImportRowResponse {
+    fn new(result: BookImportResult, dry_run: bool) -> Self {
+        let BookImportResult { line, status } = result;
+        let (status, book_id, message) = match status {
+            BookImportStatus::Created(book_id) => (
+                BookImportStatusName::Created,
+                Some(book_id).filter(|_| !dry_run),
+                None,
+            ),
+            BookImportStatus::Duplicate => (BookImportStatusName::Duplicate, None, None),
+            BookImportStatus::Invalid(message) => {
+                (BookImportStatusName::Invalid, None, Some(message))
+            }
+        };
+        Self {
+            line,
+            status,
+            book_id,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum BookImportStatusName {
+    Created,
+    Duplicate,
+    Invalid,
+}
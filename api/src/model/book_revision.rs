@@ -0,0 +1,109 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::{BookRevision, BookSnapshot},
+    id::{BookId, BookRevisionId},
+};
+
+use super::user::BookEditor;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionsResponse {
+    pub items: Vec<BookRevisionResponse>,
+}
+
+impl From<Vec<BookRevision>> for BookRevisionsResponse {
+    fn from(value: Vec<BookRevision>) -> Self {
+        Self {
+            items: value.into_iter().map(BookRevisionResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionResponse {
+    pub id: BookRevisionId,
+    pub book_id: BookId,
+    /// 変更したユーザー。ユーザーが削除済みの場合はnull
+    pub changed_by: Option<BookEditor>,
+    pub changed_at: DateTime<Utc>,
+    /// 変更された項目の名前（titleやpublishedOnなど）
+    pub changed_fields: Vec<String>,
+    pub before: BookSnapshotResponse,
+    pub after: BookSnapshotResponse,
+}
+
+impl From<BookRevision> for BookRevisionResponse {
+    fn from(value: BookRevision) -> Self {
+        let changed_fields = value
+            .changed_fields()
+            .into_iter()
+            .map(|f| f.as_ref().to_string())
+            .collect();
+        let BookRevision {
+            id,
+            book_id,
+            changed_by,
+            changed_at,
+            before,
+            after,
+        } = value;
+        Self {
+            id,
+            book_id,
+            changed_by: changed_by.map(BookEditor::from),
+            changed_at,
+            changed_fields,
+            before: before.into(),
+            after: after.into(),
+        }
+    }
+}
+
+/// 変更履歴に記録された書籍の項目の値
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookSnapshotResponse {
+    pub title: String,
+    pub author: String,
+    /// 表示用のISBN
+    pub isbn: String,
+    /// ハイフンなしのISBN-13
+    pub isbn13: String,
+    pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
+}
+
+impl From<BookSnapshot> for BookSnapshotResponse {
+    fn from(value: BookSnapshot) -> Self {
+        let BookSnapshot {
+            title,
+            author,
+            isbn,
+            description,
+            publisher,
+            published_on,
+            page_count,
+        } = value;
+        Self {
+            title,
+            author,
+            isbn: isbn.display().into(),
+            isbn13: isbn.as_isbn13().into(),
+            description,
+            publisher,
+            published_on,
+            page_count,
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::{
+        BookTransfer,
+        event::{TransferAllBooks, TransferBook},
+    },
+    id::{BookId, BookTransferId, UserId},
+};
+
+use super::user::BookOwner;
+
+/// 書籍を他のユーザーに譲渡するリクエスト
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBookRequest {
+    pub to_user_id: UserId,
+    /// trueの場合は、譲渡先のユーザーが承認するまで所有者を変えない
+    #[serde(default)]
+    pub require_acceptance: bool,
+}
+
+#[derive(new)]
+pub struct TransferBookRequestWithIds(BookId, UserId, bool, TransferBookRequest);
+
+impl From<TransferBookRequestWithIds> for TransferBook {
+    fn from(value: TransferBookRequestWithIds) -> Self {
+        let TransferBookRequestWithIds(
+            book_id,
+            user_id,
+            is_admin,
+            TransferBookRequest {
+                to_user_id,
+                require_acceptance,
+            },
+        ) = value;
+        TransferBook {
+            book_id,
+            to_user: to_user_id,
+            require_acceptance,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+/// ユーザーが所有するすべての書籍を他のユーザーに譲渡するリクエスト
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TransferAllBooksRequest {
+    pub to_user_id: UserId,
+}
+
+#[derive(new)]
+pub struct TransferAllBooksRequestWithIds(UserId, UserId, TransferAllBooksRequest);
+
+impl From<TransferAllBooksRequestWithIds> for TransferAllBooks {
+    fn from(value: TransferAllBooksRequestWithIds) -> Self {
+        let TransferAllBooksRequestWithIds(
+            from_user,
+            requested_user,
+            TransferAllBooksRequest { to_user_id },
+        ) = value;
+        TransferAllBooks {
+            from_user,
+            to_user: to_user_id,
+            requested_user,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TransferAllBooksResponse {
+    /// 譲渡した書籍の数（削除済みの書籍を含む）
+    pub transferred: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookTransfersResponse {
+    pub items: Vec<BookTransferResponse>,
+}
+
+impl From<Vec<BookTransfer>> for BookTransfersResponse {
+    fn from(value: Vec<BookTransfer>) -> Self {
+        Self {
+            items: value.into_iter().map(BookTransferResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookTransferResponse {
+    pub id: BookTransferId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub from: BookOwner,
+    pub to: BookOwner,
+    pub requested_at: DateTime<Utc>,
+}
+
+impl From<BookTransfer> for BookTransferResponse {
+    fn from(value: BookTransfer) -> Self {
+        let BookTransfer {
+            id,
+            book_id,
+            book_title,
+            from,
+            to,
+            requested_at,
+        } = value;
+        Self {
+            id,
+            book_id,
+            book_title,
+            from: from.into(),
+            to: to.into(),
+            requested_at,
+        }
+    }
+}
@@ -1,13 +1,41 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
 #[cfg(debug_assertions)]
 use utoipa::ToSchema;
 
+use super::list::{CursorPaginatedResponse, default_limit};
+
 use kernel::model::{
-    checkout::{Checkout, CheckoutBook},
-    id::{BookId, CheckoutId, UserId},
+    checkout::{Checkout, CheckoutBook, CheckoutOutcome},
+    id::{BookId, CheckoutId, CopyId, UserId},
+    list::CursorOptions,
 };
 
+/// 貸出中の蔵書一覧のクエリ
+/// cursorに空文字を指定すると先頭から取得する。overdue=trueで返却期限を過ぎた貸出のみに絞り込む
+#[derive(Debug, Deserialize, Validate)]
+pub struct CheckoutListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(skip)]
+    pub cursor: Option<String>,
+    #[garde(skip)]
+    #[serde(default)]
+    pub overdue: bool,
+}
+
+impl From<CheckoutListQuery> for CursorOptions {
+    fn from(value: CheckoutListQuery) -> Self {
+        let CheckoutListQuery { limit, cursor, .. } = value;
+        Self {
+            limit,
+            cursor: cursor.filter(|c| !c.is_empty()),
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +51,14 @@ impl From<Vec<Checkout>> for CheckoutsResponse {
     }
 }
 
+/// 貸出中の蔵書一覧のレスポンス。ページネーションの方式に応じて形が変わる
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum CheckoutListResponse {
+    All(CheckoutsResponse),
+    Cursor(CursorPaginatedResponse<CheckoutResponse>),
+}
+
 #[derive(Serialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
@@ -30,24 +66,53 @@ pub struct CheckoutResponse {
     pub id: CheckoutId,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
+    /// 返却期限
+    pub due_at: DateTime<Utc>,
+    /// 延長した回数
+    pub renewal_count: i64,
     pub returned_at: Option<DateTime<Utc>>,
+    /// 貸出の終わり方。未返却の場合はnull
+    pub outcome: Option<CheckoutOutcomeName>,
     pub book: CheckoutBookResponse,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckoutOutcomeName {
+    Returned,
+    Lost,
+}
+
+impl From<CheckoutOutcome> for CheckoutOutcomeName {
+    fn from(value: CheckoutOutcome) -> Self {
+        match value {
+            CheckoutOutcome::Returned => Self::Returned,
+            CheckoutOutcome::Lost => Self::Lost,
+        }
+    }
+}
+
 impl From<Checkout> for CheckoutResponse {
     fn from(value: Checkout) -> Self {
         let Checkout {
             id,
             checked_out_by,
             checked_out_at,
+            due_at,
+            renewal_count,
             returned_at,
+            outcome,
             book,
         } = value;
         Self {
             id,
             checked_out_by,
             checked_out_at,
+            due_at,
+            renewal_count,
             returned_at,
+            outcome: outcome.map(CheckoutOutcomeName::from),
             book: book.into(),
         }
     }
@@ -61,6 +126,8 @@ pub struct CheckoutBookResponse {
     pub title: String,
     pub author: String,
     pub isbn: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
 }
 
 impl From<CheckoutBook> for CheckoutBookResponse {
@@ -70,6 +137,8 @@ impl From<CheckoutBook> for CheckoutBookResponse {
             title,
             author,
             isbn,
+            copy_id,
+            barcode,
         } = value;
 
         Self {
@@ -77,6 +146,8 @@ impl From<CheckoutBook> for CheckoutBookResponse {
             title,
             author,
             isbn,
+            copy_id,
+            barcode,
         }
     }
 }
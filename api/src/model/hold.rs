@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    hold::{Hold, HoldBook},
+    id::{BookId, HoldId},
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct HoldsResponse {
+    pub items: Vec<HoldResponse>,
+}
+
+impl From<Vec<Hold>> for HoldsResponse {
+    fn from(value: Vec<Hold>) -> Self {
+        Self {
+            items: value.into_iter().map(HoldResponse::from).collect(),
+        }
+    }
+}
+
+/// 予約の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum HoldStatusName {
+    /// 順番待ち
+    Waiting,
+    /// 取り置き中。受け取りの期限までは予約したユーザーのみが借りられる
+    Ready,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct HoldResponse {
+    pub id: HoldId,
+    pub book: HoldBookResponse,
+    /// 書籍の予約の中での順番（1始まり）。取り置き中の予約も含めて数える
+    pub position: i64,
+    pub status: HoldStatusName,
+    pub created_at: DateTime<Utc>,
+    /// 取り置きを始めた日時。順番待ちの間はnull
+    pub ready_at: Option<DateTime<Utc>>,
+    /// 受け取りの期限。順番待ちの間はnull
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<Hold> for HoldResponse {
+    fn from(value: Hold) -> Self {
+        let status = if value.is_ready() {
+            HoldStatusName::Ready
+        } else {
+            HoldStatusName::Waiting
+        };
+        let Hold {
+            id,
+            book,
+            position,
+            created_at,
+            ready_at,
+            expires_at,
+        } = value;
+        Self {
+            id,
+            book: book.into(),
+            position,
+            status,
+            created_at,
+            ready_at,
+            expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct HoldBookResponse {
+    pub id: BookId,
+    pub title: String,
+    pub author: String,
+}
+
+impl From<HoldBook> for HoldBookResponse {
+    fn from(value: HoldBook) -> Self {
+        let HoldBook { id, title, author } = value;
+        Self { id, title, author }
+    }
+}
@@ -0,0 +1,74 @@
+use garde::Validate;
+use kernel::model::list::{CursorOptions, CursorPaginatedList, SortOrder};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+/// クエリで昇順・降順を受け取るための型
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrderName {
+    Asc,
+    Desc,
+}
+
+impl From<SortOrderName> for SortOrder {
+    fn from(value: SortOrderName) -> Self {
+        match value {
+            SortOrderName::Asc => Self::Asc,
+            SortOrderName::Desc => Self::Desc,
+        }
+    }
+}
+
+/// クエリでカーソルによるページネーションの範囲を受け取るための構造体
+/// cursorに空文字を指定すると先頭から取得する
+#[derive(Debug, Deserialize, Validate)]
+pub struct CursorListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(skip)]
+    pub cursor: Option<String>,
+}
+
+const DEFAULT_LIMIT: i64 = 20;
+pub(crate) const fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+impl From<CursorListQuery> for CursorOptions {
+    fn from(value: CursorListQuery) -> Self {
+        let CursorListQuery { limit, cursor } = value;
+        Self {
+            limit,
+            cursor: cursor.filter(|c| !c.is_empty()),
+        }
+    }
+}
+
+/// apiレイヤーでのカーソルによるページネーション表現用の型
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginatedResponse<T> {
+    pub limit: i64,
+    /// 次のページを取得するためのカーソル。次のページがない場合はnull
+    pub next_cursor: Option<String>,
+    pub items: Vec<T>,
+}
+
+impl<T, U: From<T>> From<CursorPaginatedList<T>> for CursorPaginatedResponse<U> {
+    fn from(value: CursorPaginatedList<T>) -> Self {
+        let CursorPaginatedList {
+            items,
+            limit,
+            next_cursor,
+        } = value;
+        Self {
+            limit,
+            next_cursor,
+            items: items.into_iter().map(U::from).collect(),
+        }
+    }
+}
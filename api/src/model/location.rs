@@ -0,0 +1,103 @@
+use derive_new::new;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    id::LocationId,
+    location::{
+        Location,
+        event::{CreateLocation, UpdateLocation},
+    },
+};
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLocationRequest {
+    /// 拠点（建物など）
+    #[garde(length(min = 1))]
+    pub site: String,
+    #[garde(length(min = 1))]
+    pub room: String,
+    #[garde(length(min = 1))]
+    pub shelf: String,
+}
+
+impl From<CreateLocationRequest> for CreateLocation {
+    fn from(value: CreateLocationRequest) -> Self {
+        let CreateLocationRequest { site, room, shelf } = value;
+        CreateLocation { site, room, shelf }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLocationRequest {
+    #[garde(length(min = 1))]
+    pub site: String,
+    #[garde(length(min = 1))]
+    pub room: String,
+    #[garde(length(min = 1))]
+    pub shelf: String,
+}
+
+#[derive(new)]
+pub struct UpdateLocationRequestWithId(LocationId, UpdateLocationRequest);
+
+impl From<UpdateLocationRequestWithId> for UpdateLocation {
+    fn from(value: UpdateLocationRequestWithId) -> Self {
+        let UpdateLocationRequestWithId(location_id, UpdateLocationRequest { site, room, shelf }) =
+            value;
+        UpdateLocation {
+            location_id,
+            site,
+            room,
+            shelf,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LocationsResponse {
+    pub items: Vec<LocationResponse>,
+}
+
+impl From<Vec<Location>> for LocationsResponse {
+    fn from(value: Vec<Location>) -> Self {
+        Self {
+            items: value.into_iter().map(LocationResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LocationResponse {
+    pub id: LocationId,
+    pub site: String,
+    pub room: String,
+    pub shelf: String,
+}
+
+impl From<Location> for LocationResponse {
+    fn from(value: Location) -> Self {
+        let Location {
+            id,
+            site,
+            room,
+            shelf,
+        } = value;
+        Self {
+            id,
+            site,
+            room,
+            shelf,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! JSON Merge Patch（RFC 7396）形式のリクエストを受け取るための補助関数
+//! 項目が省略された場合はNone、nullの場合はSome(None)として区別する
+use serde::{Deserialize, Deserializer};
+
+/// 項目が存在する場合にSomeで包んでデシリアライズする
+/// `#[serde(default, deserialize_with = "patch_field")]`と組み合わせて使う
+pub fn patch_field<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 削除できない項目にnullが指定されていないことを検証する
+pub fn not_null<T>(value: &Option<Option<T>>, _: &()) -> garde::Result {
+    if matches!(value, Some(None)) {
+        return Err(garde::Error::new("cannot be null"));
+    }
+    Ok(())
+}
@@ -1,4 +1,20 @@
 pub mod auth;
 pub mod book;
+pub mod book_admin_action;
+pub mod book_copy_status;
+pub mod book_cover;
+pub mod book_export;
+pub mod book_import;
+pub mod book_revision;
+pub mod book_transfer;
 pub mod checkout;
+pub mod hold;
+pub mod list;
+pub mod location;
+pub mod merge_patch;
+pub mod notification;
+pub mod purchase_request;
+pub mod review;
+pub mod tag;
 pub mod user;
+pub mod wishlist;
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    id::{BookId, NotificationId},
+    notification::{Notification, NotificationKind},
+};
+
+/// 通知の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationKindName {
+    WishlistBookRegistered,
+    PurchaseRequestStatusChanged,
+    HoldReady,
+    HoldExpired,
+}
+
+impl From<NotificationKind> for NotificationKindName {
+    fn from(value: NotificationKind) -> Self {
+        match value {
+            NotificationKind::WishlistBookRegistered => Self::WishlistBookRegistered,
+            NotificationKind::PurchaseRequestStatusChanged => Self::PurchaseRequestStatusChanged,
+            NotificationKind::HoldReady => Self::HoldReady,
+            NotificationKind::HoldExpired => Self::HoldExpired,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsResponse {
+    pub items: Vec<NotificationResponse>,
+}
+
+impl From<Vec<Notification>> for NotificationsResponse {
+    fn from(value: Vec<Notification>) -> Self {
+        Self {
+            items: value.into_iter().map(NotificationResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationResponse {
+    pub id: NotificationId,
+    pub kind: NotificationKindName,
+    pub book_id: Option<BookId>,
+    pub message: String,
+    /// 既読にした日時。未読の場合はnull
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(value: Notification) -> Self {
+        let Notification {
+            id,
+            kind,
+            book_id,
+            message,
+            read_at,
+            created_at,
+        } = value;
+        Self {
+            id,
+            kind: kind.into(),
+            book_id,
+            message,
+            read_at,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    book::BookMetadata,
+    id::{BookId, PurchaseRequestId, UserId},
+    list::PaginatedList,
+    purchase_request::{
+        PurchaseRequest, PurchaseRequestListOptions, PurchaseRequestStatus,
+        event::{ChangePurchaseRequestStatus, CreatePurchaseRequest},
+    },
+};
+use shared::error::{AppError, AppResult};
+
+use super::{
+    book::{parse_isbn, validate_isbn},
+    list::default_limit,
+    user::PurchaseRequester,
+};
+
+/// 購入リクエストの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum PurchaseRequestStatusName {
+    Open,
+    Approved,
+    Ordered,
+    Received,
+    Rejected,
+}
+
+impl From<PurchaseRequestStatus> for PurchaseRequestStatusName {
+    fn from(value: PurchaseRequestStatus) -> Self {
+        match value {
+            PurchaseRequestStatus::Open => Self::Open,
+            PurchaseRequestStatus::Approved => Self::Approved,
+            PurchaseRequestStatus::Ordered => Self::Ordered,
+            PurchaseRequestStatus::Received => Self::Received,
+            PurchaseRequestStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+impl From<PurchaseRequestStatusName> for PurchaseRequestStatus {
+    fn from(value: PurchaseRequestStatusName) -> Self {
+        match value {
+            PurchaseRequestStatusName::Open => Self::Open,
+            PurchaseRequestStatusName::Approved => Self::Approved,
+            PurchaseRequestStatusName::Ordered => Self::Ordered,
+            PurchaseRequestStatusName::Received => Self::Received,
+            PurchaseRequestStatusName::Rejected => Self::Rejected,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePurchaseRequestRequest {
+    /// ISBN-10またはISBN-13（ハイフンの有無は問わない）
+    #[garde(custom(validate_isbn))]
+    pub isbn: String,
+    /// 省略した場合はISBNから取得した書誌情報で補う
+    #[garde(inner(length(min = 1)))]
+    pub title: Option<String>,
+    /// 省略した場合はISBNから取得した書誌情報で補う
+    #[garde(inner(length(min = 1)))]
+    pub author: Option<String>,
+    /// 購入してほしい理由
+    #[garde(length(min = 1))]
+    pub reason: String,
+    /// 書籍の紹介ページなどのURL
+    #[garde(inner(custom(validate_link)))]
+    pub link: Option<String>,
+}
+
+impl CreatePurchaseRequestRequest {
+    /// タイトルか著者が省略されており、書誌情報で補う必要があるかどうか
+    pub fn needs_metadata(&self) -> bool {
+        self.title.is_none() || self.author.is_none()
+    }
+
+    /// 省略された項目を書誌情報で補ってCreatePurchaseRequestに変換する
+    pub fn into_create_purchase_request(
+        self,
+        user_id: UserId,
+        metadata: Option<BookMetadata>,
+    ) -> AppResult<CreatePurchaseRequest> {
+        let CreatePurchaseRequestRequest {
+            isbn,
+            mut title,
+            mut author,
+            reason,
+            link,
+        } = self;
+        let isbn = parse_isbn(&isbn)?;
+
+        if let Some(metadata) = metadata {
+            title = title.or(Some(metadata.title));
+            author = author.or(metadata.author);
+        }
+
+        let (Some(title), Some(author)) = (title, author) else {
+            return Err(AppError::UnprocessableEntity(format!(
+                "title and author are required because no book metadata was found for ISBN {}",
+                isbn.as_isbn13()
+            )));
+        };
+
+        Ok(CreatePurchaseRequest {
+            requested_user: user_id,
+            isbn,
+            title,
+            author,
+            reason,
+            link,
+        })
+    }
+}
+
+/// リンクはhttpかhttpsのURLのみ受け付ける
+fn validate_link(value: &str, _: &()) -> garde::Result {
+    if value.starts_with("https://") || value.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(garde::Error::new("link must be an http or https url"))
+    }
+}
+
+/// 購入リクエストの状態を変えるリクエスト（管理者のみ）
+/// 受付中→承認済み・却下、承認済み→発注済み・却下、発注済み→受領済みの順にのみ変えられる
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePurchaseRequestStatusRequest {
+    pub status: PurchaseRequestStatusName,
+}
+
+#[derive(new)]
+pub struct ChangePurchaseRequestStatusRequestWithIds(
+    PurchaseRequestId,
+    UserId,
+    ChangePurchaseRequestStatusRequest,
+);
+
+impl From<ChangePurchaseRequestStatusRequestWithIds> for ChangePurchaseRequestStatus {
+    fn from(value: ChangePurchaseRequestStatusRequestWithIds) -> Self {
+        let ChangePurchaseRequestStatusRequestWithIds(
+            purchase_request_id,
+            user_id,
+            ChangePurchaseRequestStatusRequest { status },
+        ) = value;
+        ChangePurchaseRequestStatus {
+            purchase_request_id,
+            status: status.into(),
+            requested_user: user_id,
+        }
+    }
+}
+
+/// クエリで購入リクエスト一覧の絞り込みとページネーションの範囲を受け取るための構造体
+#[derive(Debug, Deserialize, Validate)]
+pub struct PurchaseRequestListQuery {
+    #[garde(skip)]
+    pub status: Option<PurchaseRequestStatusName>,
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl PurchaseRequestListQuery {
+    pub fn into_options(self, viewer: UserId) -> PurchaseRequestListOptions {
+        let PurchaseRequestListQuery {
+            status,
+            limit,
+            offset,
+        } = self;
+        PurchaseRequestListOptions {
+            status: status.map(PurchaseRequestStatus::from),
+            viewer,
+            limit,
+            offset,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseRequestResponse {
+    pub id: PurchaseRequestId,
+    /// リクエストしたユーザー。ユーザーが削除済みの場合はnull
+    pub requested_by: Option<PurchaseRequester>,
+    /// 表示用のISBN
+    pub isbn: String,
+    /// ハイフンなしのISBN-13
+    pub isbn13: String,
+    pub title: String,
+    pub author: String,
+    pub reason: String,
+    pub link: Option<String>,
+    pub status: PurchaseRequestStatusName,
+    /// 賛成票の数
+    pub votes: i64,
+    /// ログイン中のユーザーが賛成票を入れているかどうか
+    pub voted: bool,
+    /// 受領して登録した書籍のID。受領前の場合はnull
+    pub book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub ordered_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl From<PurchaseRequest> for PurchaseRequestResponse {
+    fn from(value: PurchaseRequest) -> Self {
+        let PurchaseRequest {
+            id,
+            requested_by,
+            isbn,
+            title,
+            author,
+            reason,
+            link,
+            status,
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        } = value;
+        Self {
+            id,
+            requested_by: requested_by.map(PurchaseRequester::from),
+            isbn: isbn.display().to_string(),
+            isbn13: isbn.as_isbn13().to_string(),
+            title,
+            author,
+            reason,
+            link,
+            status: status.into(),
+            votes,
+            voted,
+            book_id,
+            created_at,
+            approved_at,
+            ordered_at,
+            received_at,
+            rejected_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedPurchaseRequestResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<PurchaseRequestResponse>,
+}
+
+impl From<PaginatedList<PurchaseRequest>> for PaginatedPurchaseRequestResponse {
+    fn from(value: PaginatedList<PurchaseRequest>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = value;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items
+                .into_iter()
+                .map(PurchaseRequestResponse::from)
+                .collect(),
+        }
+    }
+}
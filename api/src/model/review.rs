@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    id::{BookId, ReviewId, UserId},
+    list::PaginatedList,
+    review::{
+        Review, ReviewListOptions,
+        event::{CreateReview, UpdateReview},
+    },
+};
+
+use super::{list::default_limit, user::Reviewer};
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewRequest {
+    /// 1〜5の評価
+    #[garde(range(min = 1, max = 5))]
+    pub rating: i16,
+    /// 評価に添える感想。評価のみの場合は省略する
+    #[garde(length(min = 1))]
+    pub comment: Option<String>,
+}
+
+#[derive(new)]
+pub struct CreateReviewRequestWithIds(BookId, UserId, CreateReviewRequest);
+
+impl From<CreateReviewRequestWithIds> for CreateReview {
+    fn from(value: CreateReviewRequestWithIds) -> Self {
+        let CreateReviewRequestWithIds(book_id, user_id, CreateReviewRequest { rating, comment }) =
+            value;
+        CreateReview {
+            book_id,
+            rating,
+            comment,
+            requested_user: user_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReviewRequest {
+    #[garde(range(min = 1, max = 5))]
+    pub rating: i16,
+    #[garde(length(min = 1))]
+    pub comment: Option<String>,
+}
+
+#[derive(new)]
+pub struct UpdateReviewRequestWithIds(BookId, ReviewId, UserId, bool, UpdateReviewRequest);
+
+impl From<UpdateReviewRequestWithIds> for UpdateReview {
+    fn from(value: UpdateReviewRequestWithIds) -> Self {
+        let UpdateReviewRequestWithIds(
+            book_id,
+            review_id,
+            user_id,
+            is_admin,
+            UpdateReviewRequest { rating, comment },
+        ) = value;
+        UpdateReview {
+            book_id,
+            review_id,
+            rating,
+            comment,
+            requested_user: user_id,
+            requested_by_admin: is_admin,
+        }
+    }
+}
+
+/// クエリでレビュー一覧のlimitとoffsetを受け取るための構造体
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReviewListQuery {
+    #[garde(range(min = 0))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl From<ReviewListQuery> for ReviewListOptions {
+    fn from(value: ReviewListQuery) -> Self {
+        let ReviewListQuery { limit, offset } = value;
+        Self { limit, offset }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewResponse {
+    pub id: ReviewId,
+    pub book_id: BookId,
+    pub reviewer: Reviewer,
+    pub rating: i16,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Review> for ReviewResponse {
+    fn from(value: Review) -> Self {
+        let Review {
+            id,
+            book_id,
+            reviewer,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        Self {
+            id,
+            book_id,
+            reviewer: reviewer.into(),
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedReviewResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<ReviewResponse>,
+}
+
+impl From<PaginatedList<Review>> for PaginatedReviewResponse {
+    fn from(value: PaginatedList<Review>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = value;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(ReviewResponse::from).collect(),
+        }
+    }
+}
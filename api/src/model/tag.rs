@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::book::TagCount;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TagsResponse {
+    pub items: Vec<TagResponse>,
+}
+
+impl From<Vec<TagCount>> for TagsResponse {
+    fn from(value: Vec<TagCount>) -> Self {
+        Self {
+            items: value.into_iter().map(TagResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TagResponse {
+    pub name: String,
+    /// タグが付いた書籍の数
+    pub book_count: i64,
+}
+
+impl From<TagCount> for TagResponse {
+    fn from(value: TagCount) -> Self {
+        let TagCount { name, book_count } = value;
+        Self { name, book_count }
+    }
+}
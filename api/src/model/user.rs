@@ -7,7 +7,7 @@ use kernel::model::{
     role::Role,
     user::{
         User,
-        event::{CreateUser, UpdateUserPassword, UpdateUserRole},
+        event::{CreateUser, UpdateUserPassword, UpdateUserProfile, UpdateUserRole},
     },
 };
 use serde::{Deserialize, Serialize};
@@ -15,6 +15,11 @@ use strum::VariantNames;
 #[cfg(debug_assertions)]
 use utoipa::ToSchema;
 
+use super::{
+    list::CursorPaginatedResponse,
+    merge_patch::{not_null, patch_field},
+};
+
 #[derive(Serialize, Deserialize, VariantNames)]
 #[strum(serialize_all = "kebab-case")]
 pub enum RoleName {
@@ -46,6 +51,14 @@ pub struct UsersResponse {
     pub users: Vec<UserResponse>,
 }
 
+/// ユーザー一覧のレスポンス。ページネーションの方式に応じて形が変わる
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum UserListResponse {
+    All(UsersResponse),
+    Cursor(CursorPaginatedResponse<UserResponse>),
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserResponse {
@@ -72,6 +85,33 @@ impl From<User> for UserResponse {
     }
 }
 
+/// ユーザーの名前とメールアドレスの部分更新のリクエスト（JSON Merge Patch）
+/// 省略した項目は変更しない。どちらの項目もnullにはできない
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchUserRequest {
+    #[garde(custom(not_null), inner(inner(length(min = 1))))]
+    #[serde(default, deserialize_with = "patch_field")]
+    name: Option<Option<String>>,
+    #[garde(custom(not_null), inner(inner(email)))]
+    #[serde(default, deserialize_with = "patch_field")]
+    email: Option<Option<String>>,
+}
+
+#[derive(new)]
+pub struct PatchUserRequestWithUserId(UserId, PatchUserRequest);
+
+impl From<PatchUserRequestWithUserId> for UpdateUserProfile {
+    fn from(value: PatchUserRequestWithUserId) -> Self {
+        let PatchUserRequestWithUserId(user_id, PatchUserRequest { name, email }) = value;
+        Self {
+            id: user_id,
+            name: name.flatten(),
+            email: email.flatten(),
+        }
+    }
+}
+
 #[derive(Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateUserPasswordRequest {
@@ -177,3 +217,48 @@ impl From<kernel::model::user::CheckoutUser> for CheckoutUser {
         Self { id, name }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookEditor {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::BookEditor> for BookEditor {
+    fn from(value: kernel::model::user::BookEditor) -> Self {
+        let kernel::model::user::BookEditor { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Reviewer {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::Reviewer> for Reviewer {
+    fn from(value: kernel::model::user::Reviewer) -> Self {
+        let kernel::model::user::Reviewer { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseRequester {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::PurchaseRequester> for PurchaseRequester {
+    fn from(value: kernel::model::user::PurchaseRequester) -> Self {
+        let kernel::model::user::PurchaseRequester { id, name } = value;
+        Self { id, name }
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use kernel::model::{
+    id::{BookId, UserId, WishlistItemId},
+    wishlist::{WishlistItem, event::CreateWishlistItem},
+};
+use shared::error::{AppError, AppResult};
+
+use super::book::parse_isbn;
+
+/// ほしいものリストに項目を追加するリクエスト
+/// ISBNと書名の少なくともどちらかを指定する
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWishlistItemRequest {
+    /// ISBN-10またはISBN-13（ハイフンの有無は問わない）
+    #[garde(skip)]
+    pub isbn: Option<String>,
+    /// 書名などの自由記述
+    #[garde(length(min = 1))]
+    pub title: Option<String>,
+}
+
+#[derive(new)]
+pub struct CreateWishlistItemRequestWithUserId(UserId, CreateWishlistItemRequest);
+
+impl TryFrom<CreateWishlistItemRequestWithUserId> for CreateWishlistItem {
+    type Error = AppError;
+
+    fn try_from(value: CreateWishlistItemRequestWithUserId) -> AppResult<Self> {
+        let CreateWishlistItemRequestWithUserId(user_id, CreateWishlistItemRequest { isbn, title }) =
+            value;
+        if isbn.is_none() && title.is_none() {
+            let mut report = garde::Report::new();
+            report.append(
+                garde::Path::new("isbn"),
+                garde::Error::new("either isbn or title is required"),
+            );
+            return Err(AppError::ValidationError(report));
+        }
+        Ok(CreateWishlistItem {
+            user_id,
+            isbn: isbn.as_deref().map(parse_isbn).transpose()?,
+            title,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WishlistItemsResponse {
+    pub items: Vec<WishlistItemResponse>,
+}
+
+impl From<Vec<WishlistItem>> for WishlistItemsResponse {
+    fn from(value: Vec<WishlistItem>) -> Self {
+        Self {
+            items: value.into_iter().map(WishlistItemResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WishlistItemResponse {
+    pub id: WishlistItemId,
+    /// 表示用のISBN。ISBNを指定していない場合はnull
+    pub isbn: Option<String>,
+    /// ハイフンなしのISBN-13。ISBNを指定していない場合はnull
+    pub isbn13: Option<String>,
+    pub title: Option<String>,
+    /// ISBNが一致する書籍が登録された場合の、その書籍のID
+    pub fulfilled_book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WishlistItem> for WishlistItemResponse {
+    fn from(value: WishlistItem) -> Self {
+        let WishlistItem {
+            id,
+            isbn,
+            title,
+            fulfilled_book_id,
+            created_at,
+        } = value;
+        Self {
+            id,
+            isbn: isbn.as_ref().map(|i| i.display().to_string()),
+            isbn13: isbn.as_ref().map(|i| i.as_isbn13().to_string()),
+            title,
+            fulfilled_book_id,
+            created_at,
+        }
+    }
+}
@@ -18,6 +18,47 @@ use crate::{handler, model};
         // handler::health::health_check_db,
         handler::book::register_book,
         handler::book::show_book_list,
+        handler::book::show_books_by_isbn,
+        handler::book::import_books,
+        handler::book::export_books,
+        handler::book::patch_book,
+        handler::book::show_book_revisions,
+        handler::book::revert_book_revision,
+        handler::book::change_copy_status,
+        handler::book::show_copy_status_history,
+        handler::book::move_book,
+        handler::book::set_book_loan_period,
+        handler::book::transfer_book,
+        handler::book::accept_book_transfer,
+        handler::book::decline_book_transfer,
+        handler::book::show_deleted_book_list,
+        handler::book::restore_book,
+        handler::book::purge_book,
+        handler::book::add_book_copy,
+        handler::book::delete_book_copy,
+        handler::book::add_book_tags,
+        handler::book::remove_book_tag,
+        handler::book::upload_book_cover,
+        handler::book::show_book_cover,
+        handler::book::show_book_cover_thumbnail,
+        handler::review::register_review,
+        handler::review::show_review_list,
+        handler::review::update_review,
+        handler::review::delete_review,
+        handler::tag::list_tags,
+        handler::location::list_locations,
+        handler::location::register_location,
+        handler::location::update_location,
+        handler::location::delete_location,
+        handler::purchase_request::register_purchase_request,
+        handler::purchase_request::show_purchase_request_list,
+        handler::purchase_request::show_purchase_request,
+        handler::purchase_request::vote_purchase_request,
+        handler::purchase_request::unvote_purchase_request,
+        handler::purchase_request::change_purchase_request_status,
+        handler::hold::place_hold,
+        handler::hold::get_holds,
+        handler::hold::cancel_hold,
         // handler::book::show_book,
         // handler::book::update_book,
         // handler::book::delete_book,
@@ -32,14 +73,66 @@ use crate::{handler, model};
     components(schemas(
         model::book::CreateBookRequest,
         model::book::UpdateBookRequest,
+        model::book::PatchBookRequest,
         model::book::BookResponse,
         model::book::PaginatedBookResponse,
+        model::book::BooksResponse,
+        model::book::BookCopyResponse,
+        model::book::CreateBookCopyRequest,
         model::book::BookCheckoutResponse,
+        model::book_copy_status::CopyStatusName,
+        model::book_copy_status::CopyStatusActionName,
+        model::book_copy_status::ChangeCopyStatusRequest,
+        model::book_copy_status::CopyStatusChangesResponse,
+        model::book_copy_status::CopyStatusChangeResponse,
+        model::book::AddBookTagsRequest,
+        model::book::MoveBookRequest,
+        model::book::SetBookLoanPeriodRequest,
+        model::book::DeletedBookResponse,
+        model::book::PaginatedDeletedBookResponse,
+        model::location::CreateLocationRequest,
+        model::location::UpdateLocationRequest,
+        model::location::LocationsResponse,
+        model::location::LocationResponse,
+        model::purchase_request::PurchaseRequestStatusName,
+        model::purchase_request::CreatePurchaseRequestRequest,
+        model::purchase_request::ChangePurchaseRequestStatusRequest,
+        model::purchase_request::PurchaseRequestResponse,
+        model::purchase_request::PaginatedPurchaseRequestResponse,
+        model::hold::HoldsResponse,
+        model::hold::HoldResponse,
+        model::hold::HoldStatusName,
+        model::hold::HoldBookResponse,
+        model::review::CreateReviewRequest,
+        model::review::UpdateReviewRequest,
+        model::review::ReviewResponse,
+        model::review::PaginatedReviewResponse,
+        model::tag::TagsResponse,
+        model::tag::TagResponse,
+        model::book_export::BookExportRecord,
+        model::book_import::BookImportReportResponse,
+        model::book_import::BookImportRowResponse,
+        model::book_import::BookImportStatusName,
+        model::book_revision::BookRevisionsResponse,
+        model::book_revision::BookRevisionResponse,
+        model::book_revision::BookSnapshotResponse,
+        model::book_transfer::TransferBookRequest,
+        model::book_transfer::BookTransferResponse,
         model::checkout::CheckoutsResponse,
         model::checkout::CheckoutResponse,
         model::checkout::CheckoutBookResponse,
+        model::checkout::CheckoutOutcomeName,
         model::user::BookOwner,
         model::user::CheckoutUser,
+        model::user::BookEditor,
+        model::user::Reviewer,
+        model::user::PurchaseRequester,
+        model::wishlist::CreateWishlistItemRequest,
+        model::wishlist::WishlistItemsResponse,
+        model::wishlist::WishlistItemResponse,
+        model::notification::NotificationKindName,
+        model::notification::NotificationsResponse,
+        model::notification::NotificationResponse,
         model::auth::LoginRequest,
         model::auth::AccessTokenResponse,
     ))
@@ -1,12 +1,29 @@
 use axum::{
     Router,
-    routing::{delete, get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
 };
 use registry::AppRegistry;
 
-use crate::handler::{
-    book::{delete_book, register_book, show_book, show_book_list, update_book},
-    checkout::{checkout_book, checkout_history, return_book, show_checked_out_list},
+use crate::{
+    handler::{
+        book::{
+            accept_book_transfer, add_book_copy, add_book_tags, change_copy_status,
+            decline_book_transfer, delete_book, delete_book_copy, export_books, import_books,
+            move_book, patch_book, purge_book, register_book, remove_book_tag, restore_book,
+            revert_book_revision, set_book_loan_period, show_book, show_book_cover,
+            show_book_cover_thumbnail, show_book_list, show_book_revisions, show_books_by_isbn,
+            show_copy_status_history, show_deleted_book_list, transfer_book, update_book,
+            upload_book_cover,
+        },
+        checkout::{
+            checkout_book, checkout_book_copy, checkout_history, renew_checkout, return_book,
+            show_checked_out_list,
+        },
+        hold::place_hold,
+        review::{delete_review, register_review, show_review_list, update_review},
+    },
+    model::book_cover::MAX_COVER_REQUEST_SIZE,
 };
 
 /// 書籍関連のルータを作成する関数
@@ -14,18 +31,73 @@ pub fn build_book_routers() -> Router<AppRegistry> {
     let books_routers = Router::new()
         .route("/", post(register_book))
         .route("/", get(show_book_list))
+        .route("/import", post(import_books))
+        .route("/export", get(export_books))
+        .route("/isbn/{isbn}", get(show_books_by_isbn))
+        .route("/deleted", get(show_deleted_book_list))
         .route("/{book_id}", get(show_book))
         .route("/{book_id}", put(update_book))
-        .route("/{book_id}", delete(delete_book));
+        .route("/{book_id}", patch(patch_book))
+        .route("/{book_id}", delete(delete_book))
+        .route("/{book_id}/revisions", get(show_book_revisions))
+        .route(
+            "/{book_id}/revisions/{revision_id}/revert",
+            post(revert_book_revision),
+        )
+        .route("/{book_id}/location", put(move_book))
+        .route("/{book_id}/loan-period", put(set_book_loan_period))
+        .route("/{book_id}/transfer", post(transfer_book))
+        .route("/{book_id}/transfer/accept", post(accept_book_transfer))
+        .route("/{book_id}/transfer/decline", post(decline_book_transfer))
+        .route("/{book_id}/restore", post(restore_book))
+        .route("/{book_id}/purge", delete(purge_book))
+        .route("/{book_id}/copies", post(add_book_copy))
+        .route("/{book_id}/copies/{copy_id}", delete(delete_book_copy))
+        .route(
+            "/{book_id}/copies/{copy_id}/status-changes",
+            get(show_copy_status_history).post(change_copy_status),
+        )
+        .route("/{book_id}/tags", post(add_book_tags))
+        .route("/{book_id}/tags/{tag}", delete(remove_book_tag))
+        .route(
+            "/{book_id}/cover",
+            // 書影の画像はaxumのデフォルトの上限（2MB）より大きいものも受け付ける
+            put(upload_book_cover)
+                .layer(DefaultBodyLimit::max(MAX_COVER_REQUEST_SIZE))
+                .get(show_book_cover),
+        )
+        .route("/{book_id}/cover/thumbnail", get(show_book_cover_thumbnail));
 
     let checkout_router = Router::new()
         .route("/checkouts", get(show_checked_out_list))
         .route("/{book_id}/checkouts", post(checkout_book))
+        .route(
+            "/{book_id}/copies/{copy_id}/checkouts",
+            post(checkout_book_copy),
+        )
         .route(
             "/{book_id}/checkouts/{checkout_id}/returned",
             put(return_book),
         )
-        .route("/{book_id}/checkout-history", get(checkout_history));
+        .route(
+            "/{book_id}/checkouts/{checkout_id}/renew",
+            post(renew_checkout),
+        )
+        .route("/{book_id}/checkout-history", get(checkout_history))
+        .route("/{book_id}/holds", post(place_hold));
+
+    let review_router = Router::new()
+        .route(
+            "/{book_id}/reviews",
+            get(show_review_list).post(register_review),
+        )
+        .route(
+            "/{book_id}/reviews/{review_id}",
+            put(update_review).delete(delete_review),
+        );
 
-    Router::new().nest("/books", books_routers.merge(checkout_router))
+    Router::new().nest(
+        "/books",
+        books_routers.merge(checkout_router).merge(review_router),
+    )
 }
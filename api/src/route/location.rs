@@ -0,0 +1,19 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+use registry::AppRegistry;
+
+use crate::handler::location::{
+    delete_location, list_locations, register_location, update_location,
+};
+
+/// 置き場所関連のルータを作成する関数
+pub fn build_location_routers() -> Router<AppRegistry> {
+    Router::new()
+        .route("/locations", get(list_locations).post(register_location))
+        .route(
+            "/locations/{location_id}",
+            put(update_location).delete(delete_location),
+        )
+}
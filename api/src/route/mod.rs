@@ -1,5 +1,8 @@
 pub mod auth;
 pub mod book;
 pub mod health;
+pub mod location;
+pub mod purchase_request;
+pub mod tag;
 pub mod user;
 pub mod v1;
@@ -0,0 +1,30 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+use registry::AppRegistry;
+
+use crate::handler::purchase_request::{
+    change_purchase_request_status, register_purchase_request, show_purchase_request,
+    show_purchase_request_list, unvote_purchase_request, vote_purchase_request,
+};
+
+/// 購入リクエスト関連のルータを作成する関数
+pub fn build_purchase_request_routers() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route(
+            "/",
+            get(show_purchase_request_list).post(register_purchase_request),
+        )
+        .route("/{purchase_request_id}", get(show_purchase_request))
+        .route(
+            "/{purchase_request_id}/vote",
+            put(vote_purchase_request).delete(unvote_purchase_request),
+        )
+        .route(
+            "/{purchase_request_id}/status",
+            put(change_purchase_request_status),
+        );
+
+    Router::new().nest("/purchase-requests", routers)
+}
@@ -0,0 +1,9 @@
+use axum::{Router, routing::get};
+use registry::AppRegistry;
+
+use crate::handler::tag::list_tags;
+
+/// タグ関連のルータを作成する関数
+pub fn build_tag_routers() -> Router<AppRegistry> {
+    Router::new().route("/tags", get(list_tags))
+}
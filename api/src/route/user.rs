@@ -1,22 +1,50 @@
 use axum::{
     Router,
-    routing::{delete, get, put},
+    routing::{delete, get, post, put},
 };
 
 use registry::AppRegistry;
 
-use crate::handler::user::{
-    delete_user, get_checkouts, get_current_user, list_users, register_user, update_user_password,
-    update_user_role,
+use crate::handler::{
+    hold::{cancel_hold, get_holds},
+    user::{
+        add_favorite, add_wishlist_item, delete_user, delete_wishlist_item, get_book_admin_actions,
+        get_book_transfers, get_checkouts, get_current_user, get_favorites, get_notifications,
+        get_wishlist, list_users, patch_current_user, read_notification, register_user,
+        remove_favorite, transfer_user_books, update_user_password, update_user_role,
+    },
 };
 
 /// ユーザー関連のルータを作成する関数
 pub fn build_user_routers() -> Router<AppRegistry> {
     Router::new()
-        .route("/users/me", get(get_current_user))
+        .route("/users/me", get(get_current_user).patch(patch_current_user))
         .route("/users/me/password", put(update_user_password))
         .route("/users", get(list_users).post(register_user))
         .route("/users/{user_id}", delete(delete_user))
         .route("/users/{user_id}/role", put(update_user_role))
+        .route("/users/{user_id}/books/transfer", post(transfer_user_books))
         .route("/users/me/checkouts", get(get_checkouts))
+        .route("/users/me/book-admin-actions", get(get_book_admin_actions))
+        .route("/users/me/book-transfers", get(get_book_transfers))
+        .route("/users/me/favorites", get(get_favorites))
+        .route(
+            "/users/me/favorites/{book_id}",
+            put(add_favorite).delete(remove_favorite),
+        )
+        .route(
+            "/users/me/wishlist",
+            get(get_wishlist).post(add_wishlist_item),
+        )
+        .route(
+            "/users/me/wishlist/{wishlist_item_id}",
+            delete(delete_wishlist_item),
+        )
+        .route("/users/me/holds", get(get_holds))
+        .route("/users/me/holds/{hold_id}", delete(cancel_hold))
+        .route("/users/me/notifications", get(get_notifications))
+        .route(
+            "/users/me/notifications/{notification_id}/read",
+            put(read_notification),
+        )
 }
@@ -2,7 +2,9 @@ use axum::Router;
 use registry::AppRegistry;
 
 use super::{
-    book::build_book_routers, health::build_health_check_routers, user::build_user_routers,
+    book::build_book_routers, health::build_health_check_routers, location::build_location_routers,
+    purchase_request::build_purchase_request_routers, tag::build_tag_routers,
+    user::build_user_routers,
 };
 
 /// v1 APIのルータを構築する関数
@@ -10,6 +12,9 @@ pub fn routes() -> Router<AppRegistry> {
     let router = Router::new()
         .merge(build_book_routers())
         .merge(build_health_check_routers())
+        .merge(build_location_routers())
+        .merge(build_purchase_request_routers())
+        .merge(build_tag_routers())
         .merge(build_user_routers());
 
     Router::new().nest("/api/v1", router)
@@ -1,4 +1,5 @@
 use axum::{body::Body, http::Request};
+use chrono::NaiveDate;
 use rstest::rstest;
 use std::sync::Arc;
 use tower::ServiceExt;
@@ -8,15 +9,23 @@ use crate::{
     helper::{TestRequestExt, fixture, make_router, v1},
 };
 
-use api::model::book::PaginatedBookResponse;
+use api::model::{
+    book::{BookResponse, BooksResponse, PaginatedBookResponse},
+    list::CursorPaginatedResponse,
+    tag::TagsResponse,
+};
 use kernel::{
     model::{
-        book::Book,
-        id::{BookId, UserId},
-        list::PaginatedList,
+        book::{Book, BookAvailability, BookMetadata, BookSort, BookSortKey, TagCount},
+        id::{BookId, CopyId, LocationId, UserId},
+        isbn::Isbn,
+        list::{CursorPaginatedList, PaginatedList, SortOrder},
         user::BookOwner,
     },
-    repository::book::MockBookRepository,
+    repository::{
+        book::MockBookRepository, book_metadata::MockBookMetadataProvider,
+        checkout::MockCheckoutRepository,
+    },
 };
 
 /// ・リクエストパスに応じて、期待している関数が返されることの確認
@@ -46,14 +55,23 @@ async fn test_show_book_list_with_query_200(
             let items = vec![Book {
                 id: book_id,
                 title: "Test Book".to_string(),
-                isbn: "".to_string(),
+                isbn: Isbn::from_stored("".into(), "".into()),
                 author: "Test Author".to_string(),
                 description: "Test Description".to_string(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
                 owner: BookOwner {
                     id: UserId::new(),
                     name: "Test User".to_string(),
                 },
-                checkout: None,
+                copies: vec![],
+                tags: vec![],
+                cover: None,
+                version: 1,
+                location: None,
+                rating: Default::default(),
+                loan_period_days: None,
             }];
             Ok(PaginatedList {
                 total: 1,
@@ -69,7 +87,7 @@ async fn test_show_book_list_with_query_200(
     let router: axum::Router = make_router(fixture);
 
     // リクエストを作成・送信し、レスポンスを検証
-    let request = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
     let resp = router.oneshot(request).await?;
     assert_eq!(resp.status(), axum::http::StatusCode::OK);
 
@@ -87,6 +105,12 @@ async fn test_show_book_list_with_query_200(
 #[case("/books?offset=-10")]
 #[case("/books?limit=abc")]
 #[case("/books?offset=xyz")]
+#[case("/books?q=")]
+#[case("/books?availability=unknown")]
+#[case("/books?sort=popularity")]
+#[case("/books?order=up")]
+#[case("/books?tag=")]
+#[case("/books?cursor=abc&offset=10")]
 #[tokio::test]
 async fn test_show_book_list_with_query_400(
     mut fixture: registry::MockAppRegistryExt,
@@ -101,9 +125,587 @@ async fn test_show_book_list_with_query_400(
 
     let router: axum::Router = make_router(fixture);
 
-    let request = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// 検索・絞り込みのクエリパラメータがBookListOptionsに渡されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_list_with_filters_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let owner_id = UserId::new();
+    let location_id = LocationId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |options| {
+                options.q.as_deref() == Some("rust")
+                    && options.author.as_deref() == Some("Tanaka")
+                    && options.owner == Some(owner_id)
+                    && options.location == Some(location_id)
+                    && options.availability == Some(BookAvailability::CheckedOut)
+            })
+            .returning(|options| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books?q=rust&author=Tanaka&owner={owner_id}&location={location_id}&availability=checked-out"
+    );
+    let request = Request::get(v1(&path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// 複数のtagクエリパラメータが正規化されてBookListOptionsに渡されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_list_with_tags_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(|options| options.tags == vec!["rust".to_string(), "async".to_string()])
+            .returning(|options| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/books?tag=Rust&tag=async&tag=%20rust"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// 並び順のクエリパラメータがBookListOptionsに渡されることの確認
+#[rstest]
+#[case("/books", None)]
+#[case("/books?sort=title", Some((BookSortKey::Title, SortOrder::Asc)))]
+#[case("/books?sort=most-borrowed", Some((BookSortKey::MostBorrowed, SortOrder::Desc)))]
+#[case("/books?sort=rating", Some((BookSortKey::Rating, SortOrder::Desc)))]
+#[case("/books?sort=updated-at&order=asc", Some((BookSortKey::UpdatedAt, SortOrder::Asc)))]
+#[case("/books?order=asc", Some((BookSortKey::CreatedAt, SortOrder::Asc)))]
+#[tokio::test]
+async fn test_show_book_list_with_sort_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected: Option<(BookSortKey, SortOrder)>,
+) -> anyhow::Result<()> {
+    let expected = expected.map(|(key, order)| BookSort { key, order });
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |options| options.sort == expected)
+            .returning(|options| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// cursorが指定された場合にカーソルによるページネーションが行われることの確認
+#[rstest]
+#[case("/books?cursor=", None)]
+#[case("/books?cursor=abc&limit=5", Some("abc"))]
+#[tokio::test]
+async fn test_show_book_list_with_cursor_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_cursor: Option<&'static str>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all().never();
+        mock.expect_find_all_by_cursor()
+            .withf(move |_, cursor| cursor.as_deref() == expected_cursor)
+            .returning(|options, _| {
+                Ok(CursorPaginatedList {
+                    items: vec![],
+                    limit: options.limit,
+                    next_cursor: Some("next".into()),
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, CursorPaginatedResponse<BookResponse>);
+    assert_eq!(result.next_cursor.as_deref(), Some("next"));
+
+    Ok(())
+}
+
+/// ISBNが正規化されてリポジトリに渡されることの確認
+#[rstest]
+#[case("978-4-7980-6170-2")]
+#[case("9784798061702")]
+#[case("4-7980-6170-0")]
+#[tokio::test]
+async fn test_register_book_201(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .withf(|event, _| event.isbn.as_isbn13() == "9784798061702")
+            .returning(|_, _| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "Test Title",
+        "author": "Test Author",
+        "isbn": isbn,
+        "description": "",
+    });
+    let request = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+/// タイトルや著者を省略した場合に、ISBNから取得した書誌情報で補われることの確認
+/// リクエストで指定した項目は書誌情報より優先される
+#[rstest]
+#[case(serde_json::json!({ "isbn": "978-4-7980-6170-2" }), "Metadata Title")]
+#[case(serde_json::json!({ "isbn": "978-4-7980-6170-2", "title": "My Title" }), "My Title")]
+#[tokio::test]
+async fn test_register_book_with_metadata_201(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: serde_json::Value,
+    #[case] expected_title: &'static str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn()
+            .withf(|isbn| isbn.as_isbn13() == "9784798061702")
+            .returning(|_| {
+                Ok(Some(BookMetadata {
+                    title: "Metadata Title".into(),
+                    author: Some("Metadata Author".into()),
+                    publisher: Some("Metadata Publisher".into()),
+                    published_on: NaiveDate::from_ymd_opt(2020, 8, 27),
+                    page_count: Some(548),
+                }))
+            });
+        Arc::new(mock)
+    });
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create()
+            .withf(move |event, _| {
+                event.title == expected_title
+                    && event.author == "Metadata Author"
+                    && event.publisher.as_deref() == Some("Metadata Publisher")
+                    && event.published_on == NaiveDate::from_ymd_opt(2020, 8, 27)
+                    && event.page_count == Some(548)
+            })
+            .returning(|_, _| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+/// タイトルや著者を省略し、書誌情報も見つからない場合に422が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_register_book_without_metadata_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({ "isbn": "978-4-7980-6170-2", "title": "Only Title" });
+    let request = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+/// 不正なISBNの場合に、isbnフィールドのエラーとして400が返ることの確認
+#[rstest]
+#[case("")]
+#[case("978-4-7980-6170-3")]
+#[case("abcdefghij")]
+#[tokio::test]
+async fn test_register_book_with_invalid_isbn_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_create().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "Test Title",
+        "author": "Test Author",
+        "isbn": isbn,
+        "description": "",
+    });
+    let request = Request::post(v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
     let resp = router.oneshot(request).await?;
     assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
 
+    let result = deserialize_json!(resp, serde_json::Value);
+    assert_eq!(result["errors"][0]["field"], "isbn");
+
+    Ok(())
+}
+
+/// スキャナから読み取ったISBNが正規化されてリポジトリに渡されることの確認
+#[rstest]
+#[case("/books/isbn/9784798061702")]
+#[case("/books/isbn/978-4-7980-6170-2")]
+#[case("/books/isbn/4798061700")]
+#[tokio::test]
+async fn test_show_books_by_isbn_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_isbn()
+            .withf(|isbn| isbn.as_isbn13() == "9784798061702")
+            .returning(|isbn| {
+                Ok(vec![Book {
+                    id: BookId::new(),
+                    title: "Test Book".to_string(),
+                    isbn,
+                    author: "Test Author".to_string(),
+                    description: "Test Description".to_string(),
+                    publisher: None,
+                    published_on: None,
+                    page_count: None,
+                    owner: BookOwner {
+                        id: UserId::new(),
+                        name: "Test User".to_string(),
+                    },
+                    copies: vec![],
+                    tags: vec![],
+                    cover: None,
+                    version: 1,
+                    location: None,
+                    rating: Default::default(),
+                    loan_period_days: None,
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BooksResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].isbn13, "9784798061702");
+
+    Ok(())
+}
+
+/// 不正なISBNの場合に400が返ることの確認
+#[rstest]
+#[case("/books/isbn/9784798061703")]
+#[case("/books/isbn/123")]
+#[tokio::test]
+async fn test_show_books_by_isbn_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_isbn().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// リクエストボディを省略した場合は、バーコードを指定せずに蔵書が追加されることの確認
+#[rstest]
+#[case(None, None)]
+#[case(Some(r#"{"barcode":"T00000010"}"#), Some("T00000010"))]
+#[tokio::test]
+async fn test_add_book_copy_201(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: Option<&'static str>,
+    #[case] expected_barcode: Option<&'static str>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_copy()
+            .withf(move |event| event.barcode.as_deref() == expected_barcode)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let path = v1(&format!("/books/{}/copies", BookId::new()));
+    let request = match body {
+        Some(body) => Request::post(path)
+            .bearer()
+            .application_json()
+            .body(Body::from(body))?,
+        None => Request::post(path).bearer().body(Body::empty())?,
+    };
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+/// 空のバーコードを指定した場合に400が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_add_book_copy_400(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_copy().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{}/copies", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"barcode":""}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// 蔵書を指定した貸出で、指定した蔵書がリポジトリに渡されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_checkout_book_copy_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let copy_id = CopyId::new();
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_create()
+            .withf(move |event| event.book_id == book_id && event.copy_id == Some(copy_id))
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{book_id}/copies/{copy_id}/checkouts")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+/// タグが正規化されてリポジトリに渡されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_add_book_tags_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_tags()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.tags == vec!["rust".to_string(), "async".to_string()]
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({ "tags": ["Rust", "async", "RUST "] });
+    let request = Request::post(v1(&format!("/books/{book_id}/tags")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// タグが空の場合の異常系テスト
+#[rstest]
+#[case(serde_json::json!({ "tags": [] }))]
+#[case(serde_json::json!({ "tags": ["  "] }))]
+#[tokio::test]
+async fn test_add_book_tags_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: serde_json::Value,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_tags().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{}/tags", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+/// パスで指定したタグが正規化されてリポジトリに渡されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_remove_book_tag_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_remove_tag()
+            .withf(move |event| event.book_id == book_id && event.tag == "rust")
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::delete(v1(&format!("/books/{book_id}/tags/Rust")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+/// タグ一覧が書籍数とともに返されることの確認
+#[rstest]
+#[tokio::test]
+async fn test_list_tags_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all_tags().returning(|| {
+            Ok(vec![TagCount {
+                name: "rust".into(),
+                book_count: 2,
+            }])
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/tags")).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, TagsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].name, "rust");
+    assert_eq!(result.items[0].book_count, 2);
+
     Ok(())
 }
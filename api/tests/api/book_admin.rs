@@ -0,0 +1,197 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::book_admin_action::{BookAdminActionName, BookAdminActionsResponse};
+use kernel::{
+    model::{
+        book::{BookAdminAction, BookAdminActionKind},
+        id::{BookAdminActionId, BookId, CopyId, UserId},
+        user::BookEditor,
+    },
+    repository::book::MockBookRepository,
+};
+use shared::error::AppError;
+
+/// 更新・削除の際に、管理者かどうかがリポジトリへ渡ることの確認
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[tokio::test]
+async fn test_update_and_delete_by_admin(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        mock.expect_delete()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+    let path = v1(&format!("/books/{}", BookId::new()));
+
+    let body = r#"{"title":"Title","author":"Author","isbn":"9784798061702","description":""}"#;
+    let request = Request::put(&path)
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::delete(&path).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 蔵書・タグの操作の際にも、管理者かどうかがリポジトリへ渡ることの確認
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[tokio::test]
+async fn test_copy_and_tag_operations_by_admin(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_add_copy()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        mock.expect_delete_copy()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        mock.expect_add_tags()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        mock.expect_remove_tag()
+            .withf(move |event| event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+    let book_id = BookId::new();
+
+    let request = Request::post(v1(&format!("/books/{book_id}/copies")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let request = Request::delete(v1(&format!("/books/{book_id}/copies/{}", CopyId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::post(v1(&format!("/books/{book_id}/tags")))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"tags":["rust"]}"#))?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::delete(v1(&format!("/books/{book_id}/tags/rust")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 書籍が存在しない場合と、編集する権限がない場合が区別されることの確認
+#[rstest]
+#[case(false, StatusCode::NOT_FOUND)]
+#[case(true, StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn test_delete_book_not_found_or_forbidden(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] exists: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_delete().returning(move |_| {
+            if exists {
+                Err(AppError::ForbiddenOperationError)
+            } else {
+                Err(AppError::EntityNotFound("not found".into()))
+            }
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::delete(v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// 自分の書籍に対する管理者の操作を取得できることの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_book_admin_actions_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_admin_actions().returning(|_| {
+            Ok(vec![BookAdminAction {
+                id: BookAdminActionId::new(),
+                book_id: BookId::new(),
+                book_title: "My Book".into(),
+                action: BookAdminActionKind::Delete,
+                admin: Some(BookEditor {
+                    id: UserId::new(),
+                    name: "Admin".into(),
+                }),
+                acted_at: chrono::Utc::now(),
+            }])
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/book-admin-actions"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookAdminActionsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].action, BookAdminActionName::Delete);
+    assert_eq!(result.items[0].book_title, "My Book");
+
+    Ok(())
+}
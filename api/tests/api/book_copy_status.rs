@@ -0,0 +1,192 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::book_copy_status::{
+    CopyStatusActionName, CopyStatusChangesResponse, CopyStatusName,
+};
+use kernel::{
+    model::{
+        book::{CopyStatus, CopyStatusAction, CopyStatusChange},
+        id::{BookId, CopyId, CopyStatusChangeId, UserId},
+        user::BookEditor,
+    },
+    repository::book::MockBookRepository,
+};
+use shared::error::AppError;
+
+/// 操作・メモ・管理者かどうかがリポジトリに渡ることの確認
+#[rstest]
+#[case(
+    false,
+    r#"{"action":"report-lost","note":"電車に置き忘れた"}"#,
+    CopyStatusAction::ReportLost
+)]
+#[case(
+    true,
+    r#"{"action":"send-to-repair","note":"背表紙の補修"}"#,
+    CopyStatusAction::SendToRepair
+)]
+#[tokio::test]
+async fn test_change_copy_status_200(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] is_admin: bool,
+    #[case] body: &'static str,
+    #[case] expected_action: CopyStatusAction,
+) -> anyhow::Result<()> {
+    let mut fixture = if is_admin { fixture_admin } else { fixture };
+    let book_id = BookId::new();
+    let copy_id = CopyId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_change_copy_status()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.copy_id == copy_id
+                    && event.action == expected_action
+                    && !event.note.is_empty()
+                    && event.requested_by_admin == is_admin
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!(
+        "/books/{book_id}/copies/{copy_id}/status-changes"
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// メモが空の場合や未知の操作の場合はリポジトリを呼ばずに4xxを返すことの確認
+#[rstest]
+#[case(r#"{"action":"report-lost","note":""}"#, StatusCode::BAD_REQUEST)]
+#[case(r#"{"action":"burn","note":"不要"}"#, StatusCode::UNPROCESSABLE_ENTITY)]
+#[tokio::test]
+async fn test_change_copy_status_invalid_request(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_change_copy_status().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!(
+        "/books/{}/copies/{}/status-changes",
+        BookId::new(),
+        CopyId::new()
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// リポジトリが権限エラーや状態の不整合を返した場合のステータスコードの確認
+#[rstest]
+#[case(|| AppError::ForbiddenOperationError, StatusCode::FORBIDDEN)]
+#[case(
+    || AppError::UnprocessableEntity("貸出中の蔵書は修理に出せません".into()),
+    StatusCode::UNPROCESSABLE_ENTITY
+)]
+#[tokio::test]
+async fn test_change_copy_status_rejected(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] error: fn() -> AppError,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_change_copy_status()
+            .returning(move |_| Err(error()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!(
+        "/books/{}/copies/{}/status-changes",
+        BookId::new(),
+        CopyId::new()
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(r#"{"action":"send-to-repair","note":"修理"}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// 状態の変更履歴を取得できることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_copy_status_history_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let copy_id = CopyId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_copy_status_history()
+            .withf(move |b, c| *b == book_id && *c == copy_id)
+            .returning(move |_, _| {
+                Ok(vec![CopyStatusChange {
+                    id: CopyStatusChangeId::new(),
+                    copy_id,
+                    action: CopyStatusAction::ReportDamaged,
+                    from: CopyStatus::Available,
+                    to: CopyStatus::Damaged,
+                    note: "ページが破れていた".into(),
+                    changed_by: Some(BookEditor {
+                        id: UserId::new(),
+                        name: "Borrower".into(),
+                    }),
+                    changed_at: chrono::Utc::now(),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!(
+        "/books/{book_id}/copies/{copy_id}/status-changes"
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, CopyStatusChangesResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].action, CopyStatusActionName::ReportDamaged);
+    assert_eq!(result.items[0].to, CopyStatusName::Damaged);
+
+    Ok(())
+}
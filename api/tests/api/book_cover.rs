@@ -0,0 +1,164 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::CONTENT_TYPE},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::helper::{TestRequestExt, fixture, make_router, v1};
+
+use api::model::book_cover::MAX_COVER_SIZE;
+use kernel::{
+    model::{
+        book::{BookCover, CoverImageType, CoverSize},
+        id::BookId,
+    },
+    repository::book::MockBookRepository,
+};
+
+const BOUNDARY: &str = "cover-boundary";
+
+/// 1つのファイルフィールドを持つmultipartのリクエストボディを作成する
+fn multipart_body(field: &str, content_type: &str, data: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"cover\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+fn upload_request(book_id: BookId, body: Vec<u8>) -> anyhow::Result<Request<Body>> {
+    Ok(Request::put(v1(&format!("/books/{book_id}/cover")))
+        .bearer()
+        .header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))?)
+}
+
+/// coverフィールドの画像と画像形式がリポジトリに渡されることの確認
+/// axumのデフォルトの上限（2MB）を超える画像も受け付ける
+#[rstest]
+#[case("image/png", CoverImageType::Png, 16)]
+#[case("image/jpeg", CoverImageType::Jpeg, 3 * 1024 * 1024)]
+#[tokio::test]
+async fn test_upload_book_cover_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] content_type: &str,
+    #[case] expected: CoverImageType,
+    #[case] size: usize,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_upload_cover()
+            .withf(move |event| {
+                event.book_id == book_id && event.image_type == expected && event.data.len() == size
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = multipart_body("cover", content_type, &vec![1; size]);
+    let resp = router.oneshot(upload_request(book_id, body)?).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 画像形式やサイズが受け付けられない場合に、リポジトリが呼ばれないことの確認
+#[rstest]
+#[case("cover", "image/gif", 16, StatusCode::UNSUPPORTED_MEDIA_TYPE)]
+#[case("cover", "image/png", MAX_COVER_SIZE + 1, StatusCode::PAYLOAD_TOO_LARGE)]
+#[case("cover", "image/png", 0, StatusCode::UNPROCESSABLE_ENTITY)]
+#[case("file", "image/png", 16, StatusCode::UNPROCESSABLE_ENTITY)]
+#[tokio::test]
+async fn test_upload_book_cover_rejected(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] field: &str,
+    #[case] content_type: &str,
+    #[case] size: usize,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_upload_cover().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = multipart_body(field, content_type, &vec![1; size]);
+    let resp = router.oneshot(upload_request(BookId::new(), body)?).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// 書影とサムネイルが画像形式に応じたContent-Typeで返されることの確認
+#[rstest]
+#[case("cover", CoverSize::Original)]
+#[case("cover/thumbnail", CoverSize::Thumbnail)]
+#[tokio::test]
+async fn test_show_book_cover_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected: CoverSize,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_cover()
+            .withf(move |id, size| *id == book_id && *size == expected)
+            .returning(|_, _| {
+                Ok(Some(BookCover {
+                    image_type: CoverImageType::Jpeg,
+                    data: vec![1, 2, 3],
+                }))
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}/{path}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[CONTENT_TYPE], "image/jpeg");
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    assert_eq!(&body[..], &[1, 2, 3]);
+
+    Ok(())
+}
+
+/// 書影が登録されていない場合の確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_cover_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_cover().returning(|_, _| Ok(None));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{}/cover", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
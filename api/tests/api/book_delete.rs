@@ -0,0 +1,171 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::{str::FromStr, sync::Arc};
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::book::PaginatedDeletedBookResponse;
+use kernel::{
+    model::{
+        book::{Book, DeletedBook},
+        id::{BookId, UserId},
+        isbn::Isbn,
+        list::PaginatedList,
+        user::BookOwner,
+    },
+    repository::book::MockBookRepository,
+};
+use shared::error::AppError;
+
+/// 貸出中で削除できない場合に422が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_delete_checked_out_book_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_delete()
+            .returning(|_| Err(AppError::UnprocessableEntity("checked out".into())));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::delete(v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+/// 管理者が削除済みの書籍を削除日時とともに取得できることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_deleted_book_list_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let deleted_at = chrono::Utc::now();
+
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_deleted()
+            .withf(|options| options.limit == 5 && options.offset == 10)
+            .returning(move |options| {
+                Ok(PaginatedList {
+                    total: 11,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![DeletedBook {
+                        book: Book {
+                            id: BookId::new(),
+                            title: "Deleted Book".into(),
+                            author: "Author".into(),
+                            isbn: Isbn::from_stored("".into(), "".into()),
+                            description: "".into(),
+                            publisher: None,
+                            published_on: None,
+                            page_count: None,
+                            owner: BookOwner {
+                                id: UserId::new(),
+                                name: "Owner".into(),
+                            },
+                            copies: vec![],
+                            tags: vec![],
+                            cover: None,
+                            version: 1,
+                            location: None,
+                            rating: Default::default(),
+                            loan_period_days: None,
+                        },
+                        deleted_at,
+                    }],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_admin);
+
+    let request = Request::get(v1("/books/deleted?limit=5&offset=10"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedDeletedBookResponse);
+    assert_eq!(result.total, 11);
+    assert_eq!(result.items[0].book.title, "Deleted Book");
+    assert_eq!(result.items[0].deleted_at, deleted_at);
+
+    Ok(())
+}
+
+/// 管理者が削除済みの書籍を復元・完全に削除できることの確認
+#[rstest]
+#[case(Request::post(v1(&format!("/books/{BOOK_ID}/restore"))))]
+#[case(Request::delete(v1(&format!("/books/{BOOK_ID}/purge"))))]
+#[tokio::test]
+async fn test_restore_and_purge_book_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+    #[case] request: axum::http::request::Builder,
+) -> anyhow::Result<()> {
+    fixture_admin.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_restore()
+            .withf(|event| event.book_id == BookId::from_str(BOOK_ID).unwrap())
+            .returning(|_| Ok(()));
+        mock.expect_purge()
+            .withf(|event| event.book_id == BookId::from_str(BOOK_ID).unwrap())
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_admin);
+
+    let resp = router
+        .oneshot(request.bearer().body(Body::empty())?)
+        .await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 管理者以外は削除済みの書籍を操作できないことの確認
+#[rstest]
+#[case(Request::get(v1("/books/deleted")))]
+#[case(Request::post(v1(&format!("/books/{BOOK_ID}/restore"))))]
+#[case(Request::delete(v1(&format!("/books/{BOOK_ID}/purge"))))]
+#[tokio::test]
+async fn test_deleted_books_forbidden_for_user(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] request: axum::http::request::Builder,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_deleted().never();
+        mock.expect_restore().never();
+        mock.expect_purge().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let resp = router
+        .oneshot(request.bearer().body(Body::empty())?)
+        .await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+const BOOK_ID: &str = "9890736e-a4e4-461a-a77d-eac3517ef11b";
@@ -0,0 +1,140 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header::ETAG},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::helper::{TestRequestExt, fixture, make_router, v1};
+
+use kernel::{
+    model::{
+        book::Book,
+        id::{BookId, UserId},
+        isbn::Isbn,
+        user::BookOwner,
+    },
+    repository::book::MockBookRepository,
+};
+use shared::error::AppError;
+
+const UPDATE_BODY: &str =
+    r#"{"title":"Title","author":"Author","isbn":"9784798061702","description":""}"#;
+
+/// 書籍の取得時に、書誌情報の版がETagとして返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_etag(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(|id| {
+            Ok(Some(Book {
+                id,
+                title: "Title".into(),
+                author: "Author".into(),
+                isbn: Isbn::from_stored("9784798061702".into(), "9784798061702".into()),
+                description: "".into(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+                owner: BookOwner {
+                    id: UserId::new(),
+                    name: "Owner".into(),
+                },
+                copies: vec![],
+                tags: vec![],
+                cover: None,
+                version: 3,
+                location: None,
+                rating: Default::default(),
+                loan_period_days: None,
+            }))
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[ETAG], "\"3\"");
+
+    Ok(())
+}
+
+/// If-Matchの値が、更新・削除の前提とする版としてリポジトリへ渡ることの確認
+#[rstest]
+#[case(None, None)]
+#[case(Some("*"), None)]
+#[case(Some("\"3\""), Some(3))]
+#[tokio::test]
+async fn test_update_and_delete_with_if_match(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] if_match: Option<&'static str>,
+    #[case] expected: Option<i32>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update()
+            .withf(move |event| event.expected_version == expected)
+            .returning(|_| Ok(()));
+        mock.expect_delete()
+            .withf(move |event| event.expected_version == expected)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+    let path = v1(&format!("/books/{}", BookId::new()));
+
+    let mut update = Request::put(&path).bearer().application_json();
+    let mut delete = Request::delete(&path).bearer();
+    if let Some(if_match) = if_match {
+        update = update.header("If-Match", if_match);
+        delete = delete.header("If-Match", if_match);
+    }
+
+    let resp = router
+        .clone()
+        .oneshot(update.body(Body::from(UPDATE_BODY))?)
+        .await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = router.oneshot(delete.body(Body::empty())?).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 書籍が変更されていた場合や、If-Matchが解釈できない場合に412が返ることの確認
+#[rstest]
+#[case("\"3\"")]
+#[case("W/\"3\"")]
+#[case("\"abc\"")]
+#[tokio::test]
+async fn test_update_book_412(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] if_match: &'static str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update()
+            .returning(|_| Err(AppError::PreconditionFailed("changed".into())));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .application_json()
+        .header("If-Match", if_match)
+        .body(Body::from(UPDATE_BODY))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    Ok(())
+}
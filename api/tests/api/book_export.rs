@@ -0,0 +1,142 @@
+use axum::{body::Body, http::Request};
+use futures::stream;
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::helper::{TestRequestExt, fixture, make_router, v1};
+
+use api::model::{book_copy_status::CopyStatusName, book_export::BookExportRecord};
+use kernel::{
+    model::{
+        book::{BookCatalogEntry, BookCopy, Checkout, CopyStatus},
+        id::{BookId, CheckoutId, CopyId, UserId},
+        isbn::Isbn,
+        user::{BookOwner, CheckoutUser},
+    },
+    repository::book::MockBookRepository,
+};
+
+fn catalog() -> Vec<BookCatalogEntry> {
+    let book_id = BookId::new();
+    let owner = || BookOwner {
+        id: UserId::new(),
+        name: "Owner".to_string(),
+    };
+    let entry = |checkout: Option<Checkout>| BookCatalogEntry {
+        book_id,
+        title: "Test Book, 2nd edition".to_string(),
+        author: "Test Author".to_string(),
+        isbn: Isbn::from_stored("9784798061702".into(), "978-4-7980-6170-2".into()),
+        description: "".to_string(),
+        owner: owner(),
+        copy: Some(BookCopy {
+            id: CopyId::new(),
+            barcode: "C00000001".to_string(),
+            status: CopyStatus::Available,
+            checkout,
+        }),
+    };
+    vec![
+        entry(Some(Checkout {
+            checkout_id: CheckoutId::new(),
+            checked_out_by: CheckoutUser {
+                id: UserId::new(),
+                name: "Borrower".to_string(),
+            },
+            checked_out_at: chrono::Utc::now(),
+            due_at: chrono::Utc::now() + chrono::Duration::days(14),
+            renewal_count: 0,
+        })),
+        entry(None),
+    ]
+}
+
+fn mock_fixture(mut fixture: registry::MockAppRegistryExt) -> registry::MockAppRegistryExt {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_stream_catalog()
+            .returning(|| Box::pin(stream::iter(catalog().into_iter().map(Ok))));
+        Arc::new(mock)
+    });
+    fixture
+}
+
+/// CSV形式では、ヘッダー行に続いて蔵書ごとに1行ずつ返ることの確認
+#[rstest]
+#[case("/books/export")]
+#[case("/books/export?format=csv")]
+#[tokio::test]
+async fn test_export_books_csv_200(
+    fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    let router: axum::Router = make_router(mock_fixture(fixture));
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert!(
+        resp.headers()["content-type"]
+            .to_str()?
+            .starts_with("text/csv")
+    );
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let mut reader = csv::Reader::from_reader(body.as_ref());
+    let records = reader
+        .deserialize::<BookExportRecord>()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].title, "Test Book, 2nd edition");
+    assert_eq!(records[0].isbn13, "9784798061702");
+    assert_eq!(records[0].checked_out_by_name.as_deref(), Some("Borrower"));
+    assert_eq!(records[0].copy_status, Some(CopyStatusName::Available));
+    assert!(records[1].checkout_id.is_none());
+
+    Ok(())
+}
+
+/// JSON Lines形式では、1行に1つのJSONオブジェクトが返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_export_books_jsonl_200(fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let router: axum::Router = make_router(mock_fixture(fixture));
+
+    let request = Request::get(v1("/books/export?format=jsonl"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/x-ndjson");
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let records = std::str::from_utf8(&body)?
+        .lines()
+        .map(serde_json::from_str::<BookExportRecord>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(records.len(), 2);
+    assert!(records[0].checkout_id.is_some());
+
+    Ok(())
+}
+
+/// 不正な形式を指定した場合に400が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_export_books_400(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_stream_catalog().never();
+        Arc::new(mock)
+    });
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/books/export?format=xml"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
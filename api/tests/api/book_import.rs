@@ -0,0 +1,118 @@
+use axum::{body::Body, http::Request};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::book_import::{BookImportReportResponse, BookImportStatusName};
+use kernel::{
+    model::{
+        book::{BookImportResult, BookImportStatus},
+        id::BookId,
+    },
+    repository::book::MockBookRepository,
+};
+
+const CSV: &str = "title,author,isbn,description,owner_email
+Rust入門,著者A,978-4-7980-6170-2,,
+既にある本,著者B,9784065301951,説明,owner@example.com
+,著者C,9784065369579,,
+ISBN不正,著者D,123,,
+";
+
+/// 検証を通過した行だけがリポジトリに渡され、不備のある行と合わせて行番号順に返ることの確認
+#[rstest]
+#[case("/books/import", false)]
+#[case("/books/import?dryRun=true", true)]
+#[tokio::test]
+async fn test_import_books_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] dry_run: bool,
+) -> anyhow::Result<()> {
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_import()
+            .withf(move |event| {
+                event.dry_run == dry_run
+                    && event.default_owner.is_some()
+                    && event.rows.iter().map(|r| r.line).eq([2, 3])
+                    && event.rows[1].owner_email.as_deref() == Some("owner@example.com")
+            })
+            .returning(|_| {
+                Ok(vec![
+                    BookImportResult {
+                        line: 2,
+                        status: BookImportStatus::Created(BookId::new()),
+                    },
+                    BookImportResult {
+                        line: 3,
+                        status: BookImportStatus::Duplicate,
+                    },
+                ])
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_admin);
+
+    let request = Request::post(v1(path)).bearer().body(Body::from(CSV))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookImportReportResponse);
+    assert_eq!(result.dry_run, dry_run);
+    assert_eq!((result.created, result.skipped, result.invalid), (1, 1, 2));
+    assert_eq!(
+        result.rows.iter().map(|r| r.status).collect::<Vec<_>>(),
+        vec![
+            BookImportStatusName::Created,
+            BookImportStatusName::Duplicate,
+            BookImportStatusName::Invalid,
+            BookImportStatusName::Invalid,
+        ]
+    );
+    // ドライランの場合は書籍IDを返さない
+    assert_eq!(result.rows[0].book_id.is_some(), !dry_run);
+    assert!(
+        result.rows[2]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("title")
+    );
+    assert!(
+        result.rows[3]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("isbn")
+    );
+
+    Ok(())
+}
+
+/// 管理者以外は一括登録できないことの確認
+#[rstest]
+#[tokio::test]
+async fn test_import_books_403(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_import().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/books/import"))
+        .bearer()
+        .body(Body::from(CSV))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
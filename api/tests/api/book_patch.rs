@@ -0,0 +1,77 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::helper::{TestRequestExt, fixture, make_router, v1};
+
+use kernel::{model::id::BookId, repository::book::MockBookRepository};
+
+/// 省略した項目は変更せず、nullを指定した任意項目は削除として渡ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_patch_book_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_patch()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.title.as_deref() == Some("Fixed Title")
+                    && event.author.is_none()
+                    && event.isbn.as_ref().map(|i| i.as_isbn13()) == Some("9784798061702")
+                    && event.description.is_none()
+                    && event.publisher == Some(None)
+                    && event.published_on.is_none()
+                    && event.page_count == Some(Some(320))
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let body = r#"{"title":"Fixed Title","isbn":"4798061700","publisher":null,"pageCount":320}"#;
+    let request = Request::patch(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .header("Content-Type", "application/merge-patch+json")
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 削除できない項目へのnullや、指定した項目の不正な値が400になることの確認
+#[rstest]
+#[case(r#"{"title":null}"#)]
+#[case(r#"{"description":null}"#)]
+#[case(r#"{"title":""}"#)]
+#[case(r#"{"isbn":"invalid"}"#)]
+#[case(r#"{"pageCount":0}"#)]
+#[tokio::test]
+async fn test_patch_book_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_patch().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::patch(v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .header("Content-Type", "application/merge-patch+json")
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
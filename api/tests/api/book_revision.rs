@@ -0,0 +1,120 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::book_revision::BookRevisionsResponse;
+use kernel::{
+    model::{
+        book::{BookRevision, BookSnapshot},
+        id::{BookId, BookRevisionId, UserId},
+        isbn::Isbn,
+        user::BookEditor,
+    },
+    repository::book::MockBookRepository,
+};
+
+fn snapshot(description: &str) -> BookSnapshot {
+    BookSnapshot {
+        title: "Title".into(),
+        author: "Author".into(),
+        isbn: Isbn::from_stored("9784798061702".into(), "978-4798061702".into()),
+        description: description.into(),
+        publisher: None,
+        published_on: None,
+        page_count: None,
+    }
+}
+
+/// 変更履歴が変更された項目の名前とともに返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_revisions_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_revisions()
+            .withf(move |id| *id == book_id)
+            .returning(move |book_id| {
+                Ok(vec![BookRevision {
+                    id: BookRevisionId::new(),
+                    book_id,
+                    changed_by: Some(BookEditor {
+                        id: UserId::new(),
+                        name: "Editor".into(),
+                    }),
+                    changed_at: chrono::Utc::now(),
+                    before: snapshot("Old description"),
+                    after: snapshot(""),
+                }])
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}/revisions")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookRevisionsResponse);
+    assert_eq!(result.items.len(), 1);
+    let revision = &result.items[0];
+    assert_eq!(revision.changed_fields, vec!["description".to_string()]);
+    assert_eq!(revision.before.description, "Old description");
+    assert_eq!(revision.changed_by.as_ref().unwrap().name, "Editor");
+
+    Ok(())
+}
+
+/// 変更履歴を戻す際に、管理者かどうかがリポジトリへ渡ることの確認
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[tokio::test]
+async fn test_revert_book_revision_200(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+    let book_id = BookId::new();
+    let revision_id = BookRevisionId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_revert()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.revision_id == revision_id
+                    && event.requested_by_admin == admin
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!(
+        "/books/{book_id}/revisions/{revision_id}/revert"
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
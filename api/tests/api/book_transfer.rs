@@ -0,0 +1,238 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, fixture_auth, make_router, v1},
+};
+
+use api::model::book_transfer::{
+    BookTransferResponse, BookTransfersResponse, TransferAllBooksResponse,
+};
+use kernel::{
+    model::{
+        book::BookTransfer,
+        id::{BookId, BookTransferId, UserId},
+        role::Role,
+        user::{BookOwner, User},
+    },
+    repository::{book::MockBookRepository, user::MockUserRepository},
+};
+use shared::error::AppError;
+
+fn book_transfer(book_id: BookId, to_user: UserId) -> BookTransfer {
+    BookTransfer {
+        id: BookTransferId::new(),
+        book_id,
+        book_title: "My Book".into(),
+        from: BookOwner {
+            id: UserId::new(),
+            name: "Owner".into(),
+        },
+        to: BookOwner {
+            id: to_user,
+            name: "Recipient".into(),
+        },
+        requested_at: chrono::Utc::now(),
+    }
+}
+
+/// 承認を求めない場合は200、求める場合は承認待ちの譲渡とともに202を返すことの確認
+#[rstest]
+#[case(r#"{"toUserId":"TO"}"#, false, StatusCode::OK)]
+#[case(
+    r#"{"toUserId":"TO","requireAcceptance":true}"#,
+    true,
+    StatusCode::ACCEPTED
+)]
+#[tokio::test]
+async fn test_transfer_book(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &str,
+    #[case] require_acceptance: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let to_user = UserId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_transfer()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.to_user == to_user
+                    && event.require_acceptance == require_acceptance
+                    && !event.requested_by_admin
+            })
+            .returning(move |event| {
+                Ok(event
+                    .require_acceptance
+                    .then(|| book_transfer(event.book_id, event.to_user)))
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{book_id}/transfer")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.replace("TO", &to_user.to_string())))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if require_acceptance {
+        let result = deserialize_json!(resp, BookTransferResponse);
+        assert_eq!(result.book_id, book_id);
+        assert_eq!(result.to.id, to_user);
+    }
+
+    Ok(())
+}
+
+/// 譲渡先のユーザーが承認・拒否でき、自分宛ての譲渡がなければ404を返すことの確認
+#[rstest]
+#[case("accept", true, StatusCode::OK)]
+#[case("accept", false, StatusCode::NOT_FOUND)]
+#[case("decline", true, StatusCode::OK)]
+#[case("decline", false, StatusCode::NOT_FOUND)]
+#[tokio::test]
+async fn test_accept_and_decline_transfer(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] action: &str,
+    #[case] exists: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let result = move || {
+        if exists {
+            Ok(())
+        } else {
+            Err(AppError::EntityNotFound("not found".into()))
+        }
+    };
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_accept_transfer().returning(move |_| result());
+        mock.expect_decline_transfer().returning(move |_| result());
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{}/transfer/{action}", BookId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// 自分宛ての承認待ちの譲渡を取得できることの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_book_transfers_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_incoming_transfers()
+            .returning(|user_id| Ok(vec![book_transfer(BookId::new(), user_id)]));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/book-transfers"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookTransfersResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].book_title, "My Book");
+
+    Ok(())
+}
+
+/// ユーザーの書籍をまとめて譲渡できるのは管理者のみであることの確認
+#[rstest]
+#[case(true, StatusCode::OK)]
+#[case(false, StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn test_transfer_user_books(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+    let from_user = UserId::new();
+    let to_user = UserId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_transfer_all()
+            .withf(move |event| {
+                event.from_user == from_user
+                    && event.to_user == to_user
+                    && event.requested_user != from_user
+            })
+            .returning(|_| Ok(3));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/users/{from_user}/books/transfer")))
+        .bearer()
+        .application_json()
+        .body(Body::from(format!(r#"{{"toUserId":"{to_user}"}}"#)))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if admin {
+        let result = deserialize_json!(resp, TransferAllBooksResponse);
+        assert_eq!(result.transferred, 3);
+    }
+
+    Ok(())
+}
+
+/// 書籍を所有しているユーザーを削除しようとすると422を返すことの確認
+#[rstest]
+#[tokio::test]
+async fn test_delete_user_owning_books_422(
+    mut fixture_auth: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture_auth.expect_user_repository().returning(|| {
+        let mut mock = MockUserRepository::new();
+        mock.expect_find_current_user().returning(|id| {
+            Ok(Some(User {
+                id,
+                name: "dummy-admin".into(),
+                email: "admin@example.com".into(),
+                role: Role::Admin,
+            }))
+        });
+        mock.expect_delete()
+            .returning(|_| Err(AppError::UnprocessableEntity("owns books".into())));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_auth);
+
+    let request = Request::delete(v1(&format!("/users/{}", UserId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+
+use kernel::{
+    model::{
+        checkout::{Checkout, CheckoutBook},
+        id::{BookId, CheckoutId, CopyId, UserId},
+        list::CursorPaginatedList,
+    },
+    repository::{book::MockBookRepository, checkout::MockCheckoutRepository},
+};
+use shared::error::AppError;
+
+fn checkout(due_at: chrono::DateTime<chrono::Utc>) -> Checkout {
+    Checkout {
+        id: CheckoutId::new(),
+        checked_out_by: UserId::new(),
+        checked_out_at: due_at - chrono::Duration::days(14),
+        due_at,
+        renewal_count: 0,
+        returned_at: None,
+        outcome: None,
+        book: CheckoutBook {
+            id: BookId::new(),
+            title: "Test Book".into(),
+            author: "Test Author".into(),
+            isbn: "978-4-7980-6170-2".into(),
+            copy_id: CopyId::new(),
+            barcode: "C00000001".into(),
+        },
+    }
+}
+
+/// 貸出中の一覧で、overdueの指定がリポジトリに渡り、返却期限が返ることの確認
+#[rstest]
+#[case("/books/checkouts", false)]
+#[case("/books/checkouts?overdue=true", true)]
+#[case("/books/checkouts?overdue=false", false)]
+#[tokio::test]
+async fn test_show_checked_out_list(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &'static str,
+    #[case] expected_overdue: bool,
+) -> anyhow::Result<()> {
+    let due_at = chrono::Utc::now() - chrono::Duration::days(1);
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_find_unreturned_all()
+            .withf(move |overdue| *overdue == expected_overdue)
+            .returning(move |_| Ok(vec![checkout(due_at)]));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, serde_json::Value);
+    let returned: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(result["items"][0]["dueAt"].clone())?;
+    assert_eq!(returned, due_at);
+
+    Ok(())
+}
+
+/// カーソルによるページネーションでも、overdueの指定がリポジトリに渡ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_checked_out_list_by_cursor(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_checkout_repository().returning(|| {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_find_unreturned_all_by_cursor()
+            .withf(|options, overdue| options.cursor.is_none() && *overdue)
+            .returning(|options, _| {
+                Ok(CursorPaginatedList {
+                    items: vec![],
+                    limit: options.limit,
+                    next_cursor: None,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/books/checkouts?cursor=&overdue=true"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 貸出期間の変更で、1日未満は400を返し、所有者でない場合は403を返すことの確認
+#[rstest]
+#[case(r#"{"loanPeriodDays":30}"#, false, StatusCode::OK)]
+#[case(r#"{"loanPeriodDays":null}"#, false, StatusCode::OK)]
+#[case(r#"{"loanPeriodDays":0}"#, false, StatusCode::BAD_REQUEST)]
+#[case(r#"{"loanPeriodDays":30}"#, true, StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn test_set_book_loan_period(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] forbidden: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_set_loan_period()
+            .withf(move |event| event.book_id == book_id && !event.requested_by_admin)
+            .returning(move |_| {
+                if forbidden {
+                    Err(AppError::ForbiddenOperationError)
+                } else {
+                    Ok(())
+                }
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/books/{book_id}/loan-period")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+/// 貸出の延長で、延長後の返却期限と回数が返り、延長できない場合は422を返すことの確認
+#[rstest]
+#[case(false, StatusCode::OK)]
+#[case(true, StatusCode::UNPROCESSABLE_ENTITY)]
+#[tokio::test]
+async fn test_renew_checkout(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] exceeded: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let checkout_id = CheckoutId::new();
+    let due_at = chrono::Utc::now() + chrono::Duration::days(28);
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_renew()
+            .withf(move |event| event.book_id == book_id && event.checkout_id == checkout_id)
+            .returning(move |event| {
+                if exceeded {
+                    return Err(AppError::UnprocessableEntity(
+                        "renewal limit has been reached".into(),
+                    ));
+                }
+                Ok(Checkout {
+                    id: event.checkout_id,
+                    renewal_count: 1,
+                    ..checkout(due_at)
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!(
+        "/books/{book_id}/checkouts/{checkout_id}/renew"
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::OK {
+        let result = deserialize_json!(resp, serde_json::Value);
+        assert_eq!(result["renewalCount"], 1);
+        let returned: chrono::DateTime<chrono::Utc> =
+            serde_json::from_value(result["dueAt"].clone())?;
+        assert_eq!(returned, due_at);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::{collections::HashSet, sync::Arc};
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{
+        TestRequestExt, fixture, fixture_auth, make_router, v1,
+        with_current_user_role_without_favorites,
+    },
+};
+
+use api::model::book::{BookResponse, PaginatedBookResponse};
+use kernel::{
+    model::{
+        book::Book,
+        id::{BookId, UserId},
+        isbn::Isbn,
+        list::PaginatedList,
+        role::Role,
+        user::BookOwner,
+    },
+    repository::{book::MockBookRepository, favorite::MockFavoriteRepository},
+};
+
+fn book(id: BookId) -> Book {
+    Book {
+        id,
+        title: "Test Book".to_string(),
+        isbn: Isbn::from_stored("".into(), "".into()),
+        author: "Test Author".to_string(),
+        description: "Test Description".to_string(),
+        publisher: None,
+        published_on: None,
+        page_count: None,
+        owner: BookOwner {
+            id: UserId::new(),
+            name: "Test User".to_string(),
+        },
+        copies: vec![],
+        tags: vec![],
+        cover: None,
+        version: 1,
+        location: None,
+        rating: Default::default(),
+        loan_period_days: None,
+    }
+}
+
+/// 書籍の詳細に、現在のユーザーがお気に入りに登録しているかどうかが含まれることの確認
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test]
+async fn test_show_book_favorited(
+    fixture_auth: registry::MockAppRegistryExt,
+    #[case] favorited: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = with_current_user_role_without_favorites(fixture_auth, Role::User);
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(|id| Ok(Some(book(id))));
+        Arc::new(mock)
+    });
+    fixture.expect_favorite_repository().returning(move || {
+        let mut mock = MockFavoriteRepository::new();
+        mock.expect_find_favorited()
+            .withf(move |_, ids| ids == &vec![book_id])
+            .returning(move |_, ids| {
+                Ok(if favorited {
+                    ids.into_iter().collect()
+                } else {
+                    HashSet::new()
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookResponse);
+    assert_eq!(result.favorited, favorited);
+
+    Ok(())
+}
+
+/// お気に入りの一覧は、ログイン中のユーザーのお気に入りに絞り込んで取得することの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_favorites_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(|options| options.favorited_by.is_some() && options.limit == 5)
+            .returning(|options| {
+                Ok(PaginatedList {
+                    total: 1,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![book(BookId::new())],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/favorites?limit=5"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedBookResponse);
+    assert_eq!(result.items.len(), 1);
+    assert!(result.items[0].favorited);
+
+    Ok(())
+}
+
+/// お気に入りの登録と解除で、書籍IDがリポジトリに渡ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_add_and_remove_favorite(
+    fixture_auth: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let mut fixture = with_current_user_role_without_favorites(fixture_auth, Role::User);
+    let book_id = BookId::new();
+    fixture.expect_favorite_repository().returning(move || {
+        let mut mock = MockFavoriteRepository::new();
+        mock.expect_add()
+            .withf(move |event| event.book_id == book_id)
+            .returning(|_| Ok(()));
+        mock.expect_remove()
+            .withf(move |event| event.book_id == book_id)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/users/me/favorites/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::delete(v1(&format!("/users/me/favorites/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
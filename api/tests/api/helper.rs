@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use api::route::{auth, v1};
 use axum::{Router, http::request::Builder};
 use kernel::{
     model::{auth::AccessToken, id::UserId, role::Role, user::User},
-    repository::{auth::MockAuthRepository, user::MockUserRepository},
+    repository::{
+        auth::MockAuthRepository, favorite::MockFavoriteRepository, user::MockUserRepository,
+    },
 };
 use registry::MockAppRegistryExt;
 use rstest::fixture;
@@ -44,22 +46,50 @@ pub fn fixture_auth(mut fixture_registry: MockAppRegistryExt) -> MockAppRegistry
 }
 
 #[fixture]
-pub fn fixture(mut fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
-    fixture_auth.expect_user_repository().returning(|| {
+pub fn fixture(fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
+    with_current_user_role(fixture_auth, Role::User)
+}
+
+/// 管理者としてリクエストする場合のfixture
+#[fixture]
+pub fn fixture_admin(fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
+    with_current_user_role(fixture_auth, Role::Admin)
+}
+
+/// 指定したロールのユーザーとしてリクエストするよう設定する
+pub fn with_current_user_role(registry: MockAppRegistryExt, role: Role) -> MockAppRegistryExt {
+    let mut registry = with_current_user_role_without_favorites(registry, role);
+    // 書籍の取得ではお気に入りの登録状況も確認するため、お気に入りがない状態をデフォルトとする
+    registry.expect_favorite_repository().returning(|| {
+        let mut mock_favorite_repository = MockFavoriteRepository::new();
+        mock_favorite_repository
+            .expect_find_favorited()
+            .returning(|_, _| Ok(HashSet::new()));
+        Arc::new(mock_favorite_repository)
+    });
+    registry
+}
+
+/// お気に入りのリポジトリを独自にモックするテスト用に、ユーザーのみを設定する
+pub fn with_current_user_role_without_favorites(
+    mut registry: MockAppRegistryExt,
+    role: Role,
+) -> MockAppRegistryExt {
+    registry.expect_user_repository().returning(move || {
         let mut mock_user_repository = MockUserRepository::new();
         mock_user_repository
             .expect_find_current_user()
-            .returning(|id| {
+            .returning(move |id| {
                 Ok(Some(User {
                     id,
                     name: "dummy-user".to_string(),
                     email: "dummy@example.com".to_string(),
-                    role: Role::User,
+                    role,
                 }))
             });
         Arc::new(mock_user_repository)
     });
-    fixture_auth
+    registry
 }
 
 pub trait TestRequestExt {
@@ -0,0 +1,151 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+
+use api::model::hold::{HoldResponse, HoldStatusName, HoldsResponse};
+use kernel::{
+    model::{
+        hold::{Hold, HoldBook},
+        id::{BookId, HoldId},
+    },
+    repository::hold::MockHoldRepository,
+};
+use shared::error::AppError;
+
+fn hold(book_id: BookId, position: i64, ready: bool) -> Hold {
+    let now = chrono::Utc::now();
+    Hold {
+        id: HoldId::new(),
+        book: HoldBook {
+            id: book_id,
+            title: "Test Book".into(),
+            author: "Test Author".into(),
+        },
+        position,
+        created_at: now,
+        ready_at: ready.then_some(now),
+        expires_at: ready.then(|| now + chrono::Duration::days(3)),
+    }
+}
+
+/// 予約の際に書籍がリポジトリに渡り、予約できない場合は422を返すことの確認
+#[rstest]
+#[case(false, StatusCode::CREATED)]
+#[case(true, StatusCode::UNPROCESSABLE_ENTITY)]
+#[tokio::test]
+async fn test_place_hold(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] available: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_hold_repository().returning(move || {
+        let mut mock = MockHoldRepository::new();
+        mock.expect_create()
+            .withf(move |event| event.book_id == book_id)
+            .returning(move |event| {
+                if available {
+                    return Err(AppError::UnprocessableEntity(
+                        "the book has lendable copies".into(),
+                    ));
+                }
+                Ok(hold(event.book_id, 2, false))
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{book_id}/holds")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::CREATED {
+        let result = deserialize_json!(resp, HoldResponse);
+        assert_eq!(result.book.id, book_id);
+        assert_eq!(result.position, 2);
+        assert_eq!(result.status, HoldStatusName::Waiting);
+    }
+
+    Ok(())
+}
+
+/// 自分の予約の一覧に、順番と取り置きの状態が含まれることの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_holds(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_hold_repository().returning(|| {
+        let mut mock = MockHoldRepository::new();
+        mock.expect_find_by_user_id().returning(|_| {
+            Ok(vec![
+                hold(BookId::new(), 1, true),
+                hold(BookId::new(), 3, false),
+            ])
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/holds"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, HoldsResponse);
+    assert_eq!(result.items.len(), 2);
+    assert_eq!(result.items[0].status, HoldStatusName::Ready);
+    assert!(result.items[0].expires_at.is_some());
+    assert_eq!(result.items[1].status, HoldStatusName::Waiting);
+    assert_eq!(result.items[1].position, 3);
+
+    Ok(())
+}
+
+/// 自分の予約を取り消せて、存在しない予約は404を返すことの確認
+#[rstest]
+#[tokio::test]
+async fn test_cancel_hold(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let hold_id = HoldId::new();
+
+    fixture.expect_hold_repository().returning(move || {
+        let mut mock = MockHoldRepository::new();
+        mock.expect_delete().returning(move |event| {
+            if event.hold_id == hold_id {
+                Ok(())
+            } else {
+                Err(AppError::EntityNotFound("hold not found".into()))
+            }
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::delete(v1(&format!("/users/me/holds/{hold_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::delete(v1(&format!("/users/me/holds/{}", HoldId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
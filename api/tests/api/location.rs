@@ -0,0 +1,241 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::{
+    book::BookResponse,
+    location::{LocationResponse, LocationsResponse},
+};
+use kernel::{
+    model::{
+        book::Book,
+        id::{BookId, LocationId, UserId},
+        isbn::Isbn,
+        location::Location,
+        user::BookOwner,
+    },
+    repository::{book::MockBookRepository, location::MockLocationRepository},
+};
+use shared::error::AppError;
+
+fn location() -> Location {
+    Location {
+        id: LocationId::new(),
+        site: "本社".into(),
+        room: "3F".into(),
+        shelf: "A".into(),
+    }
+}
+
+/// 置き場所の一覧を取得できることの確認
+#[rstest]
+#[tokio::test]
+async fn test_list_locations_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_find_all().returning(|| Ok(vec![location()]));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/locations"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, LocationsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].shelf, "A");
+
+    Ok(())
+}
+
+/// 置き場所を登録できるのは管理者のみで、空の名前は受け付けないことの確認
+#[rstest]
+#[case(
+    true,
+    r#"{"site":"本社","room":"3F","shelf":"A"}"#,
+    StatusCode::CREATED
+)]
+#[case(
+    true,
+    r#"{"site":"本社","room":"","shelf":"A"}"#,
+    StatusCode::BAD_REQUEST
+)]
+#[case(
+    false,
+    r#"{"site":"本社","room":"3F","shelf":"A"}"#,
+    StatusCode::FORBIDDEN
+)]
+#[tokio::test]
+async fn test_register_location(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+
+    fixture.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_create().returning(|event| {
+            Ok(Location {
+                id: LocationId::new(),
+                site: event.site,
+                room: event.room,
+                shelf: event.shelf,
+            })
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/locations"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::CREATED {
+        let result = deserialize_json!(resp, LocationResponse);
+        assert_eq!(result.room, "3F");
+    }
+
+    Ok(())
+}
+
+/// 書籍が置かれている置き場所を削除しようとすると422を返すことの確認
+#[rstest]
+#[tokio::test]
+async fn test_delete_location_in_use_422(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture_admin.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_delete()
+            .returning(|_| Err(AppError::UnprocessableEntity("in use".into())));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_admin);
+
+    let request = Request::delete(v1(&format!("/locations/{}", LocationId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+/// 書籍の置き場所を移す際に、置き場所と管理者かどうかがリポジトリへ渡ることの確認
+#[rstest]
+#[case(r#"{"locationId":"LOCATION"}"#, true)]
+#[case(r#"{"locationId":null}"#, false)]
+#[tokio::test]
+async fn test_move_book_200(
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] has_location: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = fixture_admin;
+    let book_id = BookId::new();
+    let location_id = LocationId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_move_to()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.location_id == has_location.then_some(location_id)
+                    && event.requested_by_admin
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/books/{book_id}/location")))
+        .bearer()
+        .application_json()
+        .body(Body::from(
+            body.replace("LOCATION", &location_id.to_string()),
+        ))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 書籍の詳細に置き場所が含まれることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_with_location(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(|id| {
+            Ok(Some(Book {
+                id,
+                title: "Test Book".to_string(),
+                isbn: Isbn::from_stored("".into(), "".into()),
+                author: "Test Author".to_string(),
+                description: "Test Description".to_string(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+                owner: BookOwner {
+                    id: UserId::new(),
+                    name: "Test User".to_string(),
+                },
+                copies: vec![],
+                tags: vec![],
+                cover: None,
+                version: 1,
+                location: Some(location()),
+                rating: Default::default(),
+                loan_period_days: None,
+            }))
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookResponse);
+    let location = result.location.unwrap();
+    assert_eq!(
+        (
+            location.site.as_str(),
+            location.room.as_str(),
+            location.shelf.as_str()
+        ),
+        ("本社", "3F", "A")
+    );
+
+    Ok(())
+}
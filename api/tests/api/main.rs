@@ -1,2 +1,20 @@
 mod book;
+mod book_admin;
+mod book_copy_status;
+mod book_cover;
+mod book_delete;
+mod book_etag;
+mod book_export;
+mod book_import;
+mod book_patch;
+mod book_revision;
+mod book_transfer;
+mod checkout;
+mod favorite;
 mod helper;
+mod hold;
+mod location;
+mod purchase_request;
+mod review;
+mod user;
+mod wishlist;
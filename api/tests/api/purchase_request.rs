@@ -0,0 +1,344 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::{str::FromStr, sync::Arc};
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::purchase_request::{
+    PaginatedPurchaseRequestResponse, PurchaseRequestResponse, PurchaseRequestStatusName,
+};
+use kernel::{
+    model::{
+        book::BookMetadata,
+        id::{BookId, PurchaseRequestId, UserId},
+        isbn::Isbn,
+        list::PaginatedList,
+        purchase_request::{PurchaseRequest, PurchaseRequestStatus},
+        user::PurchaseRequester,
+    },
+    repository::{
+        book_metadata::MockBookMetadataProvider, purchase_request::MockPurchaseRequestRepository,
+    },
+};
+use shared::error::AppError;
+
+fn purchase_request(status: PurchaseRequestStatus) -> PurchaseRequest {
+    PurchaseRequest {
+        id: PurchaseRequestId::new(),
+        requested_by: Some(PurchaseRequester {
+            id: UserId::new(),
+            name: "Test User".into(),
+        }),
+        isbn: Isbn::from_str("978-4-7980-6170-2").unwrap(),
+        title: "Rust本".into(),
+        author: "Test Author".into(),
+        reason: "チームで読みたい".into(),
+        link: None,
+        status,
+        votes: 2,
+        voted: true,
+        book_id: None,
+        created_at: chrono::Utc::now(),
+        approved_at: None,
+        ordered_at: None,
+        received_at: None,
+        rejected_at: None,
+    }
+}
+
+/// 購入リクエストの登録時に、ISBNと理由とリンクを検証することの確認
+#[rstest]
+#[case(
+    r#"{"isbn":"4798061700","title":"Rust本","author":"Test Author","reason":"チームで読みたい","link":"https://example.com/rust"}"#,
+    StatusCode::CREATED
+)]
+#[case(
+    r#"{"isbn":"123","title":"Rust本","author":"Test Author","reason":"チームで読みたい"}"#,
+    StatusCode::BAD_REQUEST
+)]
+#[case(
+    r#"{"isbn":"4798061700","title":"Rust本","author":"Test Author","reason":""}"#,
+    StatusCode::BAD_REQUEST
+)]
+#[case(
+    r#"{"isbn":"4798061700","title":"Rust本","author":"Test Author","reason":"チームで読みたい","link":"example.com"}"#,
+    StatusCode::BAD_REQUEST
+)]
+#[tokio::test]
+async fn test_register_purchase_request(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_purchase_request_repository().returning(|| {
+        let mut mock = MockPurchaseRequestRepository::new();
+        mock.expect_create()
+            .withf(|event| event.isbn.as_isbn13() == "9784798061702")
+            .returning(|event| {
+                Ok(PurchaseRequest {
+                    isbn: event.isbn,
+                    title: event.title,
+                    author: event.author,
+                    reason: event.reason,
+                    link: event.link,
+                    votes: 0,
+                    voted: false,
+                    ..purchase_request(PurchaseRequestStatus::Open)
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/purchase-requests"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::CREATED {
+        let result = deserialize_json!(resp, PurchaseRequestResponse);
+        assert_eq!(result.status, PurchaseRequestStatusName::Open);
+        assert_eq!(result.link.as_deref(), Some("https://example.com/rust"));
+    }
+
+    Ok(())
+}
+
+/// タイトルと著者を省略した場合は、書誌情報で補うことの確認
+#[rstest]
+#[tokio::test]
+async fn test_register_purchase_request_with_metadata_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_metadata_provider().returning(|| {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_find_by_isbn().returning(|_| {
+            Ok(Some(BookMetadata {
+                title: "Metadata Title".into(),
+                author: Some("Metadata Author".into()),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+            }))
+        });
+        Arc::new(mock)
+    });
+    fixture.expect_purchase_request_repository().returning(|| {
+        let mut mock = MockPurchaseRequestRepository::new();
+        mock.expect_create()
+            .withf(|event| event.title == "Metadata Title" && event.author == "Metadata Author")
+            .returning(|event| {
+                Ok(PurchaseRequest {
+                    title: event.title,
+                    author: event.author,
+                    ..purchase_request(PurchaseRequestStatus::Open)
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/purchase-requests"))
+        .bearer()
+        .application_json()
+        .body(Body::from(
+            r#"{"isbn":"978-4-7980-6170-2","reason":"チームで読みたい"}"#,
+        ))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let result = deserialize_json!(resp, PurchaseRequestResponse);
+    assert_eq!(result.title, "Metadata Title");
+
+    Ok(())
+}
+
+/// 一覧の取得時に、状態の絞り込みがリポジトリに渡り、不明な状態は400を返すことの確認
+#[rstest]
+#[case("/purchase-requests", None, StatusCode::OK)]
+#[case(
+    "/purchase-requests?status=approved",
+    Some(PurchaseRequestStatus::Approved),
+    StatusCode::OK
+)]
+#[case("/purchase-requests?status=unknown", None, StatusCode::BAD_REQUEST)]
+#[tokio::test]
+async fn test_show_purchase_request_list(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &'static str,
+    #[case] expected_status: Option<PurchaseRequestStatus>,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_purchase_request_repository()
+        .returning(move || {
+            let mut mock = MockPurchaseRequestRepository::new();
+            mock.expect_find_all()
+                .withf(move |options| options.status == expected_status)
+                .returning(|options| {
+                    Ok(PaginatedList {
+                        total: 1,
+                        limit: options.limit,
+                        offset: options.offset,
+                        items: vec![purchase_request(
+                            options.status.unwrap_or(PurchaseRequestStatus::Open),
+                        )],
+                    })
+                });
+            Arc::new(mock)
+        });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(path)).bearer().body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::OK {
+        let result = deserialize_json!(resp, PaginatedPurchaseRequestResponse);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].votes, 2);
+        assert!(result.items[0].voted);
+    }
+
+    Ok(())
+}
+
+/// 賛成票を入れる・取り消す操作がリポジトリに渡り、入れられない場合は422を返すことの確認
+#[rstest]
+#[tokio::test]
+async fn test_vote_purchase_request(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let id = PurchaseRequestId::new();
+    let own = PurchaseRequestId::new();
+    fixture
+        .expect_purchase_request_repository()
+        .returning(move || {
+            let mut mock = MockPurchaseRequestRepository::new();
+            mock.expect_vote().returning(move |event| {
+                if event.purchase_request_id == own {
+                    Err(AppError::UnprocessableEntity(
+                        "you cannot vote for your own purchase request".into(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            });
+            mock.expect_unvote()
+                .withf(move |event| event.purchase_request_id == id)
+                .returning(|_| Ok(()));
+            Arc::new(mock)
+        });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/purchase-requests/{id}/vote")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let request = Request::put(v1(&format!("/purchase-requests/{own}/vote")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let request = Request::delete(v1(&format!("/purchase-requests/{id}/vote")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 状態を変えられるのは管理者のみで、受領済みにすると登録した書籍のIDを返すことの確認
+#[rstest]
+#[case(true, r#"{"status":"received"}"#, StatusCode::OK)]
+#[case(true, r#"{"status":"shipped"}"#, StatusCode::UNPROCESSABLE_ENTITY)]
+#[case(false, r#"{"status":"received"}"#, StatusCode::FORBIDDEN)]
+#[tokio::test]
+async fn test_change_purchase_request_status(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+    let book_id = BookId::new();
+    fixture
+        .expect_purchase_request_repository()
+        .returning(move || {
+            let mut mock = MockPurchaseRequestRepository::new();
+            mock.expect_change_status()
+                .withf(|event| event.status == PurchaseRequestStatus::Received)
+                .returning(move |event| {
+                    Ok(PurchaseRequest {
+                        id: event.purchase_request_id,
+                        book_id: Some(book_id),
+                        received_at: Some(chrono::Utc::now()),
+                        ..purchase_request(event.status)
+                    })
+                });
+            Arc::new(mock)
+        });
+
+    let router: axum::Router = make_router(fixture);
+
+    let id = PurchaseRequestId::new();
+    let request = Request::put(v1(&format!("/purchase-requests/{id}/status")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::OK {
+        let result = deserialize_json!(resp, PurchaseRequestResponse);
+        assert_eq!(result.id, id);
+        assert_eq!(result.status, PurchaseRequestStatusName::Received);
+        assert_eq!(result.book_id, Some(book_id));
+    }
+
+    Ok(())
+}
+
+/// 存在しない購入リクエストを取得すると404を返すことの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_purchase_request_404(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_purchase_request_repository().returning(|| {
+        let mut mock = MockPurchaseRequestRepository::new();
+        mock.expect_find_by_id().returning(|_, _| Ok(None));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!(
+        "/purchase-requests/{}",
+        PurchaseRequestId::new()
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, fixture_admin, make_router, v1},
+};
+
+use api::model::{
+    book::BookResponse,
+    review::{PaginatedReviewResponse, ReviewResponse},
+};
+use kernel::{
+    model::{
+        book::{Book, BookRating},
+        id::{BookId, ReviewId, UserId},
+        isbn::Isbn,
+        list::PaginatedList,
+        review::Review,
+        user::{BookOwner, Reviewer},
+    },
+    repository::{book::MockBookRepository, review::MockReviewRepository},
+};
+use shared::error::AppError;
+
+fn review(book_id: BookId, rating: i16, comment: Option<String>) -> Review {
+    Review {
+        id: ReviewId::new(),
+        book_id,
+        reviewer: Reviewer {
+            id: UserId::new(),
+            name: "Reader".into(),
+        },
+        rating,
+        comment,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+/// 評価が1〜5の範囲のレビューのみ投稿できることの確認
+#[rstest]
+#[case(r#"{"rating":5,"comment":"とても良かった"}"#, StatusCode::CREATED)]
+#[case(r#"{"rating":1}"#, StatusCode::CREATED)]
+#[case(r#"{"rating":0}"#, StatusCode::BAD_REQUEST)]
+#[case(r#"{"rating":6}"#, StatusCode::BAD_REQUEST)]
+#[case(r#"{"rating":3,"comment":""}"#, StatusCode::BAD_REQUEST)]
+#[tokio::test]
+async fn test_register_review(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_create()
+            .withf(move |event| event.book_id == book_id)
+            .returning(|event| Ok(review(event.book_id, event.rating, event.comment)));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{book_id}/reviews")))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::CREATED {
+        let result = deserialize_json!(resp, ReviewResponse);
+        assert_eq!(result.book_id, book_id);
+    }
+
+    Ok(())
+}
+
+/// 同じ書籍に2件目のレビューを投稿しようとした場合に422が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_register_review_twice_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_review_repository().returning(|| {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_create().returning(|_| {
+            Err(AppError::UnprocessableEntity(
+                "you have already reviewed this book".into(),
+            ))
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1(&format!("/books/{}/reviews", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"rating":4}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+/// レビューの一覧をlimitとoffsetを指定して取得できることの確認
+#[rstest]
+#[case("", 20, 0)]
+#[case("?limit=5&offset=10", 5, 10)]
+#[tokio::test]
+async fn test_show_review_list_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_limit: i64,
+    #[case] expected_offset: i64,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_find_by_book()
+            .withf(move |id, _| *id == book_id)
+            .returning(|book_id, options| {
+                Ok(PaginatedList {
+                    total: 1,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![review(book_id, 4, None)],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}/reviews{query}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedReviewResponse);
+    assert_eq!(result.limit, expected_limit);
+    assert_eq!(result.offset, expected_offset);
+    assert_eq!(result.items.len(), 1);
+
+    Ok(())
+}
+
+/// 変更・削除の際に、管理者かどうかがリポジトリに渡ることの確認
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[tokio::test]
+async fn test_update_and_delete_review(
+    fixture: registry::MockAppRegistryExt,
+    fixture_admin: registry::MockAppRegistryExt,
+    #[case] admin: bool,
+) -> anyhow::Result<()> {
+    let mut fixture = if admin { fixture_admin } else { fixture };
+    let book_id = BookId::new();
+    let review_id = ReviewId::new();
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_update()
+            .withf(move |event| {
+                event.review_id == review_id
+                    && event.rating == 2
+                    && event.requested_by_admin == admin
+            })
+            .returning(|event| Ok(review(event.book_id, event.rating, event.comment)));
+        mock.expect_delete()
+            .withf(move |event| event.review_id == review_id && event.requested_by_admin == admin)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!("/books/{book_id}/reviews/{review_id}")))
+        .bearer()
+        .application_json()
+        .body(Body::from(
+            r#"{"rating":2,"comment":"期待ほどではなかった"}"#,
+        ))?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let result = deserialize_json!(resp, ReviewResponse);
+    assert_eq!(result.rating, 2);
+
+    let request = Request::delete(v1(&format!("/books/{book_id}/reviews/{review_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 他のユーザーのレビューを変更しようとした場合に403が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_update_review_of_others_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_review_repository().returning(|| {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_update()
+            .returning(|_| Err(AppError::ForbiddenOperationError));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::put(v1(&format!(
+        "/books/{}/reviews/{}",
+        BookId::new(),
+        ReviewId::new()
+    )))
+    .bearer()
+    .application_json()
+    .body(Body::from(r#"{"rating":1}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+/// 書籍の詳細に評価の平均とレビューの数が含まれることの確認
+#[rstest]
+#[tokio::test]
+async fn test_show_book_with_rating(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id().returning(|id| {
+            Ok(Some(Book {
+                id,
+                title: "Test Book".to_string(),
+                isbn: Isbn::from_stored("".into(), "".into()),
+                author: "Test Author".to_string(),
+                description: "Test Description".to_string(),
+                publisher: None,
+                published_on: None,
+                page_count: None,
+                owner: BookOwner {
+                    id: UserId::new(),
+                    name: "Test User".to_string(),
+                },
+                copies: vec![],
+                tags: vec![],
+                cover: None,
+                version: 1,
+                location: None,
+                rating: BookRating {
+                    average: Some(4.5),
+                    count: 2,
+                },
+                loan_period_days: None,
+            }))
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1(&format!("/books/{book_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookResponse);
+    assert_eq!(result.average_rating, Some(4.5));
+    assert_eq!(result.review_count, 2);
+
+    Ok(())
+}
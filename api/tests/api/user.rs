@@ -0,0 +1,99 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture_auth, make_router, v1},
+};
+
+use api::model::user::UserResponse;
+use kernel::{
+    model::{role::Role, user::User},
+    repository::user::MockUserRepository,
+};
+
+/// 指定した項目のみが更新され、更新後のユーザーが返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_patch_current_user_200(
+    mut fixture_auth: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture_auth.expect_user_repository().returning(|| {
+        let mut mock = MockUserRepository::new();
+        mock.expect_find_current_user().returning(|id| {
+            Ok(Some(User {
+                id,
+                name: "dummy-user".into(),
+                email: "dummy@example.com".into(),
+                role: Role::User,
+            }))
+        });
+        mock.expect_update_profile()
+            .withf(|event| event.name.as_deref() == Some("New Name") && event.email.is_none())
+            .returning(|event| {
+                Ok(User {
+                    id: event.id,
+                    name: event.name.unwrap(),
+                    email: "dummy@example.com".into(),
+                    role: Role::User,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_auth);
+
+    let request = Request::patch(v1("/users/me"))
+        .bearer()
+        .header("Content-Type", "application/merge-patch+json")
+        .body(Body::from(r#"{"name":"New Name"}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, UserResponse);
+    assert_eq!(result.name, "New Name");
+    assert_eq!(result.email, "dummy@example.com");
+
+    Ok(())
+}
+
+/// nullや不正なメールアドレスが400になることの確認
+#[rstest]
+#[case(r#"{"name":null}"#)]
+#[case(r#"{"email":null}"#)]
+#[case(r#"{"email":"not-an-email"}"#)]
+#[tokio::test]
+async fn test_patch_current_user_400(
+    mut fixture_auth: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    fixture_auth.expect_user_repository().returning(|| {
+        let mut mock = MockUserRepository::new();
+        mock.expect_find_current_user().returning(|id| {
+            Ok(Some(User {
+                id,
+                name: "dummy-user".into(),
+                email: "dummy@example.com".into(),
+                role: Role::User,
+            }))
+        });
+        mock.expect_update_profile().never();
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture_auth);
+
+    let request = Request::patch(v1("/users/me"))
+        .bearer()
+        .header("Content-Type", "application/merge-patch+json")
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
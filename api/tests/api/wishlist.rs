@@ -0,0 +1,199 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use rstest::rstest;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{TestRequestExt, fixture, make_router, v1},
+};
+
+use api::model::{
+    notification::{NotificationKindName, NotificationsResponse},
+    wishlist::{WishlistItemResponse, WishlistItemsResponse},
+};
+use kernel::{
+    model::{
+        id::{BookId, NotificationId, WishlistItemId},
+        notification::{Notification, NotificationKind},
+        wishlist::WishlistItem,
+    },
+    repository::{notification::MockNotificationRepository, wishlist::MockWishlistRepository},
+};
+use shared::error::AppError;
+
+/// ISBNか書名のどちらかがあれば追加でき、ISBNは正規化して渡されることの確認
+#[rstest]
+#[case(r#"{"isbn":"4798061700"}"#, StatusCode::CREATED)]
+#[case(r#"{"title":"まだ出ていない本"}"#, StatusCode::CREATED)]
+#[case(r#"{}"#, StatusCode::BAD_REQUEST)]
+#[case(r#"{"title":""}"#, StatusCode::BAD_REQUEST)]
+#[case(r#"{"isbn":"123"}"#, StatusCode::BAD_REQUEST)]
+#[tokio::test]
+async fn test_add_wishlist_item(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_wishlist_repository().returning(|| {
+        let mut mock = MockWishlistRepository::new();
+        mock.expect_create()
+            .withf(|event| {
+                event
+                    .isbn
+                    .as_ref()
+                    .is_none_or(|isbn| isbn.as_isbn13() == "9784798061702")
+            })
+            .returning(|event| {
+                Ok(WishlistItem {
+                    id: WishlistItemId::new(),
+                    isbn: event.isbn,
+                    title: event.title,
+                    fulfilled_book_id: None,
+                    created_at: chrono::Utc::now(),
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/users/me/wishlist"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), expected);
+
+    if expected == StatusCode::CREATED {
+        let result = deserialize_json!(resp, WishlistItemResponse);
+        assert!(result.isbn13.is_some() || result.title.is_some());
+    }
+
+    Ok(())
+}
+
+/// 登録済みの書籍のISBNを追加しようとした場合に422が返ることの確認
+#[rstest]
+#[tokio::test]
+async fn test_add_registered_isbn_422(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_wishlist_repository().returning(|| {
+        let mut mock = MockWishlistRepository::new();
+        mock.expect_create().returning(|_| {
+            Err(AppError::UnprocessableEntity(
+                "a book with the specified isbn is already in the library".into(),
+            ))
+        });
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::post(v1("/users/me/wishlist"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"isbn":"978-4-7980-6170-2"}"#))?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
+
+/// ほしいものリストの取得と項目の削除ができることの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_and_delete_wishlist(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let item_id = WishlistItemId::new();
+    fixture.expect_wishlist_repository().returning(move || {
+        let mut mock = MockWishlistRepository::new();
+        mock.expect_find_all().returning(move |_| {
+            Ok(vec![WishlistItem {
+                id: item_id,
+                isbn: None,
+                title: Some("まだ出ていない本".into()),
+                fulfilled_book_id: None,
+                created_at: chrono::Utc::now(),
+            }])
+        });
+        mock.expect_delete()
+            .withf(move |event| event.wishlist_item_id == item_id)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/wishlist"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let result = deserialize_json!(resp, WishlistItemsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].id, item_id);
+
+    let request = Request::delete(v1(&format!("/users/me/wishlist/{item_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+/// 通知の取得と既読にする操作ができることの確認
+#[rstest]
+#[tokio::test]
+async fn test_get_and_read_notifications(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let notification_id = NotificationId::new();
+    fixture.expect_notification_repository().returning(move || {
+        let mut mock = MockNotificationRepository::new();
+        mock.expect_find_all().returning(move |_| {
+            Ok(vec![Notification {
+                id: notification_id,
+                kind: NotificationKind::WishlistBookRegistered,
+                book_id: Some(BookId::new()),
+                message: "ほしいものリストの「Rust本」が登録されました".into(),
+                read_at: None,
+                created_at: chrono::Utc::now(),
+            }])
+        });
+        mock.expect_mark_read()
+            .withf(move |event| event.notification_id == notification_id)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let router: axum::Router = make_router(fixture);
+
+    let request = Request::get(v1("/users/me/notifications"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = router.clone().oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let result = deserialize_json!(resp, NotificationsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(
+        result.items[0].kind,
+        NotificationKindName::WishlistBookRegistered
+    );
+
+    let request = Request::put(v1(&format!(
+        "/users/me/notifications/{notification_id}/read"
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = router.oneshot(request).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
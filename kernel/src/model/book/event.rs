@@ -1,10 +1,19 @@
-use crate::model::id::{BookId, UserId};
+use crate::model::{
+    book::{CopyStatusAction, CoverImageType},
+    id::{BookId, BookRevisionId, CopyId, LocationId, UserId},
+    isbn::Isbn,
+};
+use chrono::NaiveDate;
 
+#[derive(Debug)]
 pub struct CreateBook {
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
 }
 
 #[derive(Debug)]
@@ -12,13 +21,208 @@ pub struct UpdateBook {
     pub book_id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も更新できる
+    pub requested_by_admin: bool,
+    /// 指定された場合、書誌情報の版が一致するときのみ更新する
+    pub expected_version: Option<i32>,
+}
+
+/// 書籍の指定された項目のみを更新する
+/// Noneの項目は変更せず、任意項目のSome(None)は値を削除する
+#[derive(Debug)]
+pub struct PatchBook {
+    pub book_id: BookId,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub isbn: Option<Isbn>,
+    pub description: Option<String>,
+    pub publisher: Option<Option<String>>,
+    pub published_on: Option<Option<NaiveDate>>,
+    pub page_count: Option<Option<i32>>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も更新できる
+    pub requested_by_admin: bool,
+    /// 指定された場合、書誌情報の版が一致するときのみ更新する
+    pub expected_version: Option<i32>,
+}
+
+/// 書籍の内容を、指定した変更履歴の時点（変更後の値）に戻す
+/// 戻した内容も新しい変更履歴として記録する
+#[derive(Debug)]
+pub struct RevertBook {
+    pub book_id: BookId,
+    pub revision_id: BookRevisionId,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も戻せる
+    pub requested_by_admin: bool,
+}
+
+/// 蔵書の状態を変える
+#[derive(Debug)]
+pub struct ChangeCopyStatus {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub action: CopyStatusAction,
+    pub note: String,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍の蔵書も変更できる
+    pub requested_by_admin: bool,
+}
+
+/// 書籍の置き場所を移す。Noneの場合は置き場所を未設定に戻す
+#[derive(Debug)]
+pub struct MoveBook {
+    pub book_id: BookId,
+    pub location_id: Option<LocationId>,
     pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も移せる
+    pub requested_by_admin: bool,
 }
 
+/// 書籍の貸出期間を変える。Noneの場合は設定の既定値に戻す
+#[derive(Debug)]
+pub struct SetBookLoanPeriod {
+    pub book_id: BookId,
+    pub loan_period_days: Option<i32>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も変更できる
+    pub requested_by_admin: bool,
+}
+
+/// 書籍を削除済みにする。削除済みの書籍は復元できる
 #[derive(Debug)]
 pub struct DeleteBook {
     pub book_id: BookId,
     pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も削除できる
+    pub requested_by_admin: bool,
+    /// 指定された場合、書誌情報の版が一致するときのみ削除する
+    pub expected_version: Option<i32>,
+}
+
+/// 書籍を他のユーザーに譲渡する
+/// require_acceptanceがtrueの場合は、譲渡先が承認するまで所有者を変えない
+#[derive(Debug)]
+pub struct TransferBook {
+    pub book_id: BookId,
+    pub to_user: UserId,
+    pub require_acceptance: bool,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍も譲渡できる
+    pub requested_by_admin: bool,
+}
+
+/// 承認待ちの書籍の譲渡を、譲渡先のユーザーが承認する
+#[derive(Debug)]
+pub struct AcceptBookTransfer {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+}
+
+/// 承認待ちの書籍の譲渡を、譲渡先のユーザーが拒否する
+#[derive(Debug)]
+pub struct DeclineBookTransfer {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+}
+
+/// ユーザーが所有するすべての書籍を、他のユーザーに譲渡する
+/// 削除済みの書籍も対象とし、承認は求めない
+#[derive(Debug)]
+pub struct TransferAllBooks {
+    pub from_user: UserId,
+    pub to_user: UserId,
+    /// 譲渡を行う管理者。譲渡した書籍ごとに管理者の操作として記録する
+    pub requested_user: UserId,
+}
+
+/// 削除済みの書籍を復元する
+#[derive(Debug)]
+pub struct RestoreBook {
+    pub book_id: BookId,
+}
+
+/// 削除済みの書籍を完全に削除する
+#[derive(Debug)]
+pub struct PurgeBook {
+    pub book_id: BookId,
+}
+
+/// 書籍に蔵書を追加する
+#[derive(Debug)]
+pub struct CreateBookCopy {
+    pub book_id: BookId,
+    /// 未指定の場合は連番で採番する
+    pub barcode: Option<String>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍にも蔵書を追加できる
+    pub requested_by_admin: bool,
+}
+
+/// 書籍から蔵書を取り除く
+#[derive(Debug)]
+pub struct DeleteBookCopy {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍からも蔵書を取り除ける
+    pub requested_by_admin: bool,
+}
+
+/// 書籍を一括で登録する
+/// すべての行を1つのトランザクションで登録する
+#[derive(Debug)]
+pub struct ImportBooks {
+    pub rows: Vec<ImportBookRow>,
+    /// 所有者のメールアドレスが指定されていない行の所有者
+    pub default_owner: Option<UserId>,
+    /// trueの場合は登録結果だけを返し、登録内容はロールバックする
+    pub dry_run: bool,
+}
+
+/// 一括登録する書籍1行分
+#[derive(Debug)]
+pub struct ImportBookRow {
+    /// 取り込み元での行番号
+    pub line: u64,
+    pub book: CreateBook,
+    pub owner_email: Option<String>,
+}
+
+/// 書籍にタグを付ける。付いているタグは変更しない
+#[derive(Debug)]
+pub struct AddBookTags {
+    pub book_id: BookId,
+    /// 小文字に正規化したタグ名
+    pub tags: Vec<String>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍にもタグを付けられる
+    pub requested_by_admin: bool,
+}
+
+/// 書籍からタグを外す
+#[derive(Debug)]
+pub struct RemoveBookTag {
+    pub book_id: BookId,
+    pub tag: String,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍からもタグを外せる
+    pub requested_by_admin: bool,
+}
+
+/// 書籍の書影を登録する。登録済みの場合は置き換える
+#[derive(Debug)]
+pub struct UploadBookCover {
+    pub book_id: BookId,
+    pub image_type: CoverImageType,
+    pub data: Vec<u8>,
+    pub requested_user: UserId,
+    /// trueの場合は所有者以外の書籍にも書影を登録できる
+    pub requested_by_admin: bool,
 }
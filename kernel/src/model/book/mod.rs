@@ -1,9 +1,16 @@
 use crate::model::{
-    id::{BookId, CheckoutId},
-    user::{BookOwner, CheckoutUser},
+    id::{
+        BookAdminActionId, BookId, BookRevisionId, BookTransferId, CheckoutId, CopyId,
+        CopyStatusChangeId, LocationId, UserId,
+    },
+    isbn::Isbn,
+    list::SortOrder,
+    location::Location,
+    user::{BookEditor, BookOwner, CheckoutUser},
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use strum::{AsRefStr, EnumString};
 
 pub mod event;
 
@@ -12,17 +19,395 @@ pub struct Book {
     pub id: BookId,
     pub title: String,
     pub author: String,
-    pub isbn: String,
+    pub isbn: Isbn,
     pub description: String,
+    pub publisher: Option<String>,
+    /// 発行日
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
     pub owner: BookOwner,
+    /// 書籍に属する物理的な蔵書
+    pub copies: Vec<BookCopy>,
+    /// 書籍に付けられたタグ名（名前順）
+    pub tags: Vec<String>,
+    /// 書影の画像形式。書影が登録されていない場合はNone
+    pub cover: Option<CoverImageType>,
+    /// 書誌情報の版。書誌情報を変更するたびに増える
+    pub version: i32,
+    /// 書籍の置き場所。未設定の場合はNone
+    pub location: Option<Location>,
+    /// レビューの評価の集計
+    pub rating: BookRating,
+    /// 書籍ごとの貸出期間（日数）。未設定の場合は設定の既定値を使う
+    pub loan_period_days: Option<i32>,
+}
+
+impl Book {
+    /// 蔵書の総数を返す
+    pub fn total_copies(&self) -> usize {
+        self.copies.len()
+    }
+
+    /// 貸出可能な蔵書の数を返す
+    pub fn available_copies(&self) -> usize {
+        self.copies.iter().filter(|c| c.is_available()).count()
+    }
+}
+
+/// 書籍に付けられた評価の集計
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BookRating {
+    /// 評価の平均。レビューがない場合はNone
+    pub average: Option<f64>,
+    pub count: i64,
+}
+
+/// 変更履歴として記録する、書籍の編集可能な項目の値
+#[derive(Debug, Clone)]
+pub struct BookSnapshot {
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    pub description: String,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
+}
+
+impl BookSnapshot {
+    /// otherと値が異なる項目の名前を返す
+    pub fn changed_fields(&self, other: &Self) -> Vec<BookField> {
+        let mut fields = Vec::new();
+        if self.title != other.title {
+            fields.push(BookField::Title);
+        }
+        if self.author != other.author {
+            fields.push(BookField::Author);
+        }
+        // ISBNは表示用の文字列だけが変わった場合も変更とみなす
+        if self.isbn.as_isbn13() != other.isbn.as_isbn13()
+            || self.isbn.display() != other.isbn.display()
+        {
+            fields.push(BookField::Isbn);
+        }
+        if self.description != other.description {
+            fields.push(BookField::Description);
+        }
+        if self.publisher != other.publisher {
+            fields.push(BookField::Publisher);
+        }
+        if self.published_on != other.published_on {
+            fields.push(BookField::PublishedOn);
+        }
+        if self.page_count != other.page_count {
+            fields.push(BookField::PageCount);
+        }
+        fields
+    }
+}
+
+/// 変更履歴に記録する書籍の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "camelCase")]
+pub enum BookField {
+    Title,
+    Author,
+    Isbn,
+    Description,
+    Publisher,
+    PublishedOn,
+    PageCount,
+}
+
+/// 書籍の変更履歴の1件分
+#[derive(Debug)]
+pub struct BookRevision {
+    pub id: BookRevisionId,
+    pub book_id: BookId,
+    /// 変更したユーザー。ユーザーが削除済みの場合はNone
+    pub changed_by: Option<BookEditor>,
+    pub changed_at: DateTime<Utc>,
+    pub before: BookSnapshot,
+    pub after: BookSnapshot,
+}
+
+impl BookRevision {
+    /// 変更された項目の名前を返す
+    pub fn changed_fields(&self) -> Vec<BookField> {
+        self.before.changed_fields(&self.after)
+    }
+}
+
+/// 管理者が所有者以外の書籍に対して行った操作の記録
+#[derive(Debug)]
+pub struct BookAdminAction {
+    pub id: BookAdminActionId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub action: BookAdminActionKind,
+    /// 操作した管理者。管理者のユーザーが削除済みの場合はNone
+    pub admin: Option<BookEditor>,
+    pub acted_at: DateTime<Utc>,
+}
+
+/// 管理者が所有者以外の書籍に対して行った操作の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BookAdminActionKind {
+    /// 書籍の内容の更新（変更履歴による復元も含む）
+    Update,
+    /// 書籍の削除
+    Delete,
+    /// 書籍の譲渡
+    Transfer,
+    /// 書籍の置き場所の移動
+    Move,
+    /// 書籍の貸出期間の変更
+    SetLoanPeriod,
+    /// 蔵書の追加
+    AddCopy,
+    /// 蔵書の取り除き
+    DeleteCopy,
+    /// タグの追加
+    AddTags,
+    /// タグの取り外し
+    RemoveTag,
+    /// 書影の登録
+    UploadCover,
+}
+
+/// 譲渡先の承認を待っている書籍の譲渡
+#[derive(Debug)]
+pub struct BookTransfer {
+    pub id: BookTransferId,
+    pub book_id: BookId,
+    pub book_title: String,
+    /// 譲渡を依頼した時点の所有者
+    pub from: BookOwner,
+    /// 譲渡先。承認すると所有者になる
+    pub to: BookOwner,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// ISBNから取得した書誌情報
+/// 提供元に登録されていない項目はNoneとする
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookMetadata {
+    pub title: String,
+    pub author: Option<String>,
+    pub publisher: Option<String>,
+    pub published_on: Option<NaiveDate>,
+    pub page_count: Option<i32>,
+}
+
+/// 書影として受け付ける画像形式
+/// 文字列表現はContent-Typeと同じ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+pub enum CoverImageType {
+    #[strum(serialize = "image/jpeg")]
+    Jpeg,
+    #[strum(serialize = "image/png")]
+    Png,
+}
+
+/// 書影の大きさ。サムネイルはアップロード時に元画像から生成する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverSize {
+    Original,
+    Thumbnail,
+}
+
+/// 書影の画像データ
+#[derive(Debug)]
+pub struct BookCover {
+    pub image_type: CoverImageType,
+    pub data: Vec<u8>,
+}
+
+/// 書籍に属する物理的な蔵書を表す型
+/// 貸出は蔵書単位で行う
+#[derive(Debug)]
+pub struct BookCopy {
+    pub id: CopyId,
+    pub barcode: String,
+    pub status: CopyStatus,
     pub checkout: Option<Checkout>,
 }
 
-/// ページネーションの範囲を指定するための設定値を格納する型
+impl BookCopy {
+    /// 貸し出せる状態で、貸出中でもないかどうかを返す
+    pub fn is_available(&self) -> bool {
+        self.status.is_lendable() && self.checkout.is_none()
+    }
+}
+
+/// 蔵書の状態
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CopyStatus {
+    #[default]
+    Available,
+    InRepair,
+    Lost,
+    Damaged,
+    /// 除籍
+    Withdrawn,
+}
+
+impl CopyStatus {
+    /// 貸し出せる状態かどうかを返す
+    pub fn is_lendable(self) -> bool {
+        self == Self::Available
+    }
+}
+
+/// 蔵書の状態を変える操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CopyStatusAction {
+    /// 紛失の報告。貸出中の場合は貸出を紛失として終える
+    ReportLost,
+    /// 紛失した蔵書が見つかった
+    MarkFound,
+    ReportDamaged,
+    SendToRepair,
+    FinishRepair,
+    /// 除籍
+    Withdraw,
+}
+
+impl CopyStatusAction {
+    /// 操作後の状態を返す
+    pub fn target(self) -> CopyStatus {
+        match self {
+            Self::ReportLost => CopyStatus::Lost,
+            Self::MarkFound | Self::FinishRepair => CopyStatus::Available,
+            Self::ReportDamaged => CopyStatus::Damaged,
+            Self::SendToRepair => CopyStatus::InRepair,
+            Self::Withdraw => CopyStatus::Withdrawn,
+        }
+    }
+
+    /// 指定した状態の蔵書に対して、この操作を行えるかどうかを返す
+    pub fn can_apply_to(self, from: CopyStatus) -> bool {
+        use CopyStatus::*;
+        match self {
+            Self::ReportLost => matches!(from, Available | Damaged | InRepair),
+            Self::MarkFound => from == Lost,
+            Self::ReportDamaged => from == Available,
+            Self::SendToRepair => matches!(from, Available | Damaged),
+            Self::FinishRepair => from == InRepair,
+            Self::Withdraw => from != Withdrawn,
+        }
+    }
+
+    /// 蔵書を借りているユーザーも行える操作で、貸出中の蔵書にも行えるかどうかを返す
+    pub fn allowed_for_borrower(self) -> bool {
+        matches!(self, Self::ReportLost | Self::ReportDamaged)
+    }
+}
+
+/// 蔵書の状態の変更履歴の1件分
 #[derive(Debug)]
+pub struct CopyStatusChange {
+    pub id: CopyStatusChangeId,
+    pub copy_id: CopyId,
+    pub action: CopyStatusAction,
+    pub from: CopyStatus,
+    pub to: CopyStatus,
+    pub note: String,
+    /// 変更したユーザー。ユーザーが削除済みの場合はNone
+    pub changed_by: Option<BookEditor>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// ページネーションの範囲と、検索・絞り込みの条件を指定するための設定値を格納する型
+#[derive(Debug, Default)]
 pub struct BookListOptions {
     pub limit: i64,
     pub offset: i64,
+    /// タイトル・著者・説明・ISBNを対象とした検索キーワード
+    pub q: Option<String>,
+    /// 著者名（部分一致）
+    pub author: Option<String>,
+    /// 所有者のユーザーID
+    pub owner: Option<UserId>,
+    /// 置き場所
+    pub location: Option<LocationId>,
+    /// 指定したユーザーがお気に入りに登録している書籍に絞り込む
+    pub favorited_by: Option<UserId>,
+    /// 貸出状態
+    pub availability: Option<BookAvailability>,
+    /// 小文字に正規化した重複のないタグ名。指定したすべてのタグが付いた書籍に絞り込む
+    pub tags: Vec<String>,
+    /// 並び順。未指定の場合、検索キーワードがあれば一致度順、なければ登録日時の新しい順とする
+    pub sort: Option<BookSort>,
+}
+
+/// 削除済みの書籍を表す型
+#[derive(Debug)]
+pub struct DeletedBook {
+    pub book: Book,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// 削除済みの書籍一覧のページネーションの範囲を指定するための設定値を格納する型
+#[derive(Debug, Default)]
+pub struct DeletedBookListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// 書籍一覧の並び順を表す型
+/// 同じ値の書籍同士はbook_idで並べ、ページをまたいでも順序が変わらないようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookSort {
+    pub key: BookSortKey,
+    pub order: SortOrder,
+}
+
+impl BookSort {
+    /// 並び替えのキーに応じたデフォルトの昇順・降順で並び順を作成する
+    pub fn by(key: BookSortKey) -> Self {
+        Self {
+            key,
+            order: key.default_order(),
+        }
+    }
+}
+
+/// 書籍一覧の並び替えのキー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSortKey {
+    Title,
+    Author,
+    CreatedAt,
+    UpdatedAt,
+    /// 貸出回数（返却済みも含む）
+    MostBorrowed,
+    /// 評価の平均。レビューのない書籍は最も低い評価として扱う
+    Rating,
+}
+
+impl BookSortKey {
+    /// 文字列のキーは昇順、日時や回数、評価のキーは降順をデフォルトとする
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::Title | Self::Author => SortOrder::Asc,
+            Self::CreatedAt | Self::UpdatedAt | Self::MostBorrowed | Self::Rating => {
+                SortOrder::Desc
+            }
+        }
+    }
+}
+
+/// 書籍の貸出状態を表す型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookAvailability {
+    /// 貸出可能な蔵書が1冊以上ある
+    Available,
+    /// すべての蔵書が貸出中（蔵書がない場合も含む）
+    CheckedOut,
 }
 
 #[derive(Debug)]
@@ -30,4 +415,45 @@ pub struct Checkout {
     pub checkout_id: CheckoutId,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
+    /// 返却期限
+    pub due_at: DateTime<Utc>,
+    /// 延長した回数
+    pub renewal_count: i64,
+}
+
+/// 書籍の一括登録における1行分の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookImportResult {
+    pub line: u64,
+    pub status: BookImportStatus,
+}
+
+/// 書籍の一括登録における行ごとの処理結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookImportStatus {
+    /// 登録した（ドライランの場合は登録できる）
+    Created(BookId),
+    /// 同じISBNの書籍が登録済み、または取り込み元の前の行にあるため登録しなかった
+    Duplicate,
+    /// 内容に不備があるため登録しなかった
+    Invalid(String),
+}
+
+/// 書籍の目録の1行分。蔵書ごとに1行とし、蔵書がない書籍は蔵書なしの1行とする
+#[derive(Debug)]
+pub struct BookCatalogEntry {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: Isbn,
+    pub description: String,
+    pub owner: BookOwner,
+    pub copy: Option<BookCopy>,
+}
+
+/// タグ名と、そのタグが付いた書籍の数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagCount {
+    pub name: String,
+    pub book_count: i64,
 }
@@ -1,11 +1,13 @@
 use chrono::{DateTime, Utc};
 use derive_new::new;
 
-use crate::model::id::{BookId, CheckoutId, UserId};
+use crate::model::id::{BookId, CheckoutId, CopyId, UserId};
 
 #[derive(new)]
 pub struct CreateCheckout {
     pub book_id: BookId,
+    /// 貸し出す蔵書。未指定の場合は貸出可能な蔵書のいずれかを貸し出す
+    pub copy_id: Option<CopyId>,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
 }
@@ -17,3 +19,12 @@ pub struct UpdateReturned {
     pub returned_by: UserId,
     pub returned_at: DateTime<Utc>,
 }
+
+/// 貸出を延長する。返却期限を貸出期間の分だけ延ばす
+#[derive(new)]
+pub struct RenewCheckout {
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub renewed_by: UserId,
+    pub renewed_at: DateTime<Utc>,
+}
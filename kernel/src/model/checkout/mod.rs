@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
 
-use crate::model::id::{BookId, CheckoutId, UserId};
+use crate::model::id::{BookId, CheckoutId, CopyId, UserId};
 
 pub mod event;
 
@@ -9,14 +10,33 @@ pub struct Checkout {
     pub id: CheckoutId,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
+    /// 返却期限。貸出時に書籍の貸出期間から決まる
+    pub due_at: DateTime<Utc>,
+    /// 延長した回数
+    pub renewal_count: i64,
     pub returned_at: Option<DateTime<Utc>>,
+    /// 貸出の終わり方。未返却の場合はNone
+    pub outcome: Option<CheckoutOutcome>,
     pub book: CheckoutBook,
 }
 
+/// 貸出の終わり方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CheckoutOutcome {
+    Returned,
+    /// 借りたユーザーなどから紛失の報告があった
+    Lost,
+}
+
 #[derive(Debug)]
 pub struct CheckoutBook {
     pub id: BookId,
     pub title: String,
     pub author: String,
+    /// 表示用のISBN
     pub isbn: String,
+    /// 貸し出した蔵書
+    pub copy_id: CopyId,
+    pub barcode: String,
 }
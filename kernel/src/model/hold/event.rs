@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+
+use crate::model::id::{BookId, HoldId, UserId};
+
+/// 貸出中の書籍を予約する
+#[derive(new)]
+pub struct CreateHold {
+    pub book_id: BookId,
+    pub user_id: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 自分の予約を取り消す
+#[derive(new)]
+pub struct DeleteHold {
+    pub hold_id: HoldId,
+    pub user_id: UserId,
+    pub cancelled_at: DateTime<Utc>,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::id::{BookId, HoldId};
+
+pub mod event;
+
+/// 貸出中の書籍の予約
+/// 蔵書が返却されると、順番待ちの先頭から受け取りの期限つきで取り置きになる
+#[derive(Debug)]
+pub struct Hold {
+    pub id: HoldId,
+    pub book: HoldBook,
+    /// 書籍の予約の中での順番（1始まり）。取り置き中の予約も含めて数える
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    /// 取り置きを始めた日時。順番待ちの間はNone
+    pub ready_at: Option<DateTime<Utc>>,
+    /// 受け取りの期限。順番待ちの間はNone
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Hold {
+    /// 取り置き中かどうかを返す
+    pub fn is_ready(&self) -> bool {
+        self.ready_at.is_some()
+    }
+}
+
+#[derive(Debug)]
+pub struct HoldBook {
+    pub id: BookId,
+    pub title: String,
+    pub author: String,
+}
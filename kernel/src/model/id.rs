@@ -68,3 +68,14 @@ macro_rules! define_id {
 define_id!(UserId);
 define_id!(BookId);
 define_id!(CheckoutId);
+define_id!(CopyId);
+define_id!(BookRevisionId);
+define_id!(BookAdminActionId);
+define_id!(BookTransferId);
+define_id!(LocationId);
+define_id!(CopyStatusChangeId);
+define_id!(ReviewId);
+define_id!(WishlistItemId);
+define_id!(NotificationId);
+define_id!(PurchaseRequestId);
+define_id!(HoldId);
@@ -0,0 +1,211 @@
+use std::{fmt, str::FromStr};
+
+/// ISBNを表す値オブジェクト
+/// ISBN-10・ISBN-13のどちらもハイフンの有無を問わず受け付け、
+/// チェックディジットを検証したうえでISBN-13に正規化して保持する
+/// ISBN-13は書籍用の978・979で始まるもののみとする
+#[derive(Debug, Clone)]
+pub struct Isbn {
+    /// ハイフンなしのISBN-13
+    isbn13: String,
+    /// 表示用の文字列。入力の形式によらず、ISBN-13から同じ形式で作る
+    display: String,
+}
+
+impl Isbn {
+    /// DBに保存済みの値から復元する。値の検証は行わない
+    pub fn from_stored(isbn13: String, display: String) -> Self {
+        Self { isbn13, display }
+    }
+
+    /// ハイフンなしのISBN-13を返す。保存や重複チェックにはこちらを使う
+    pub fn as_isbn13(&self) -> &str {
+        &self.isbn13
+    }
+
+    /// 表示用の文字列を返す
+    pub fn display(&self) -> &str {
+        &self.display
+    }
+}
+
+/// 正規化したISBN-13が同じであれば、同じISBNとみなす
+impl PartialEq for Isbn {
+    fn eq(&self, other: &Self) -> bool {
+        self.isbn13 == other.isbn13
+    }
+}
+
+impl Eq for Isbn {}
+
+impl std::hash::Hash for Isbn {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.isbn13.hash(state);
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = IsbnError;
+
+    /// ISBN-10またはISBN-13の文字列からISBNを生成する
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let chars = trimmed
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| c.to_ascii_uppercase())
+            .collect::<Vec<char>>();
+
+        let isbn13 = match chars.len() {
+            10 => isbn10_to_isbn13(&chars)?,
+            13 => {
+                if !chars.iter().all(char::is_ascii_digit) {
+                    return Err(IsbnError::InvalidCharacter);
+                }
+                let digits = chars.iter().collect::<String>();
+                if !digits.starts_with("978") && !digits.starts_with("979") {
+                    return Err(IsbnError::InvalidPrefix);
+                }
+                if isbn13_check_digit(&digits[..12]) != chars[12] {
+                    return Err(IsbnError::InvalidCheckDigit);
+                }
+                digits
+            }
+            _ => return Err(IsbnError::InvalidLength),
+        };
+
+        // 同じISBNが入力の仕方で異なる表示にならないよう、接頭部の後にのみハイフンを入れる
+        let display = format!("{}-{}", &isbn13[..3], &isbn13[3..]);
+
+        Ok(Self { isbn13, display })
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display)
+    }
+}
+
+/// ISBNの解析に失敗した理由を表す型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsbnError {
+    /// ハイフンを除いた桁数が10桁でも13桁でもない
+    InvalidLength,
+    /// 数字（ISBN-10の末尾のみX）以外の文字が含まれている
+    InvalidCharacter,
+    /// ISBN-13が書籍用の978・979で始まっていない
+    InvalidPrefix,
+    /// チェックディジットが一致しない
+    InvalidCheckDigit,
+}
+
+impl fmt::Display for IsbnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidLength => "ISBN must have 10 or 13 digits",
+            Self::InvalidCharacter => "ISBN contains invalid characters",
+            Self::InvalidPrefix => "ISBN-13 must start with 978 or 979",
+            Self::InvalidCheckDigit => "ISBN check digit does not match",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl std::error::Error for IsbnError {}
+
+/// ISBN-10のチェックディジットを検証し、ISBN-13に変換する
+fn isbn10_to_isbn13(chars: &[char]) -> Result<String, IsbnError> {
+    let valid_chars = chars[..9].iter().all(char::is_ascii_digit)
+        && (chars[9].is_ascii_digit() || chars[9] == 'X');
+    if !valid_chars {
+        return Err(IsbnError::InvalidCharacter);
+    }
+
+    // 各桁に10から2までの重みを掛けた合計が11の倍数になる（Xは10を表す）
+    let sum = chars
+        .iter()
+        .zip((1..=10).rev())
+        .map(|(c, weight)| c.to_digit(10).unwrap_or(10) * weight)
+        .sum::<u32>();
+    if sum % 11 != 0 {
+        return Err(IsbnError::InvalidCheckDigit);
+    }
+
+    let body = format!("978{}", chars[..9].iter().collect::<String>());
+    let check_digit = isbn13_check_digit(&body);
+    Ok(format!("{body}{check_digit}"))
+}
+
+/// ISBN-13の先頭12桁からチェックディジットを算出する
+fn isbn13_check_digit(digits: &str) -> char {
+    // 奇数桁に1、偶数桁に3の重みを掛けた合計から算出する
+    let sum = digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d } else { d * 3 })
+        .sum::<u32>();
+    char::from_digit((10 - sum % 10) % 10, 10).unwrap_or('0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_isbn13() -> anyhow::Result<()> {
+        let isbn = Isbn::from_str("9784798061702")?;
+        assert_eq!(isbn.as_isbn13(), "9784798061702");
+        assert_eq!(isbn.display(), "978-4798061702");
+
+        // ハイフンの位置によらず、表示用の形式は同じになる
+        let isbn = Isbn::from_str("978-4-7980-6170-2")?;
+        assert_eq!(isbn.as_isbn13(), "9784798061702");
+        assert_eq!(isbn.display(), "978-4798061702");
+        assert_eq!(
+            Isbn::from_str("978 47980 61702")?.display(),
+            "978-4798061702"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_isbn10() -> anyhow::Result<()> {
+        // ISBN-10はISBN-13に正規化される
+        let isbn = Isbn::from_str("4-7980-6170-0")?;
+        assert_eq!(isbn.as_isbn13(), "9784798061702");
+        assert_eq!(isbn, Isbn::from_str("9784798061702")?);
+
+        // 末尾のチェックディジットがXの場合
+        let isbn = Isbn::from_str("080442957x")?;
+        assert_eq!(isbn.as_isbn13(), "9780804429573");
+        assert_eq!(isbn.display(), "978-0804429573");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_isbn() {
+        assert_eq!(
+            Isbn::from_str("978479806170").unwrap_err(),
+            IsbnError::InvalidLength
+        );
+        assert_eq!(
+            Isbn::from_str("97847980617O2").unwrap_err(),
+            IsbnError::InvalidCharacter
+        );
+        assert_eq!(
+            Isbn::from_str("9784798061703").unwrap_err(),
+            IsbnError::InvalidCheckDigit
+        );
+        // チェックディジットが正しくても、書籍用でないEAN-13は受け付けない
+        assert_eq!(
+            Isbn::from_str("4901234567894").unwrap_err(),
+            IsbnError::InvalidPrefix
+        );
+        assert_eq!(
+            Isbn::from_str("4-7980-6170-5").unwrap_err(),
+            IsbnError::InvalidCheckDigit
+        );
+    }
+}
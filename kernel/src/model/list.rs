@@ -11,3 +11,34 @@ impl<T> PaginatedList<T> {
         self.items
     }
 }
+
+/// カーソルによるページネーションの結果を格納する型
+/// 件数の集計を行わないため、totalは持たない
+#[derive(Debug)]
+pub struct CursorPaginatedList<T> {
+    pub items: Vec<T>,
+    pub limit: i64,
+    /// 次のページを取得するためのカーソル。次のページがない場合はNone
+    pub next_cursor: Option<String>,
+}
+
+impl<T> CursorPaginatedList<T> {
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+/// カーソルによるページネーションの範囲を指定するための設定値を格納する型
+#[derive(Debug, Default)]
+pub struct CursorOptions {
+    pub limit: i64,
+    /// 前のページで返されたカーソル。Noneの場合は先頭から取得する
+    pub cursor: Option<String>,
+}
+
+/// 昇順・降順を表す型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
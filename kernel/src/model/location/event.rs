@@ -0,0 +1,23 @@
+use derive_new::new;
+
+use crate::model::id::LocationId;
+
+#[derive(new)]
+pub struct CreateLocation {
+    pub site: String,
+    pub room: String,
+    pub shelf: String,
+}
+
+#[derive(new)]
+pub struct UpdateLocation {
+    pub location_id: LocationId,
+    pub site: String,
+    pub room: String,
+    pub shelf: String,
+}
+
+#[derive(new)]
+pub struct DeleteLocation {
+    pub location_id: LocationId,
+}
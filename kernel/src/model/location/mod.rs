@@ -0,0 +1,13 @@
+use crate::model::id::LocationId;
+
+pub mod event;
+
+/// 書籍を置いている場所
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub id: LocationId,
+    /// 拠点（建物など）
+    pub site: String,
+    pub room: String,
+    pub shelf: String,
+}
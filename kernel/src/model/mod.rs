@@ -1,7 +1,14 @@
 pub mod auth;
 pub mod book;
 pub mod checkout;
+pub mod hold;
 pub mod id;
+pub mod isbn;
 pub mod list;
+pub mod location;
+pub mod notification;
+pub mod purchase_request;
+pub mod review;
 pub mod role;
 pub mod user;
+pub mod wishlist;
@@ -0,0 +1,9 @@
+use derive_new::new;
+
+use crate::model::id::{NotificationId, UserId};
+
+#[derive(new)]
+pub struct MarkNotificationRead {
+    pub user_id: UserId,
+    pub notification_id: NotificationId,
+}
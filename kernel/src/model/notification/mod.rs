@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
+
+use crate::model::id::{BookId, NotificationId};
+
+pub mod event;
+
+/// ユーザーへの通知
+#[derive(Debug)]
+pub struct Notification {
+    pub id: NotificationId,
+    pub kind: NotificationKind,
+    /// 通知に関係する書籍。書籍に関係しない通知の場合はNone
+    pub book_id: Option<BookId>,
+    pub message: String,
+    /// 既読にした日時。未読の場合はNone
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 通知の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum NotificationKind {
+    /// ほしいものリストのISBNに一致する書籍が登録された
+    WishlistBookRegistered,
+    /// 自分の購入リクエストの状態が変わった
+    PurchaseRequestStatusChanged,
+    /// 予約した書籍の取り置きが始まった
+    HoldReady,
+    /// 取り置きの受け取りの期限を過ぎたため、予約が取り消された
+    HoldExpired,
+}
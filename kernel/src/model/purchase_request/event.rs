@@ -0,0 +1,39 @@
+use derive_new::new;
+
+use crate::model::{
+    id::{PurchaseRequestId, UserId},
+    isbn::Isbn,
+    purchase_request::PurchaseRequestStatus,
+};
+
+#[derive(Debug)]
+pub struct CreatePurchaseRequest {
+    pub requested_user: UserId,
+    pub isbn: Isbn,
+    pub title: String,
+    pub author: String,
+    pub reason: String,
+    pub link: Option<String>,
+}
+
+#[derive(new)]
+pub struct VotePurchaseRequest {
+    pub purchase_request_id: PurchaseRequestId,
+    pub user_id: UserId,
+}
+
+#[derive(new)]
+pub struct UnvotePurchaseRequest {
+    pub purchase_request_id: PurchaseRequestId,
+    pub user_id: UserId,
+}
+
+/// 購入リクエストの状態を変える
+/// 受領済みにした場合は、リクエストの内容から書籍を登録する
+#[derive(Debug, new)]
+pub struct ChangePurchaseRequestStatus {
+    pub purchase_request_id: PurchaseRequestId,
+    pub status: PurchaseRequestStatus,
+    /// 状態を変えるユーザー。受領時に登録する書籍の所有者となる
+    pub requested_user: UserId,
+}
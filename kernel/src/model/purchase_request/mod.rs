@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
+
+use crate::model::{
+    id::{BookId, PurchaseRequestId, UserId},
+    isbn::Isbn,
+    user::PurchaseRequester,
+};
+
+pub mod event;
+
+/// 書籍の購入リクエスト
+#[derive(Debug)]
+pub struct PurchaseRequest {
+    pub id: PurchaseRequestId,
+    /// リクエストしたユーザー。ユーザーが削除済みの場合はNone
+    pub requested_by: Option<PurchaseRequester>,
+    pub isbn: Isbn,
+    pub title: String,
+    pub author: String,
+    /// 購入してほしい理由
+    pub reason: String,
+    /// 書籍の紹介ページなどのURL
+    pub link: Option<String>,
+    pub status: PurchaseRequestStatus,
+    /// 賛成票の数
+    pub votes: i64,
+    /// 一覧を取得したユーザーが賛成票を入れているかどうか
+    pub voted: bool,
+    /// 受領して登録した書籍。受領前の場合はNone
+    pub book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub ordered_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+/// 購入リクエストの状態
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PurchaseRequestStatus {
+    #[default]
+    Open,
+    Approved,
+    /// 発注済み
+    Ordered,
+    /// 受領済み。書籍として登録されている
+    Received,
+    Rejected,
+}
+
+impl PurchaseRequestStatus {
+    /// この状態から指定した状態に変更できるかどうかを返す
+    pub fn can_change_to(self, to: Self) -> bool {
+        use PurchaseRequestStatus::*;
+        matches!(
+            (self, to),
+            (Open, Approved)
+                | (Open, Rejected)
+                | (Approved, Ordered)
+                | (Approved, Rejected)
+                | (Ordered, Received)
+        )
+    }
+
+    /// 賛成票を入れられる状態かどうかを返す
+    pub fn is_votable(self) -> bool {
+        matches!(self, Self::Open | Self::Approved)
+    }
+}
+
+/// 購入リクエスト一覧の絞り込みとページネーションの範囲を指定するための設定値を格納する型
+#[derive(Debug)]
+pub struct PurchaseRequestListOptions {
+    pub status: Option<PurchaseRequestStatus>,
+    /// 一覧を取得するユーザー。賛成票を入れているかどうかの判定に使う
+    pub viewer: UserId,
+    pub limit: i64,
+    pub offset: i64,
+}
@@ -0,0 +1,31 @@
+use derive_new::new;
+
+use crate::model::id::{BookId, ReviewId, UserId};
+
+#[derive(new)]
+pub struct CreateReview {
+    pub book_id: BookId,
+    pub rating: i16,
+    pub comment: Option<String>,
+    pub requested_user: UserId,
+}
+
+#[derive(new)]
+pub struct UpdateReview {
+    pub book_id: BookId,
+    pub review_id: ReviewId,
+    pub rating: i16,
+    pub comment: Option<String>,
+    pub requested_user: UserId,
+    /// 管理者は他のユーザーのレビューも変更できる
+    pub requested_by_admin: bool,
+}
+
+#[derive(new)]
+pub struct DeleteReview {
+    pub book_id: BookId,
+    pub review_id: ReviewId,
+    pub requested_user: UserId,
+    /// 管理者は他のユーザーのレビューも削除できる
+    pub requested_by_admin: bool,
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::{
+    id::{BookId, ReviewId},
+    user::Reviewer,
+};
+
+pub mod event;
+
+/// 書籍の評価とレビュー。1人のユーザーは1冊につき1件だけ書ける
+#[derive(Debug)]
+pub struct Review {
+    pub id: ReviewId,
+    pub book_id: BookId,
+    pub reviewer: Reviewer,
+    /// 1〜5の評価
+    pub rating: i16,
+    /// 評価に添える感想。評価のみの場合はNone
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// レビュー一覧のページネーションの範囲を指定するための設定値を格納する型
+#[derive(Debug, Default)]
+pub struct ReviewListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
@@ -1,6 +1,6 @@
 use strum::{AsRefStr, EnumIter, EnumString};
 
-#[derive(Debug, EnumIter, EnumString, AsRefStr, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, EnumIter, EnumString, AsRefStr, Default, PartialEq, Eq)]
 pub enum Role {
     Admin,
     #[default]
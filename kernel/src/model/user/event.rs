@@ -1,4 +1,7 @@
-use crate::model::{id::UserId, role::Role};
+use crate::model::{
+    id::{BookId, UserId},
+    role::Role,
+};
 
 #[derive(Debug)]
 pub struct CreateUser {
@@ -13,6 +16,14 @@ pub struct UpdateUserRole {
     pub role: Role,
 }
 
+/// ユーザーの名前とメールアドレスのうち、指定された項目のみを更新する
+#[derive(Debug)]
+pub struct UpdateUserProfile {
+    pub id: UserId,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct UpdateUserPassword {
     pub id: UserId,
@@ -24,3 +35,17 @@ pub struct UpdateUserPassword {
 pub struct DeleteUser {
     pub id: UserId,
 }
+
+/// 書籍をお気に入りに登録する。登録済みの場合は何もしない
+#[derive(Debug)]
+pub struct AddFavoriteBook {
+    pub user_id: UserId,
+    pub book_id: BookId,
+}
+
+/// 書籍をお気に入りから外す。登録されていない場合は何もしない
+#[derive(Debug)]
+pub struct RemoveFavoriteBook {
+    pub user_id: UserId,
+    pub book_id: BookId,
+}
@@ -20,3 +20,24 @@ pub struct CheckoutUser {
     pub id: UserId,
     pub name: String,
 }
+
+/// 書籍の内容を変更したユーザー
+#[derive(Debug)]
+pub struct BookEditor {
+    pub id: UserId,
+    pub name: String,
+}
+
+/// 書籍のレビューを書いたユーザー
+#[derive(Debug)]
+pub struct Reviewer {
+    pub id: UserId,
+    pub name: String,
+}
+
+/// 書籍の購入をリクエストしたユーザー
+#[derive(Debug)]
+pub struct PurchaseRequester {
+    pub id: UserId,
+    pub name: String,
+}
@@ -0,0 +1,19 @@
+use derive_new::new;
+
+use crate::model::{
+    id::{UserId, WishlistItemId},
+    isbn::Isbn,
+};
+
+#[derive(new)]
+pub struct CreateWishlistItem {
+    pub user_id: UserId,
+    pub isbn: Option<Isbn>,
+    pub title: Option<String>,
+}
+
+#[derive(new)]
+pub struct DeleteWishlistItem {
+    pub user_id: UserId,
+    pub wishlist_item_id: WishlistItemId,
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::{
+    id::{BookId, WishlistItemId},
+    isbn::Isbn,
+};
+
+pub mod event;
+
+/// まだ登録されていない書籍を、ほしいものとして記録した項目
+/// ISBNと書名の少なくともどちらかを持つ
+#[derive(Debug)]
+pub struct WishlistItem {
+    pub id: WishlistItemId,
+    pub isbn: Option<Isbn>,
+    pub title: Option<String>,
+    /// ISBNが一致する書籍が登録された場合の、その書籍のID
+    pub fulfilled_book_id: Option<BookId>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,13 +1,22 @@
 //! 蔵書のDB操作のための抽象実装をするモジュール
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 
 use crate::model::{
     book::{
-        Book, BookListOptions,
-        event::{CreateBook, DeleteBook, UpdateBook},
+        Book, BookAdminAction, BookCatalogEntry, BookCover, BookImportResult, BookListOptions,
+        BookRevision, BookTransfer, CopyStatusChange, CoverSize, DeletedBook,
+        DeletedBookListOptions, TagCount,
+        event::{
+            AcceptBookTransfer, AddBookTags, ChangeCopyStatus, CreateBook, CreateBookCopy,
+            DeclineBookTransfer, DeleteBook, DeleteBookCopy, ImportBooks, MoveBook, PatchBook,
+            PurgeBook, RemoveBookTag, RestoreBook, RevertBook, SetBookLoanPeriod, TransferAllBooks,
+            TransferBook, UpdateBook, UploadBookCover,
+        },
     },
-    id::{BookId, UserId},
-    list::PaginatedList,
+    id::{BookId, CopyId, UserId},
+    isbn::Isbn,
+    list::{CursorPaginatedList, PaginatedList},
 };
 use shared::error::AppResult;
 
@@ -16,12 +25,82 @@ use shared::error::AppResult;
 pub trait BookRepository: Send + Sync {
     /// 書籍を登録する
     async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()>;
-    /// 書籍を全件取得する
+    /// 書籍を一括で登録し、行ごとの結果を返す
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>>;
+    /// 削除済みでない書籍を全件取得する
     async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>>;
-    /// 書籍を取得する
+    /// カーソルの位置から書籍を取得する（options.offsetは使用しない）
+    async fn find_all_by_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<String>,
+    ) -> AppResult<CursorPaginatedList<Book>>;
+    /// 全書籍の目録を、蔵書と貸出情報とともに1行ずつ取得する
+    /// 全件をメモリに載せずに済むよう、ストリームとして返す
+    fn stream_catalog(&self) -> BoxStream<'static, AppResult<BookCatalogEntry>>;
+    /// 書籍を取得する。削除済みの書籍はNoneとする
     async fn find_by_id(&self, book_id: BookId) -> AppResult<Option<Book>>;
-    /// 書籍を更新する
+    /// ISBNに一致する書籍をすべて取得する
+    async fn find_by_isbn(&self, isbn: Isbn) -> AppResult<Vec<Book>>;
+    /// 書籍を更新し、変更があれば変更履歴に記録する
+    /// 版が指定された場合、現在の版と異なれば更新せずにエラーとする
+    /// 管理者が所有者以外の書籍を更新した場合は、その操作を記録する
     async fn update(&self, event: UpdateBook) -> AppResult<()>;
-    /// 書籍を削除する
+    /// 書籍の指定された項目のみを更新し、変更があれば変更履歴に記録する
+    async fn patch(&self, event: PatchBook) -> AppResult<()>;
+    /// 書籍の変更履歴を、新しい順に取得する
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>>;
+    /// 書籍の内容を、指定した変更履歴の時点（変更後の値）に戻す
+    async fn revert(&self, event: RevertBook) -> AppResult<()>;
+    /// 書籍を削除済みにする。貸出中の書籍や、指定された版と現在の版が異なる書籍は削除できない
+    /// 管理者が所有者以外の書籍を削除した場合は、その操作を記録する
     async fn delete(&self, event: DeleteBook) -> AppResult<()>;
+    /// 書籍を他のユーザーに譲渡する
+    /// 譲渡先の承認が必要な場合は、承認待ちの譲渡を返す
+    async fn transfer(&self, event: TransferBook) -> AppResult<Option<BookTransfer>>;
+    /// 指定したユーザーが譲渡先となっている承認待ちの譲渡を、古い順に取得する
+    async fn find_incoming_transfers(&self, user_id: UserId) -> AppResult<Vec<BookTransfer>>;
+    /// 承認待ちの譲渡を承認し、書籍の所有者を譲渡先に変える
+    async fn accept_transfer(&self, event: AcceptBookTransfer) -> AppResult<()>;
+    /// 承認待ちの譲渡を拒否する
+    async fn decline_transfer(&self, event: DeclineBookTransfer) -> AppResult<()>;
+    /// ユーザーが所有するすべての書籍を譲渡し、譲渡した書籍の数を返す
+    async fn transfer_all(&self, event: TransferAllBooks) -> AppResult<u64>;
+    /// 蔵書の状態を変える
+    async fn change_copy_status(&self, event: ChangeCopyStatus) -> AppResult<()>;
+    /// 蔵書の状態の変更履歴を、古い順に取得する
+    async fn find_copy_status_history(
+        &self,
+        book_id: BookId,
+        copy_id: CopyId,
+    ) -> AppResult<Vec<CopyStatusChange>>;
+    /// 書籍の置き場所を移す
+    async fn move_to(&self, event: MoveBook) -> AppResult<()>;
+    /// 書籍の貸出期間を変える。変更後の貸出から新しい期間で返却期限を決める
+    async fn set_loan_period(&self, event: SetBookLoanPeriod) -> AppResult<()>;
+    /// 管理者が指定したユーザーの書籍に対して行った操作を、新しい順に取得する
+    async fn find_admin_actions(&self, owner: UserId) -> AppResult<Vec<BookAdminAction>>;
+    /// 削除済みの書籍を、削除日時の新しい順に取得する
+    async fn find_deleted(
+        &self,
+        options: DeletedBookListOptions,
+    ) -> AppResult<PaginatedList<DeletedBook>>;
+    /// 削除済みの書籍を復元する
+    async fn restore(&self, event: RestoreBook) -> AppResult<()>;
+    /// 削除済みの書籍を完全に削除する。書影の画像も削除する
+    async fn purge(&self, event: PurgeBook) -> AppResult<()>;
+    /// 書籍に蔵書を追加する
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<()>;
+    /// 書籍から蔵書を取り除く
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()>;
+    /// 書籍にタグを付ける
+    async fn add_tags(&self, event: AddBookTags) -> AppResult<()>;
+    /// 書籍からタグを外す
+    async fn remove_tag(&self, event: RemoveBookTag) -> AppResult<()>;
+    /// 書籍に付いているすべてのタグを、付いている書籍の数とともに取得する
+    async fn find_all_tags(&self) -> AppResult<Vec<TagCount>>;
+    /// 書籍の書影を登録し、サムネイルを生成する
+    async fn upload_cover(&self, event: UploadBookCover) -> AppResult<()>;
+    /// 書籍の書影を取得する。書影が登録されていない場合はNoneを返す
+    async fn find_cover(&self, book_id: BookId, size: CoverSize) -> AppResult<Option<BookCover>>;
 }
@@ -0,0 +1,14 @@
+//! 書誌情報の取得のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::{book::BookMetadata, isbn::Isbn};
+use shared::error::AppResult;
+
+/// ISBNから書誌情報を取得する提供元
+/// データダンプや外部のAPIなど、取得元ごとに実装を差し替えられるようにする
+#[mockall::automock]
+#[async_trait]
+pub trait BookMetadataProvider: Send + Sync {
+    /// ISBNに一致する書誌情報を取得する。見つからない場合はNoneを返す
+    async fn find_by_isbn(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>>;
+}
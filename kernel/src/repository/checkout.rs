@@ -4,9 +4,10 @@ use async_trait::async_trait;
 use crate::model::{
     checkout::{
         Checkout,
-        event::{CreateCheckout, UpdateReturned},
+        event::{CreateCheckout, RenewCheckout, UpdateReturned},
     },
     id::{BookId, UserId},
+    list::{CursorOptions, CursorPaginatedList},
 };
 use shared::error::AppResult;
 
@@ -17,8 +18,17 @@ pub trait CheckoutRepository: Send + Sync {
     async fn create(&self, event: CreateCheckout) -> AppResult<()>;
     /// 返却操作を行う
     async fn update_returned(&self, event: UpdateReturned) -> AppResult<()>;
+    /// 貸出を延長し、延長後の貸出情報を返す
+    async fn renew(&self, event: RenewCheckout) -> AppResult<Checkout>;
     /// 全ての未返却の貸出情報を取得する
-    async fn find_unreturned_all(&self) -> AppResult<Vec<Checkout>>;
+    /// overdue_onlyがtrueの場合は、返却期限を過ぎたもののみとする
+    async fn find_unreturned_all(&self, overdue_only: bool) -> AppResult<Vec<Checkout>>;
+    /// カーソルの位置から未返却の貸出情報を取得する
+    async fn find_unreturned_all_by_cursor(
+        &self,
+        options: CursorOptions,
+        overdue_only: bool,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
     /// ユーザーIDに紐づく未返却の貸出情報を取得する
     async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>>;
     /// 蔵書の貸出履歴（返却済みも含む）を取得する
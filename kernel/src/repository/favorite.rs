@@ -0,0 +1,25 @@
+//! お気に入りのDB操作のための抽象実装をするモジュール
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use crate::model::{
+    id::{BookId, UserId},
+    user::event::{AddFavoriteBook, RemoveFavoriteBook},
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait FavoriteRepository: Send + Sync {
+    /// 書籍をお気に入りに登録する
+    async fn add(&self, event: AddFavoriteBook) -> AppResult<()>;
+    /// 書籍をお気に入りから外す
+    async fn remove(&self, event: RemoveFavoriteBook) -> AppResult<()>;
+    /// 指定した書籍のうち、ユーザーがお気に入りに登録している書籍のIDを取得する
+    async fn find_favorited(
+        &self,
+        user_id: UserId,
+        book_ids: Vec<BookId>,
+    ) -> AppResult<HashSet<BookId>>;
+}
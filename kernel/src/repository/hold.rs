@@ -0,0 +1,25 @@
+//! 書籍の予約のDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::model::{
+    hold::{
+        Hold,
+        event::{CreateHold, DeleteHold},
+    },
+    id::UserId,
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait HoldRepository: Send + Sync {
+    /// 貸出中の書籍を予約し、予約の順番とともに返す
+    async fn create(&self, event: CreateHold) -> AppResult<Hold>;
+    /// ユーザーの予約を、予約した順に取得する
+    async fn find_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Hold>>;
+    /// 予約を取り消す。取り置き中の場合は次の予約者に回す
+    async fn delete(&self, event: DeleteHold) -> AppResult<()>;
+    /// 受け取りの期限を過ぎた取り置きを取り消し、空いた蔵書を次の予約者に回す
+    async fn advance(&self, now: DateTime<Utc>) -> AppResult<()>;
+}
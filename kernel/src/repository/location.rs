@@ -0,0 +1,21 @@
+//! 書籍の置き場所のDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::location::{
+    Location,
+    event::{CreateLocation, DeleteLocation, UpdateLocation},
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait LocationRepository: Send + Sync {
+    /// 置き場所を登録する
+    async fn create(&self, event: CreateLocation) -> AppResult<Location>;
+    /// すべての置き場所を、拠点・部屋・棚の名前順に取得する
+    async fn find_all(&self) -> AppResult<Vec<Location>>;
+    /// 置き場所の名前を変更する
+    async fn update(&self, event: UpdateLocation) -> AppResult<Location>;
+    /// 置き場所を削除する。書籍が置かれている場所は削除できない
+    async fn delete(&self, event: DeleteLocation) -> AppResult<()>;
+}
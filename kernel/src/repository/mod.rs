@@ -1,5 +1,13 @@
 pub mod auth;
 pub mod book;
+pub mod book_metadata;
 pub mod checkout;
+pub mod favorite;
 pub mod health;
+pub mod hold;
+pub mod location;
+pub mod notification;
+pub mod purchase_request;
+pub mod review;
 pub mod user;
+pub mod wishlist;
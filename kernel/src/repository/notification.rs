@@ -0,0 +1,17 @@
+//! 通知のDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::{
+    id::UserId,
+    notification::{Notification, event::MarkNotificationRead},
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    /// ユーザーへの通知を、新しい順に取得する
+    async fn find_all(&self, user_id: UserId) -> AppResult<Vec<Notification>>;
+    /// 通知を既読にする
+    async fn mark_read(&self, event: MarkNotificationRead) -> AppResult<()>;
+}
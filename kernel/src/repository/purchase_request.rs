@@ -0,0 +1,40 @@
+//! 書籍の購入リクエストのDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::{
+    id::{PurchaseRequestId, UserId},
+    list::PaginatedList,
+    purchase_request::{
+        PurchaseRequest, PurchaseRequestListOptions,
+        event::{
+            ChangePurchaseRequestStatus, CreatePurchaseRequest, UnvotePurchaseRequest,
+            VotePurchaseRequest,
+        },
+    },
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait PurchaseRequestRepository: Send + Sync {
+    /// 購入リクエストを登録する
+    async fn create(&self, event: CreatePurchaseRequest) -> AppResult<PurchaseRequest>;
+    /// 購入リクエストを、賛成票の多い順に取得する
+    async fn find_all(
+        &self,
+        options: PurchaseRequestListOptions,
+    ) -> AppResult<PaginatedList<PurchaseRequest>>;
+    /// 購入リクエストを取得する
+    async fn find_by_id(
+        &self,
+        purchase_request_id: PurchaseRequestId,
+        viewer: UserId,
+    ) -> AppResult<Option<PurchaseRequest>>;
+    /// 購入リクエストに賛成票を入れる。自分のリクエストには入れられない
+    async fn vote(&self, event: VotePurchaseRequest) -> AppResult<()>;
+    /// 購入リクエストへの賛成票を取り消す
+    async fn unvote(&self, event: UnvotePurchaseRequest) -> AppResult<()>;
+    /// 購入リクエストの状態を変え、変更後のリクエストを返す
+    async fn change_status(&self, event: ChangePurchaseRequestStatus)
+    -> AppResult<PurchaseRequest>;
+}
@@ -0,0 +1,29 @@
+//! 書籍のレビューのDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::{
+    id::BookId,
+    list::PaginatedList,
+    review::{
+        Review, ReviewListOptions,
+        event::{CreateReview, DeleteReview, UpdateReview},
+    },
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait ReviewRepository: Send + Sync {
+    /// 書籍にレビューを投稿する。同じ書籍に2件目のレビューは投稿できない
+    async fn create(&self, event: CreateReview) -> AppResult<Review>;
+    /// 書籍のレビューを、新しい順に取得する
+    async fn find_by_book(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<Review>>;
+    /// レビューを変更する。変更できるのは投稿者か管理者のみ
+    async fn update(&self, event: UpdateReview) -> AppResult<Review>;
+    /// レビューを削除する。削除できるのは投稿者か管理者のみ
+    async fn delete(&self, event: DeleteReview) -> AppResult<()>;
+}
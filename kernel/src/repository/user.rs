@@ -3,9 +3,10 @@ use async_trait::async_trait;
 
 use crate::model::{
     id::UserId,
+    list::{CursorOptions, CursorPaginatedList},
     user::{
         User,
-        event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole},
+        event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserProfile, UpdateUserRole},
     },
 };
 use shared::error::AppResult;
@@ -17,12 +18,19 @@ pub trait UserRepository: Send + Sync {
     async fn create(&self, event: CreateUser) -> AppResult<User>;
     /// ユーザーを全件取得する
     async fn find_all(&self) -> AppResult<Vec<User>>;
+    /// カーソルの位置からユーザーを取得する
+    async fn find_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<User>>;
     /// ユーザーを取得する
     async fn find_current_user(&self, current_user_id: UserId) -> AppResult<Option<User>>;
     /// ユーザーを削除する
     async fn delete(&self, event: DeleteUser) -> AppResult<()>;
     /// ユーザーのロールを更新する
     async fn update_role(&self, event: UpdateUserRole) -> AppResult<()>;
+    /// ユーザーの名前とメールアドレスを更新し、更新後のユーザーを返す
+    async fn update_profile(&self, event: UpdateUserProfile) -> AppResult<User>;
     /// ユーザーのパスワードを更新する
     async fn update_password(&self, event: UpdateUserPassword) -> AppResult<()>;
 }
@@ -0,0 +1,22 @@
+//! ほしいものリストのDB操作のための抽象実装をするモジュール
+use async_trait::async_trait;
+
+use crate::model::{
+    id::UserId,
+    wishlist::{
+        WishlistItem,
+        event::{CreateWishlistItem, DeleteWishlistItem},
+    },
+};
+use shared::error::AppResult;
+
+#[mockall::automock]
+#[async_trait]
+pub trait WishlistRepository: Send + Sync {
+    /// ほしいものリストに項目を追加する。登録済みの書籍のISBNは追加できない
+    async fn create(&self, event: CreateWishlistItem) -> AppResult<WishlistItem>;
+    /// ユーザーのほしいものリストを、追加した順に取得する
+    async fn find_all(&self, user_id: UserId) -> AppResult<Vec<WishlistItem>>;
+    /// ほしいものリストから項目を削除する
+    async fn delete(&self, event: DeleteWishlistItem) -> AppResult<()>;
+}
@@ -2,17 +2,27 @@ use std::sync::Arc;
 
 use adapter::{
     database::ConnectionPool,
+    metadata::{
+        DisabledBookMetadataProvider, dump::OpenLibraryDumpProvider, http::OpenLibraryHttpProvider,
+    },
     redis::RedisClient,
     repository::{
         auth::AuthRepositoryImpl, book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl,
-        health::HealthCheckRepositoryImpl, user::UserRepositoryImpl,
+        favorite::FavoriteRepositoryImpl, health::HealthCheckRepositoryImpl,
+        hold::HoldRepositoryImpl, location::LocationRepositoryImpl,
+        notification::NotificationRepositoryImpl, purchase_request::PurchaseRequestRepositoryImpl,
+        review::ReviewRepositoryImpl, user::UserRepositoryImpl, wishlist::WishlistRepositoryImpl,
     },
+    storage::local::LocalBlobStorage,
 };
 use kernel::repository::{
-    auth::AuthRepository, book::BookRepository, checkout::CheckoutRepository,
-    health::HealthCheckRepository, user::UserRepository,
+    auth::AuthRepository, book::BookRepository, book_metadata::BookMetadataProvider,
+    checkout::CheckoutRepository, favorite::FavoriteRepository, health::HealthCheckRepository,
+    hold::HoldRepository, location::LocationRepository, notification::NotificationRepository,
+    purchase_request::PurchaseRequestRepository, review::ReviewRepository, user::UserRepository,
+    wishlist::WishlistRepository,
 };
-use shared::config::AppConfig;
+use shared::config::{AppConfig, BookMetadataConfig};
 
 /// DIコンテナの構造体
 #[derive(Clone)]
@@ -22,26 +32,67 @@ pub struct AppRegistryImpl {
     auth_repository: Arc<dyn AuthRepository>,
     user_repository: Arc<dyn UserRepository>,
     checkout_repository: Arc<dyn CheckoutRepository>,
+    location_repository: Arc<dyn LocationRepository>,
+    review_repository: Arc<dyn ReviewRepository>,
+    favorite_repository: Arc<dyn FavoriteRepository>,
+    wishlist_repository: Arc<dyn WishlistRepository>,
+    notification_repository: Arc<dyn NotificationRepository>,
+    purchase_request_repository: Arc<dyn PurchaseRequestRepository>,
+    hold_repository: Arc<dyn HoldRepository>,
+    book_metadata_provider: Arc<dyn BookMetadataProvider>,
 }
 
 impl AppRegistryImpl {
     /// DIコンテナを作成する
     pub fn new(db: ConnectionPool, redis_client: Arc<RedisClient>, app_config: AppConfig) -> Self {
         let health_check_repository = Arc::new(HealthCheckRepositoryImpl::new(db.clone()));
-        let book_repository = Arc::new(BookRepositoryImpl::new(db.clone()));
+        let blob_storage = Arc::new(LocalBlobStorage::new(&app_config.storage));
+        let book_repository = Arc::new(BookRepositoryImpl::new(
+            db.clone(),
+            blob_storage,
+            app_config.checkout.hold_pickup_days,
+        ));
         let auth_repository = Arc::new(AuthRepositoryImpl::new(
             db.clone(),
             redis_client.clone(),
             app_config.auth.ttl,
         ));
         let user_repository = Arc::new(UserRepositoryImpl::new(db.clone()));
-        let checkout_repository = Arc::new(CheckoutRepositoryImpl::new(db.clone()));
+        let checkout_repository = Arc::new(CheckoutRepositoryImpl::new(
+            db.clone(),
+            app_config.checkout.loan_period_days,
+            app_config.checkout.max_renewals,
+            app_config.checkout.hold_pickup_days,
+        ));
+        let location_repository = Arc::new(LocationRepositoryImpl::new(db.clone()));
+        let review_repository = Arc::new(ReviewRepositoryImpl::new(db.clone()));
+        let favorite_repository = Arc::new(FavoriteRepositoryImpl::new(db.clone()));
+        let wishlist_repository = Arc::new(WishlistRepositoryImpl::new(db.clone()));
+        let notification_repository = Arc::new(NotificationRepositoryImpl::new(db.clone()));
+        let purchase_request_repository = Arc::new(PurchaseRequestRepositoryImpl::new(db.clone()));
+        let hold_repository = Arc::new(HoldRepositoryImpl::new(
+            db.clone(),
+            app_config.checkout.hold_pickup_days,
+        ));
+        let book_metadata_provider: Arc<dyn BookMetadataProvider> = match app_config.book_metadata {
+            BookMetadataConfig::Disabled => Arc::new(DisabledBookMetadataProvider),
+            BookMetadataConfig::Dump(path) => Arc::new(OpenLibraryDumpProvider::new(path)),
+            BookMetadataConfig::Http(url) => Arc::new(OpenLibraryHttpProvider::new(url)),
+        };
         Self {
             health_check_repository,
             book_repository,
             auth_repository,
             user_repository,
             checkout_repository,
+            location_repository,
+            review_repository,
+            favorite_repository,
+            wishlist_repository,
+            notification_repository,
+            purchase_request_repository,
+            hold_repository,
+            book_metadata_provider,
         }
     }
 }
@@ -58,6 +109,22 @@ pub trait AppRegistryExt {
     fn user_repository(&self) -> Arc<dyn UserRepository>;
     /// 貸出リポジトリを取得する
     fn checkout_repository(&self) -> Arc<dyn CheckoutRepository>;
+    /// 置き場所リポジトリを取得する
+    fn location_repository(&self) -> Arc<dyn LocationRepository>;
+    /// レビューリポジトリを取得する
+    fn review_repository(&self) -> Arc<dyn ReviewRepository>;
+    /// お気に入りリポジトリを取得する
+    fn favorite_repository(&self) -> Arc<dyn FavoriteRepository>;
+    /// ほしいものリストリポジトリを取得する
+    fn wishlist_repository(&self) -> Arc<dyn WishlistRepository>;
+    /// 通知リポジトリを取得する
+    fn notification_repository(&self) -> Arc<dyn NotificationRepository>;
+    /// 購入リクエストリポジトリを取得する
+    fn purchase_request_repository(&self) -> Arc<dyn PurchaseRequestRepository>;
+    /// 予約リポジトリを取得する
+    fn hold_repository(&self) -> Arc<dyn HoldRepository>;
+    /// 書誌情報の提供元を取得する
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider>;
 }
 
 impl AppRegistryExt for AppRegistryImpl {
@@ -80,6 +147,38 @@ impl AppRegistryExt for AppRegistryImpl {
     fn checkout_repository(&self) -> Arc<dyn CheckoutRepository> {
         self.checkout_repository.clone()
     }
+
+    fn location_repository(&self) -> Arc<dyn LocationRepository> {
+        self.location_repository.clone()
+    }
+
+    fn review_repository(&self) -> Arc<dyn ReviewRepository> {
+        self.review_repository.clone()
+    }
+
+    fn favorite_repository(&self) -> Arc<dyn FavoriteRepository> {
+        self.favorite_repository.clone()
+    }
+
+    fn wishlist_repository(&self) -> Arc<dyn WishlistRepository> {
+        self.wishlist_repository.clone()
+    }
+
+    fn notification_repository(&self) -> Arc<dyn NotificationRepository> {
+        self.notification_repository.clone()
+    }
+
+    fn purchase_request_repository(&self) -> Arc<dyn PurchaseRequestRepository> {
+        self.purchase_request_repository.clone()
+    }
+
+    fn hold_repository(&self) -> Arc<dyn HoldRepository> {
+        self.hold_repository.clone()
+    }
+
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider> {
+        self.book_metadata_provider.clone()
+    }
 }
 
 pub type AppRegistry = Arc<dyn AppRegistryExt + Send + Sync + 'static>;
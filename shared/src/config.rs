@@ -1,11 +1,16 @@
 //! アプリケーション全体で使用する設定を定義する
 
+use std::path::PathBuf;
+
 use anyhow::Result;
 
 pub struct AppConfig {
     pub database: DatabaseConfig,
     pub redis: RedisConfig,
     pub auth: AuthConfig,
+    pub storage: StorageConfig,
+    pub book_metadata: BookMetadataConfig,
+    pub checkout: CheckoutConfig,
 }
 
 impl AppConfig {
@@ -24,10 +29,45 @@ impl AppConfig {
         let auth = AuthConfig {
             ttl: std::env::var("AUTH_TOKEN_TTL")?.parse::<u64>()?,
         };
+        let optional_var = |key| std::env::var(key).ok().filter(|v| !v.is_empty());
+        // 保存先は任意で、未指定の場合は既定のディレクトリとする
+        let storage = StorageConfig {
+            root: optional_var("BLOB_STORAGE_PATH")
+                .unwrap_or_else(|| StorageConfig::DEFAULT_ROOT.into())
+                .into(),
+        };
+        // データダンプのパスとAPIのURLはどちらも任意で、両方ある場合はデータダンプを優先する
+        let book_metadata = match (
+            optional_var("BOOK_METADATA_DUMP_PATH"),
+            optional_var("BOOK_METADATA_API_URL"),
+        ) {
+            (Some(path), _) => BookMetadataConfig::Dump(path.into()),
+            (None, Some(url)) => BookMetadataConfig::Http(url),
+            (None, None) => BookMetadataConfig::Disabled,
+        };
+        // 貸出期間と延長回数の上限、取り置きの日数は任意で、未指定の場合は既定値とする
+        let checkout = CheckoutConfig {
+            loan_period_days: optional_var("CHECKOUT_LOAN_PERIOD_DAYS")
+                .map(|v| v.parse::<i32>())
+                .transpose()?
+                .unwrap_or(CheckoutConfig::DEFAULT_LOAN_PERIOD_DAYS),
+            max_renewals: optional_var("CHECKOUT_MAX_RENEWALS")
+                .map(|v| v.parse::<i32>())
+                .transpose()?
+                .unwrap_or(CheckoutConfig::DEFAULT_MAX_RENEWALS),
+            hold_pickup_days: optional_var("CHECKOUT_HOLD_PICKUP_DAYS")
+                .map(|v| v.parse::<i32>())
+                .transpose()?
+                .unwrap_or(CheckoutConfig::DEFAULT_HOLD_PICKUP_DAYS),
+        };
+        checkout.validate()?;
         Ok(Self {
             database,
             redis,
             auth,
+            storage,
+            book_metadata,
+            checkout,
         })
     }
 }
@@ -51,3 +91,59 @@ pub struct RedisConfig {
 pub struct AuthConfig {
     pub ttl: u64,
 }
+
+// 書影などのファイルの保存先を表す構造体
+pub struct StorageConfig {
+    pub root: PathBuf,
+}
+
+impl StorageConfig {
+    pub const DEFAULT_ROOT: &'static str = "./storage";
+}
+
+// ISBNから書誌情報を取得する提供元を表す列挙型
+pub enum BookMetadataConfig {
+    /// 書誌情報を取得しない
+    Disabled,
+    /// OpenLibraryのデータダンプ（editionsとauthorsを含むTSV）のパス
+    Dump(PathBuf),
+    /// OpenLibrary互換のAPIのベースURL
+    Http(String),
+}
+
+// 貸出の設定を表す構造体
+pub struct CheckoutConfig {
+    /// 書籍ごとの指定がない場合の貸出期間（日数）
+    pub loan_period_days: i32,
+    /// 1回の貸出で延長できる回数の上限
+    pub max_renewals: i32,
+    /// 予約した書籍を取り置いておく日数
+    pub hold_pickup_days: i32,
+}
+
+impl CheckoutConfig {
+    pub const DEFAULT_LOAN_PERIOD_DAYS: i32 = 14;
+    pub const DEFAULT_MAX_RENEWALS: i32 = 2;
+    pub const DEFAULT_HOLD_PICKUP_DAYS: i32 = 3;
+
+    /// 日数は1日以上、延長回数の上限は0回以上であることを確認する
+    /// 0日以下の貸出期間では貸出した時点で延滞となり、取り置きはすぐに期限切れとなるため
+    fn validate(&self) -> Result<()> {
+        anyhow::ensure!(
+            self.loan_period_days >= 1,
+            "CHECKOUT_LOAN_PERIOD_DAYS must be at least 1, got {}",
+            self.loan_period_days
+        );
+        anyhow::ensure!(
+            self.max_renewals >= 0,
+            "CHECKOUT_MAX_RENEWALS must not be negative, got {}",
+            self.max_renewals
+        );
+        anyhow::ensure!(
+            self.hold_pickup_days >= 1,
+            "CHECKOUT_HOLD_PICKUP_DAYS must be at least 1, got {}",
+            self.hold_pickup_days
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use axum::{http::StatusCode, response::IntoResponse};
+use axum::{Json, http::StatusCode, response::IntoResponse};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -29,18 +29,51 @@ pub enum AppError {
     ForbiddenOperationError,
     #[error("{0}")]
     ConversionEntityError(String),
+    #[error("不正なカーソルが指定されました")]
+    InvalidCursorError,
+    #[error("{0}")]
+    PreconditionFailed(String),
+    #[error("{0}")]
+    PayloadTooLarge(String),
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    #[error("{0}")]
+    ExternalServiceError(String),
+    #[error("ファイルの読み書き中にエラーが発生しました")]
+    BlobStorageError(#[source] std::io::Error),
 }
 
 /// Errorをレスポンスに変換するためのトレイト
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
+        // 検証エラーの場合は、どのフィールドがなぜ不正なのかをレスポンスボディで返す
+        if let AppError::ValidationError(report) = &self {
+            let errors = report
+                .iter()
+                .map(|(path, error)| {
+                    serde_json::json!({
+                        "field": path.to_string(),
+                        "message": error.message(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "errors": errors })),
+            )
+                .into_response();
+        }
+
         // エラーの種類に応じて、適切なHTTPステータスコードを返す
         let status_code = match self {
             AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             AppError::EntityNotFound(_) => StatusCode::NOT_FOUND,
-            AppError::ValidationError(_) | AppError::ConvertToUuidError(_) => {
-                StatusCode::BAD_REQUEST
-            }
+            AppError::ValidationError(_)
+            | AppError::ConvertToUuidError(_)
+            | AppError::InvalidCursorError => StatusCode::BAD_REQUEST,
             AppError::UnauthenticatedError | AppError::ForbiddenOperationError => {
                 StatusCode::FORBIDDEN
             }
@@ -50,7 +83,8 @@ impl IntoResponse for AppError {
             | AppError::NoRowsAffectedError(_)
             | AppError::KeyValueStoreError(_)
             | AppError::BcryptError(_)
-            | AppError::ConversionEntityError(_)) => {
+            | AppError::ConversionEntityError(_)
+            | AppError::BlobStorageError(_)) => {
                 tracing::error!(
                                     error.cause_chain = ?e,
                                     error.message = %e,
@@ -58,6 +92,13 @@ impl IntoResponse for AppError {
                 );
                 StatusCode::INTERNAL_SERVER_ERROR
             }
+            e @ AppError::ExternalServiceError(_) => {
+                tracing::error!(
+                    error.message = %e,
+                    "External service error occurred"
+                );
+                StatusCode::BAD_GATEWAY
+            }
         };
         status_code.into_response()
     }
@@ -1,16 +1,25 @@
 use std::{
+    fs::File,
     net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use adapter::{database::connect_database_with, redis::RedisClient};
 use anyhow::{Context, Result};
+use api::model::book_import::{BookImportReportResponse, BookImportStatusName, parse_book_csv};
 #[cfg(debug_assertions)]
 use api::openapi::ApiDoc;
 use api::route::{auth::build_auth_routers, v1};
-use axum::{Router, http::Method};
+use axum::{
+    Router,
+    http::{Method, header},
+};
+use clap::{Parser, Subcommand};
+use kernel::model::book::event::ImportBooks;
 use opentelemetry::global;
-use registry::AppRegistryImpl;
+use registry::{AppRegistryExt, AppRegistryImpl};
 use shared::config::AppConfig;
 use shared::env::{Environment, which};
 use tokio::net::TcpListener;
@@ -28,10 +37,46 @@ use utoipa::OpenApi;
 #[cfg(debug_assertions)]
 use utoipa_redoc::{Redoc, Servable};
 
+/// 受け取りの期限を過ぎた取り置きを確認する間隔
+const HOLD_ADVANCE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// サーバーを起動する（サブコマンドを省略した場合も同様）
+    Serve,
+    /// CSVファイルから書籍を一括で登録する
+    ImportBooks {
+        /// title, author, isbn, description, owner_email列を持つCSVファイル
+        path: PathBuf,
+        /// owner_emailが空の行の所有者のメールアドレス
+        #[arg(long)]
+        owner_email: Option<String>,
+        /// 登録せずに結果だけを表示する
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    init_logger()?;
-    bootstrap().await
+    match Cli::parse().command {
+        None | Some(Command::Serve) => {
+            init_logger()?;
+            bootstrap().await
+        }
+        Some(Command::ImportBooks {
+            path,
+            owner_email,
+            dry_run,
+        }) => import_books(path, owner_email, dry_run).await,
+    }
 }
 
 /// ロガーを初期化する関数
@@ -93,6 +138,9 @@ async fn bootstrap() -> Result<()> {
     // AppRegistry(DIコンテナ)を作成
     let registry = Arc::new(AppRegistryImpl::new(pool, kv, app_config));
 
+    // 受け取りの期限を過ぎた取り置きを定期的に取り消し、次の予約者に回す
+    tokio::spawn(advance_holds(registry.clone()));
+
     // ヘルスチェック用のルーターを作成
     // ルーターのStateにAppRegistryを登録し、各ハンドラで使えるようにする
     let app = Router::new()
@@ -135,10 +183,75 @@ async fn bootstrap() -> Result<()> {
         })
 }
 
+/// 予約の取り置きを定期的に進める関数
+async fn advance_holds(registry: Arc<AppRegistryImpl>) {
+    let mut interval = tokio::time::interval(HOLD_ADVANCE_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = registry.hold_repository().advance(chrono::Utc::now()).await {
+            tracing::error!(
+                error.message = %e,
+                "Failed to advance holds"
+            );
+        }
+    }
+}
+
+/// CSVファイルから書籍を一括で登録するサブコマンドの関数
+async fn import_books(path: PathBuf, owner_email: Option<String>, dry_run: bool) -> Result<()> {
+    let app_config = AppConfig::new()?;
+    let pool = connect_database_with(&app_config.database);
+    let kv = Arc::new(RedisClient::new(&app_config.redis)?);
+    let registry = AppRegistryImpl::new(pool, kv, app_config);
+
+    let file = File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let (mut rows, invalid) = parse_book_csv(file);
+    // 所有者の指定がない行は、引数で指定したメールアドレスのユーザーの所有とする
+    for row in rows.iter_mut().filter(|row| row.owner_email.is_none()) {
+        row.owner_email = owner_email.clone();
+    }
+
+    let results = registry
+        .book_repository()
+        .import(ImportBooks {
+            rows,
+            default_owner: None,
+            dry_run,
+        })
+        .await?;
+    let report = BookImportReportResponse::new(invalid.into_iter().chain(results), dry_run);
+
+    for row in report
+        .rows
+        .iter()
+        .filter(|row| row.status != BookImportStatusName::Created)
+    {
+        let message = row.message.as_deref().unwrap_or_default();
+        println!("line {}: {:?} {}", row.line, row.status, message);
+    }
+    println!(
+        "{}created: {}, skipped: {}, invalid: {}",
+        if dry_run { "[dry run] " } else { "" },
+        report.created,
+        report.skipped,
+        report.invalid
+    );
+
+    Ok(())
+}
+
 fn cors() -> CorsLayer {
     CorsLayer::new()
         .allow_headers(cors::Any)
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        // If-Matchに使うETagをブラウザのクライアントからも読めるようにする
+        .expose_headers([header::ETAG])
         .allow_origin(cors::Any)
 }
 